@@ -0,0 +1,389 @@
+//! Boards, sprints and backlogs through the Jira Software Agile API
+//! (`/rest/agile/1.0`), which is the same on Cloud and Data Center.
+//!
+//! Reference: https://developer.atlassian.com/cloud/jira/software/rest/intro/
+
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+
+use super::error::Result;
+use super::{
+    AgilePage, Board, ISSUE_LIST_FIELDS, JiraClient, MoveIssuesRequest, SearchResult, Sprint,
+    SprintRequest,
+};
+
+/// Largest page size the Agile API accepts
+const AGILE_PAGE_SIZE: u32 = 50;
+
+/// Most issues the Agile API moves in one request
+const MOVE_ISSUES_BATCH: usize = 50;
+
+impl JiraClient {
+    fn agile_url(&self, path: &str) -> String {
+        format!("{}/rest/agile/1.0/{}", self.base_url, path)
+    }
+
+    /// List boards, optionally only those of a project, of a type ("scrum",
+    /// "kanban") or whose name contains `name`.
+    pub async fn get_boards(
+        &self,
+        project_key: Option<&str>,
+        board_type: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<Vec<Board>> {
+        let query: Vec<(&str, &str)> = [
+            ("projectKeyOrId", project_key),
+            ("type", board_type),
+            ("name", name),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|v| (key, v)))
+        .collect();
+
+        self.agile_values(&self.agile_url("board"), &query).await
+    }
+
+    /// List a board's sprints, optionally filtered by state
+    /// (comma-separated "future", "active", "closed").
+    pub async fn get_sprints(&self, board_id: u64, state: Option<&str>) -> Result<Vec<Sprint>> {
+        let url = self.agile_url(&format!("board/{}/sprint", board_id));
+        let query: Vec<(&str, &str)> = state.map(|s| ("state", s)).into_iter().collect();
+
+        self.agile_values(&url, &query).await
+    }
+
+    pub async fn get_sprint(&self, sprint_id: u64) -> Result<Sprint> {
+        let url = self.agile_url(&format!("sprint/{}", sprint_id));
+
+        self.get_json(&url).await
+    }
+
+    /// Get up to `limit` issues in a sprint, in rank order.
+    pub async fn get_sprint_issues(&self, sprint_id: u64, limit: u32) -> Result<SearchResult> {
+        let url = self.agile_url(&format!("sprint/{}/issue", sprint_id));
+
+        self.agile_issues(&url, limit).await
+    }
+
+    /// Get up to `limit` issues in a board's backlog, in rank order.
+    pub async fn get_backlog(&self, board_id: u64, limit: u32) -> Result<SearchResult> {
+        let url = self.agile_url(&format!("board/{}/backlog", board_id));
+
+        self.agile_issues(&url, limit).await
+    }
+
+    /// Move issues into a sprint.
+    pub async fn move_issues_to_sprint(&self, sprint_id: u64, issue_keys: &[String]) -> Result<()> {
+        let url = self.agile_url(&format!("sprint/{}/issue", sprint_id));
+
+        self.move_issues(&url, issue_keys).await
+    }
+
+    /// Move issues out of any sprint into the backlog.
+    pub async fn move_issues_to_backlog(&self, issue_keys: &[String]) -> Result<()> {
+        let url = self.agile_url("backlog/issue");
+
+        self.move_issues(&url, issue_keys).await
+    }
+
+    /// Create a future sprint.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let request = SprintRequest::create(42, "Sprint 7").goal("Ship search");
+    /// let sprint = client.create_sprint(request).await?;
+    /// ```
+    pub async fn create_sprint(&self, request: SprintRequest) -> Result<Sprint> {
+        let url = self.agile_url("sprint");
+
+        self.send_json(Method::POST, &url, &request).await
+    }
+
+    /// Change a sprint's name, dates, goal or state. Only the fields set in
+    /// `request` are changed, so this also starts and closes sprints.
+    pub async fn update_sprint(&self, sprint_id: u64, request: SprintRequest) -> Result<Sprint> {
+        let url = self.agile_url(&format!("sprint/{}", sprint_id));
+
+        self.send_json(Method::POST, &url, &request).await
+    }
+
+    /// Collect every value of a paged Agile API list.
+    async fn agile_values<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>> {
+        let mut values = Vec::new();
+        loop {
+            let request = self.request(Method::GET, url).query(query).query(&[
+                ("startAt", values.len() as u32),
+                ("maxResults", AGILE_PAGE_SIZE),
+            ]);
+            let page: AgilePage<T> = self.decode(self.send(request).await?).await?;
+            let page_len = page.values.len();
+            values.extend(page.values);
+
+            let is_last = page
+                .is_last
+                .unwrap_or_else(|| page.total.is_none_or(|total| values.len() as u32 >= total));
+            if is_last || page_len == 0 {
+                return Ok(values);
+            }
+        }
+    }
+
+    /// Collect up to `limit` issues from a paged Agile API issue list.
+    async fn agile_issues(&self, url: &str, limit: u32) -> Result<SearchResult> {
+        let mut issues = Vec::new();
+        let mut total = None;
+        loop {
+            let remaining = limit.saturating_sub(issues.len() as u32);
+            if remaining == 0 {
+                break;
+            }
+
+            let request = self.request(Method::GET, url).query(&[
+                ("startAt", issues.len().to_string()),
+                ("maxResults", remaining.min(AGILE_PAGE_SIZE).to_string()),
+                ("fields", ISSUE_LIST_FIELDS.join(",")),
+            ]);
+            let page: SearchResult = self.decode(self.send(request).await?).await?;
+            let page_len = page.issues.len();
+            issues.extend(page.issues);
+            total = page.total;
+
+            if page_len == 0 || total.is_some_and(|total| issues.len() as u32 >= total) {
+                break;
+            }
+        }
+
+        let is_last = total.is_none_or(|total| issues.len() as u32 >= total);
+        Ok(SearchResult {
+            total,
+            max_results: Some(limit),
+            start_at: Some(0),
+            next_page_token: None,
+            is_last: Some(is_last),
+            issues,
+        })
+    }
+
+    async fn move_issues(&self, url: &str, issue_keys: &[String]) -> Result<()> {
+        for batch in issue_keys.chunks(MOVE_ISSUES_BATCH) {
+            let request = MoveIssuesRequest {
+                issues: batch.to_vec(),
+            };
+            self.send_json::<()>(Method::POST, url, &request).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::{Deployment, JiraError};
+    use wiremock::matchers::{body_json, body_partial_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> JiraClient {
+        JiraClient::new(&server.uri(), "test@example.com", "test-token")
+    }
+
+    fn issue(key: &str, status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": key,
+            "key": key,
+            "self": format!("https://example.atlassian.net/rest/api/3/issue/{}", key),
+            "fields": { "summary": key, "status": { "name": status } }
+        })
+    }
+
+    #[tokio::test]
+    async fn get_boards_filters_by_project_and_pages() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/board"))
+            .and(query_param("projectKeyOrId", "PROJ"))
+            .and(query_param("startAt", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 0, "maxResults": 1, "total": 2, "isLast": false,
+                "values": [{ "id": 1, "name": "PROJ board", "type": "scrum",
+                             "location": { "projectKey": "PROJ", "displayName": "Project" } }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/board"))
+            .and(query_param("startAt", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 1, "maxResults": 1, "total": 2, "isLast": true,
+                "values": [{ "id": 2, "name": "PROJ kanban", "type": "kanban" }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let boards = client(&mock_server)
+            .get_boards(Some("PROJ"), None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].board_type, "scrum");
+        assert_eq!(boards[1].id, 2);
+    }
+
+    #[tokio::test]
+    async fn get_sprints_filters_by_state() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/board/7/sprint"))
+            .and(query_param("state", "active"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "isLast": true,
+                "values": [{ "id": 12, "name": "Sprint 3", "state": "active",
+                             "startDate": "2025-01-06T09:00:00.000Z", "goal": "Ship search" }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let sprints = client(&mock_server)
+            .get_sprints(7, Some("active"))
+            .await
+            .unwrap();
+
+        assert_eq!(sprints.len(), 1);
+        assert_eq!(sprints[0].goal.as_deref(), Some("Ship search"));
+    }
+
+    #[tokio::test]
+    async fn get_sprint_issues_pages_up_to_limit() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/sprint/12/issue"))
+            .and(query_param("startAt", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 0, "maxResults": 2, "total": 5,
+                "issues": [issue("PROJ-1", "Done"), issue("PROJ-2", "To Do")]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/sprint/12/issue"))
+            .and(query_param("startAt", "2"))
+            .and(query_param("maxResults", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 2, "maxResults": 1, "total": 5,
+                "issues": [issue("PROJ-3", "In Progress")]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let result = client(&mock_server).get_sprint_issues(12, 3).await.unwrap();
+
+        assert_eq!(result.issues.len(), 3);
+        assert_eq!(result.total, Some(5));
+        assert_eq!(result.is_last, Some(false));
+    }
+
+    #[tokio::test]
+    async fn move_issues_to_sprint_sends_batches_of_fifty() {
+        let mock_server = MockServer::start().await;
+        let keys: Vec<String> = (1..=60).map(|n| format!("PROJ-{}", n)).collect();
+
+        Mock::given(method("POST"))
+            .and(path("/rest/agile/1.0/sprint/12/issue"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/agile/1.0/backlog/issue"))
+            .and(body_json(serde_json::json!({ "issues": ["PROJ-1"] })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = client(&mock_server);
+        client.move_issues_to_sprint(12, &keys).await.unwrap();
+        client
+            .move_issues_to_backlog(&["PROJ-1".to_string()])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn create_and_start_sprint() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/agile/1.0/sprint"))
+            .and(body_json(serde_json::json!({
+                "name": "Sprint 4",
+                "originBoardId": 7,
+                "goal": "Ship search"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": 13, "name": "Sprint 4", "state": "future", "originBoardId": 7
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/agile/1.0/sprint/13"))
+            .and(body_partial_json(serde_json::json!({
+                "state": "active",
+                "endDate": "2025-01-20T09:00:00.000Z"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 13, "name": "Sprint 4", "state": "active"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = client(&mock_server);
+        let created = client
+            .create_sprint(SprintRequest::create(7, "Sprint 4").goal("Ship search"))
+            .await
+            .unwrap();
+        assert_eq!(created.state, "future");
+
+        let started = client
+            .update_sprint(
+                created.id,
+                SprintRequest::start()
+                    .start_date("2025-01-06T09:00:00.000Z")
+                    .end_date("2025-01-20T09:00:00.000Z"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(started.state, "active");
+    }
+
+    #[tokio::test]
+    async fn data_center_uses_the_same_agile_path() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/sprint/99"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "errorMessages": ["Sprint does not exist"]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat")
+            .with_deployment(Deployment::DataCenter);
+
+        let error = client.get_sprint(99).await.unwrap_err();
+
+        assert!(matches!(error, JiraError::NotFound(_)));
+    }
+}
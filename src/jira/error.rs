@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+
+use super::retry::server_delay;
+
+pub type Result<T, E = JiraError> = std::result::Result<T, E>;
+
+/// Errors returned by `JiraClient`.
+#[derive(Debug)]
+pub enum JiraError {
+    /// 401: credentials are missing, invalid or expired
+    Authentication(String),
+    /// Credentials could not be obtained locally (e.g. OAuth refresh failed)
+    Credentials(String),
+    /// 403: authenticated, but not allowed to do this
+    PermissionDenied(String),
+    /// 404: the issue, project or resource does not exist (or is not visible)
+    NotFound(String),
+    /// 400: Jira rejected the input
+    Validation {
+        status: StatusCode,
+        /// General messages from `errorMessages`
+        messages: Vec<String>,
+        /// Per-field messages from `errors`, keyed by field ID
+        fields: BTreeMap<String, String>,
+    },
+    /// 429: too many requests
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// 5xx
+    Server { status: StatusCode, message: String },
+    /// Any other unexpected status
+    Api { status: StatusCode, message: String },
+    /// The request could not be sent or the response not received
+    Transport(reqwest::Error),
+    /// The response body did not have the expected shape
+    Decode(String),
+    /// The response body exceeded the configured size limit
+    ResponseTooLarge { limit: usize },
+}
+
+/// Jira's standard error body: `{"errorMessages": [...], "errors": {"field": "message"}}`
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorBody {
+    #[serde(default)]
+    error_messages: Vec<String>,
+    #[serde(default)]
+    errors: BTreeMap<String, serde_json::Value>,
+}
+
+impl JiraError {
+    /// Classify a non-success response, consuming its body.
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let retry_after = server_delay(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        Self::from_parts(status, retry_after, &body)
+    }
+
+    fn from_parts(status: StatusCode, retry_after: Option<Duration>, body: &str) -> Self {
+        let parsed = serde_json::from_str::<ErrorBody>(body).unwrap_or_default();
+        let fields: BTreeMap<String, String> = parsed
+            .errors
+            .into_iter()
+            .map(|(field, message)| match message {
+                serde_json::Value::String(s) => (field, s),
+                other => (field, other.to_string()),
+            })
+            .collect();
+
+        let message = if parsed.error_messages.is_empty() && fields.is_empty() {
+            match body.trim() {
+                "" => status.canonical_reason().unwrap_or_default().to_string(),
+                text => text.to_string(),
+            }
+        } else {
+            parsed
+                .error_messages
+                .iter()
+                .cloned()
+                .chain(
+                    fields
+                        .iter()
+                        .map(|(field, msg)| format!("{}: {}", field, msg)),
+                )
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Authentication(message),
+            StatusCode::FORBIDDEN => Self::PermissionDenied(message),
+            StatusCode::NOT_FOUND => Self::NotFound(message),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited {
+                retry_after,
+                message,
+            },
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::Validation {
+                status,
+                messages: if parsed.error_messages.is_empty() && fields.is_empty() {
+                    vec![message]
+                } else {
+                    parsed.error_messages
+                },
+                fields,
+            },
+            s if s.is_server_error() => Self::Server { status, message },
+            _ => Self::Api { status, message },
+        }
+    }
+}
+
+impl fmt::Display for JiraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Authentication(message) => {
+                write!(
+                    f,
+                    "Jira authentication failed (401 Unauthorized): {}",
+                    message
+                )
+            }
+            Self::Credentials(message) => write!(f, "Could not obtain credentials: {}", message),
+            Self::PermissionDenied(message) => {
+                write!(f, "Permission denied (403 Forbidden): {}", message)
+            }
+            Self::NotFound(message) => write!(f, "Not found (404 Not Found): {}", message),
+            Self::Validation {
+                status,
+                messages,
+                fields,
+            } => {
+                let details: Vec<String> = messages
+                    .iter()
+                    .cloned()
+                    .chain(
+                        fields
+                            .iter()
+                            .map(|(field, msg)| format!("{}: {}", field, msg)),
+                    )
+                    .collect();
+                write!(
+                    f,
+                    "Jira rejected the request ({}): {}",
+                    status,
+                    details.join("; ")
+                )
+            }
+            Self::RateLimited {
+                retry_after,
+                message,
+            } => match retry_after {
+                Some(delay) => write!(
+                    f,
+                    "Rate limited by Jira (429 Too Many Requests), retry after {}s: {}",
+                    delay.as_secs(),
+                    message
+                ),
+                None => write!(
+                    f,
+                    "Rate limited by Jira (429 Too Many Requests): {}",
+                    message
+                ),
+            },
+            Self::Server { status, message } => {
+                write!(f, "Jira server error ({}): {}", status, message)
+            }
+            Self::Api { status, message } => write!(f, "Jira API error ({}): {}", status, message),
+            Self::Transport(e) => write!(f, "Could not reach Jira: {}", e),
+            Self::Decode(message) => write!(f, "Unexpected response from Jira: {}", message),
+            Self::ResponseTooLarge { limit } => {
+                write!(f, "Jira response exceeded the {} byte limit", limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JiraError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transport(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for JiraError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            Self::Decode(e.to_string())
+        } else {
+            Self::Transport(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_error_parses_messages_and_fields() {
+        let body = r#"{
+            "errorMessages": ["Issue type is invalid"],
+            "errors": {"summary": "You must specify a summary of the issue.", "priority": "Priority name 'Urgent' is not valid"}
+        }"#;
+
+        let error = JiraError::from_parts(StatusCode::BAD_REQUEST, None, body);
+
+        let JiraError::Validation {
+            messages, fields, ..
+        } = &error
+        else {
+            panic!("expected validation error, got {:?}", error);
+        };
+        assert_eq!(messages, &vec!["Issue type is invalid".to_string()]);
+        assert_eq!(
+            fields["summary"],
+            "You must specify a summary of the issue."
+        );
+        assert_eq!(fields.len(), 2);
+        assert!(error.to_string().contains("400"));
+        assert!(error.to_string().contains("priority: Priority name"));
+    }
+
+    #[test]
+    fn plain_text_bodies_become_messages() {
+        let error = JiraError::from_parts(StatusCode::BAD_REQUEST, None, "Bad Request");
+
+        assert!(matches!(
+            &error,
+            JiraError::Validation { messages, fields, .. }
+                if messages == &vec!["Bad Request".to_string()] && fields.is_empty()
+        ));
+    }
+
+    #[test]
+    fn statuses_map_to_variants() {
+        let error = JiraError::from_parts(
+            StatusCode::NOT_FOUND,
+            None,
+            r#"{"errorMessages":["Issue does not exist or you do not have permission to see it."]}"#,
+        );
+        assert!(matches!(
+            &error,
+            JiraError::NotFound(message) if message.starts_with("Issue does not exist")
+        ));
+
+        assert!(matches!(
+            JiraError::from_parts(StatusCode::UNAUTHORIZED, None, ""),
+            JiraError::Authentication(message) if message == "Unauthorized"
+        ));
+        assert!(matches!(
+            JiraError::from_parts(StatusCode::FORBIDDEN, None, "nope"),
+            JiraError::PermissionDenied(_)
+        ));
+        assert!(matches!(
+            JiraError::from_parts(StatusCode::SERVICE_UNAVAILABLE, None, "down"),
+            JiraError::Server { .. }
+        ));
+        assert!(matches!(
+            JiraError::from_parts(StatusCode::CONFLICT, None, "conflict"),
+            JiraError::Api { .. }
+        ));
+    }
+
+    #[test]
+    fn rate_limit_keeps_retry_after() {
+        let error = JiraError::from_parts(
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(Duration::from_secs(30)),
+            "",
+        );
+
+        assert!(matches!(
+            error,
+            JiraError::RateLimited { retry_after: Some(d), .. } if d == Duration::from_secs(30)
+        ));
+        assert!(error.to_string().contains("retry after 30s"));
+    }
+}
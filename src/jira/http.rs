@@ -0,0 +1,260 @@
+//! Request executor shared by every `JiraClient` endpoint: authentication,
+//! retries, timeouts, tracing and response decoding.
+
+use std::time::{Duration, Instant};
+
+use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::{Method, Request, RequestBuilder, Response, StatusCode};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::error::Result;
+use super::{Credentials, JiraClient, JiraError, credentials_error, retry};
+
+pub const DEFAULT_USER_AGENT: &str = concat!("jira-mcp-rs/", env!("CARGO_PKG_VERSION"));
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Largest response body accepted before giving up (10 MiB)
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+/// Largest attachment accepted for upload (10 MiB)
+pub const DEFAULT_MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+
+/// Headers whose values must never reach the logs.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+impl JiraClient {
+    /// Start a JSON request with the client's default headers and timeout.
+    pub(super) fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        self.request_accepting(method, url, "application/json")
+    }
+
+    /// Start a request for a response of the given media type (e.g. `*/*` for
+    /// attachment content).
+    pub(super) fn request_accepting(
+        &self,
+        method: Method,
+        url: &str,
+        accept: &str,
+    ) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header(ACCEPT, accept)
+            .header(USER_AGENT, &self.user_agent)
+            .timeout(self.timeout)
+    }
+
+    /// GET `url` and decode the JSON response.
+    pub(super) async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self.send(self.request(Method::GET, url)).await?;
+        self.decode(response).await
+    }
+
+    /// Send `body` as JSON and decode the JSON response. Use `T = ()` for
+    /// endpoints that answer with no content.
+    pub(super) async fn send_json<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        body: &impl Serialize,
+    ) -> Result<T> {
+        let response = self.send(self.request(method, url).json(body)).await?;
+        self.decode(response).await
+    }
+
+    /// Like `send_json`, for POST endpoints that only read (e.g. search) and
+    /// can be retried like a GET.
+    pub(super) async fn query_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &impl Serialize,
+    ) -> Result<T> {
+        let request = self.request(Method::POST, url).json(body).build()?;
+        let response = self.execute(request, true).await?;
+        self.decode(response).await
+    }
+
+    /// Authenticate and send a request, retrying when it is safe to do so.
+    ///
+    /// Rate-limited (429) responses are retried for every method, honouring
+    /// `Retry-After` / `X-RateLimit-Reset`. Server errors and connection failures
+    /// are retried only for idempotent methods. With OAuth, a 401 response
+    /// triggers one token refresh before retrying.
+    pub(super) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let request = request.build()?;
+        let idempotent = retry::is_idempotent(request.method());
+        self.execute(request, idempotent).await
+    }
+
+    /// Check the status of a response and decode its JSON body, enforcing the
+    /// response size limit. An empty body decodes as JSON `null`.
+    pub(super) async fn decode<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
+        let bytes = self.read_body(response).await?;
+        let json: &[u8] = if bytes.iter().all(u8::is_ascii_whitespace) {
+            b"null"
+        } else {
+            &bytes
+        };
+        serde_json::from_slice(json).map_err(|e| JiraError::Decode(e.to_string()))
+    }
+
+    /// Check the status of a response and return its raw body, enforcing the
+    /// response size limit.
+    pub(super) async fn read_body(&self, mut response: Response) -> Result<Vec<u8>> {
+        if !response.status().is_success() {
+            return Err(JiraError::from_response(response).await);
+        }
+
+        let limit = self.max_response_bytes;
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(JiraError::ResponseTooLarge { limit });
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(JiraError::ResponseTooLarge { limit });
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    async fn execute(&self, request: Request, idempotent: bool) -> Result<Response> {
+        let policy = self.retry_policy;
+        let mut retries = 0;
+        let mut refreshed = false;
+
+        loop {
+            let Some(mut attempt) = request.try_clone() else {
+                // Streaming bodies cannot be replayed, so send them exactly once
+                return self.execute_once(request).await;
+            };
+            let authorization = self.authorize(&mut attempt).await?;
+
+            let response = match self.execute_once(attempt).await {
+                Ok(response) => response,
+                Err(JiraError::Transport(e))
+                    if idempotent
+                        && retries < policy.max_retries
+                        && (e.is_connect() || e.is_timeout()) =>
+                {
+                    let delay = policy.backoff(retries);
+                    tracing::debug!("Request to Jira failed ({}); retrying in {:?}", e, delay);
+                    tokio::time::sleep(delay).await;
+                    retries += 1;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let status = response.status();
+
+            if status == StatusCode::UNAUTHORIZED
+                && !refreshed
+                && let Credentials::OAuth(session) = &self.credentials
+            {
+                tracing::debug!("Access token rejected; refreshing and retrying");
+                session
+                    .refresh_rejected(&authorization)
+                    .await
+                    .map_err(credentials_error)?;
+                refreshed = true;
+                continue;
+            }
+
+            if !retry::is_retryable(status, idempotent) || retries >= policy.max_retries {
+                return Ok(response);
+            }
+            let delay = match retry::server_delay(response.headers()) {
+                // Waiting longer than allowed: let the caller see the rate limit
+                Some(delay) if delay > policy.max_delay => return Ok(response),
+                Some(delay) => delay,
+                None => policy.backoff(retries),
+            };
+            tracing::debug!(
+                "Jira returned {}; retry {}/{} in {:?}",
+                status,
+                retries + 1,
+                policy.max_retries,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            retries += 1;
+        }
+    }
+
+    /// Send one attempt, authorizing it if that has not happened yet.
+    async fn execute_once(&self, mut request: Request) -> Result<Response> {
+        if !request.headers().contains_key(AUTHORIZATION) {
+            self.authorize(&mut request).await?;
+        }
+
+        tracing::debug!(
+            method = %request.method(),
+            url = %request.url(),
+            headers = ?redacted_headers(request.headers()),
+            body_bytes = request.body().and_then(|b| b.as_bytes()).map(<[u8]>::len),
+            "Jira request"
+        );
+        let started = Instant::now();
+        let response = self.client.execute(request).await?;
+        tracing::debug!(
+            status = %response.status(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            content_length = response.content_length(),
+            "Jira response"
+        );
+        Ok(response)
+    }
+
+    /// Set the `Authorization` header, returning the value used.
+    async fn authorize(&self, request: &mut Request) -> Result<String> {
+        let authorization = self.credentials.authorization().await?;
+        let mut value = HeaderValue::from_str(&authorization)
+            .map_err(|e| JiraError::Credentials(e.to_string()))?;
+        value.set_sensitive(true);
+        request.headers_mut().insert(AUTHORIZATION, value);
+        Ok(authorization)
+    }
+}
+
+/// Header names and values for logging, with credentials replaced.
+fn redacted_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) || value.is_sensitive() {
+                "[redacted]".to_string()
+            } else {
+                value.to_str().unwrap_or("[binary]").to_string()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_headers_hide_credentials() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Basic c2VjcmV0"));
+        headers.insert("cookie", HeaderValue::from_static("session=secret"));
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+
+        let redacted = redacted_headers(&headers);
+        let rendered = format!("{:?}", redacted);
+
+        assert!(!rendered.contains("c2VjcmV0"));
+        assert!(!rendered.contains("session=secret"));
+        assert!(rendered.contains("application/json"));
+    }
+}
@@ -1,85 +1,374 @@
+mod agile;
+mod error;
+mod http;
 mod models;
-
+pub mod oauth;
+mod projects;
+mod random;
+mod retry;
+mod time;
+mod users;
+mod versions;
+mod wiki;
+
+pub use error::JiraError;
 pub use models::*;
+pub use random::random_token;
+pub use retry::RetryPolicy;
+pub use time::{
+    date_from_millis, date_today, format_duration, iso_timestamp_now, iso_timestamp_plus_days,
+    jira_timestamp_now, parse_duration, to_date, to_iso_timestamp, to_jira_timestamp,
+};
+pub use versions::RELEASE_NOTES_LIMIT;
+pub use wiki::{markdown_to_wiki, wiki_to_markdown};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::Result;
 use base64::{engine::general_purpose::STANDARD, Engine};
-use reqwest::Client;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Method};
+use tokio::sync::RwLock;
+
+use error::Result;
+use oauth::OAuthSession;
+
+/// Largest page size accepted by the enhanced search endpoint
+pub const SEARCH_PAGE_SIZE: u32 = 100;
+
+/// Fields requested for issue lists (search results, sprints, backlogs)
+const ISSUE_LIST_FIELDS: &[&str] = &[
+    "summary",
+    "status",
+    "assignee",
+    "priority",
+    "issuetype",
+    "created",
+    "updated",
+];
+
+/// Largest page size accepted by the changelog endpoint
+const CHANGELOG_PAGE_SIZE: u32 = 100;
+
+/// Largest page size accepted by the create metadata endpoints
+const CREATEMETA_PAGE_SIZE: usize = 50;
+
+/// Which Jira product the client talks to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Deployment {
+    /// Jira Cloud: REST API v3, Atlassian Document Format, `accountId` user references
+    #[default]
+    Cloud,
+    /// Jira Data Center / Server: REST API v2, wiki markup, `name` user references
+    DataCenter,
+}
+
+impl Deployment {
+    /// Parse a deployment name as used in `JIRA_DEPLOYMENT`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "cloud" => Some(Self::Cloud),
+            "server" | "datacenter" | "data-center" | "data_center" | "dc" => {
+                Some(Self::DataCenter)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn api_version(&self) -> u8 {
+        match self {
+            Self::Cloud => 3,
+            Self::DataCenter => 2,
+        }
+    }
+}
+
+/// How requests are authenticated.
+#[derive(Clone)]
+enum Credentials {
+    /// A fixed `Authorization` header value (Basic auth or a Personal Access Token)
+    Static(String),
+    /// OAuth 2.0 (3LO) access token, refreshed before expiry and on 401
+    OAuth(Arc<OAuthSession>),
+}
+
+impl Credentials {
+    async fn authorization(&self) -> Result<String> {
+        match self {
+            Self::Static(header) => Ok(header.clone()),
+            Self::OAuth(session) => session.authorization().await.map_err(credentials_error),
+        }
+    }
+}
+
+fn credentials_error(e: anyhow::Error) -> JiraError {
+    JiraError::Credentials(format!("{:#}", e))
+}
 
 #[derive(Clone)]
 pub struct JiraClient {
     client: Client,
     base_url: String,
-    auth_header: String,
+    credentials: Credentials,
+    deployment: Deployment,
+    retry_policy: RetryPolicy,
+    user_agent: String,
+    timeout: Duration,
+    max_response_bytes: usize,
+    max_upload_bytes: usize,
+    /// Field catalogue, fetched on first use and shared between clones
+    field_catalogue: Arc<RwLock<Option<Arc<FieldCatalogue>>>>,
+    /// User IDs resolved from emails, names and "me", keyed by lowercased input
+    user_ids: Arc<RwLock<HashMap<String, String>>>,
 }
 
 impl JiraClient {
+    /// Create a client authenticating with email and API token (Basic auth).
     pub fn new(base_url: &str, email: &str, api_token: &str) -> Self {
         let credentials = format!("{}:{}", email, api_token);
         let auth_header = format!("Basic {}", STANDARD.encode(credentials));
 
+        Self::with_credentials(
+            base_url,
+            Credentials::Static(auth_header),
+            Deployment::Cloud,
+        )
+    }
+
+    /// Create a Jira Data Center / Server client authenticating with a
+    /// Personal Access Token (Bearer auth).
+    pub fn with_personal_access_token(base_url: &str, token: &str) -> Self {
+        Self::with_credentials(
+            base_url,
+            Credentials::Static(format!("Bearer {}", token)),
+            Deployment::DataCenter,
+        )
+    }
+
+    /// Create a Jira Cloud client authenticating with OAuth 2.0 (3LO) tokens.
+    ///
+    /// Requests go through the Atlassian API gateway for the authorized site.
+    pub fn with_oauth(session: OAuthSession) -> Self {
+        let base_url = session.api_base_url().to_string();
+        Self::with_credentials(
+            &base_url,
+            Credentials::OAuth(Arc::new(session)),
+            Deployment::Cloud,
+        )
+    }
+
+    /// Client with default request settings and empty caches.
+    fn with_credentials(base_url: &str, credentials: Credentials, deployment: Deployment) -> Self {
         Self {
             client: Client::new(),
             base_url: base_url.trim_end_matches('/').to_string(),
-            auth_header,
+            credentials,
+            deployment,
+            retry_policy: RetryPolicy::default(),
+            user_agent: http::DEFAULT_USER_AGENT.to_string(),
+            timeout: http::DEFAULT_TIMEOUT,
+            max_response_bytes: http::DEFAULT_MAX_RESPONSE_BYTES,
+            max_upload_bytes: http::DEFAULT_MAX_UPLOAD_BYTES,
+            field_catalogue: Arc::default(),
+            user_ids: Arc::default(),
         }
     }
 
+    /// Set the deployment flavor (e.g. Data Center with username/password Basic auth).
+    pub fn with_deployment(mut self, deployment: Deployment) -> Self {
+        self.deployment = deployment;
+        self
+    }
+
+    /// Set how rate-limited and failed requests are retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the `User-Agent` sent with every request.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Set how long a single request attempt may take.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the largest response body the client will read.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// Set the largest attachment the server will read for upload.
+    pub fn with_max_upload_bytes(mut self, max_upload_bytes: usize) -> Self {
+        self.max_upload_bytes = max_upload_bytes;
+        self
+    }
+
+    /// Largest attachment the server will read for upload.
+    pub fn max_upload_bytes(&self) -> usize {
+        self.max_upload_bytes
+    }
+
+    /// Link to an issue in the Jira web UI, when the site URL is known.
+    pub fn browse_url(&self, issue_key: &str) -> Option<String> {
+        let site_url = match &self.credentials {
+            Credentials::Static(_) => Some(self.base_url.as_str()),
+            Credentials::OAuth(session) => session.site_url(),
+        }?;
+        Some(format!(
+            "{}/browse/{}",
+            site_url.trim_end_matches('/'),
+            issue_key
+        ))
+    }
+
+    /// Build a REST API URL for the configured deployment's API version.
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "{}/rest/api/{}/{}",
+            self.base_url,
+            self.deployment.api_version(),
+            path
+        )
+    }
+
     pub async fn search_issues(&self, jql: &str, max_results: u32) -> Result<SearchResult> {
-        let url = format!("{}/rest/api/3/search/jql", self.base_url);
+        self.search_issues_page(jql, max_results, None).await
+    }
+
+    /// Fetch a single page of search results.
+    ///
+    /// Pass the `next_page_token` of a previous result to continue where it left off.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-search/#api-rest-api-3-search-jql-post
+    pub async fn search_issues_page(
+        &self,
+        jql: &str,
+        max_results: u32,
+        page_token: Option<&str>,
+    ) -> Result<SearchResult> {
+        self.search_page_with_fields(jql, max_results, page_token, ISSUE_LIST_FIELDS)
+            .await
+    }
+
+    async fn search_page_with_fields(
+        &self,
+        jql: &str,
+        max_results: u32,
+        page_token: Option<&str>,
+        fields: &[&str],
+    ) -> Result<SearchResult> {
+        // Data Center has no enhanced search; its offset is carried in the page token
+        let (url, next_page_token, start_at) = match self.deployment {
+            Deployment::Cloud => (
+                self.api_url("search/jql"),
+                page_token.map(|t| t.to_string()),
+                None,
+            ),
+            Deployment::DataCenter => (
+                self.api_url("search"),
+                None,
+                Some(page_token.and_then(|t| t.parse().ok()).unwrap_or(0)),
+            ),
+        };
 
         let request_body = SearchRequest {
             jql: jql.to_string(),
             max_results,
-            fields: vec![
-                "summary".to_string(),
-                "status".to_string(),
-                "assignee".to_string(),
-                "priority".to_string(),
-                "issuetype".to_string(),
-                "created".to_string(),
-                "updated".to_string(),
-            ],
+            next_page_token,
+            start_at,
+            fields: fields.iter().map(|f| f.to_string()).collect(),
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Jira API error ({}): {}", status, error_text);
+        let mut result: SearchResult = self.query_json(&url, &request_body).await?;
+        if let (Some(start_at), Some(total)) = (start_at, result.total) {
+            let next = start_at + result.issues.len() as u32;
+            let is_last = result.issues.is_empty() || next >= total;
+            result.next_page_token = (!is_last).then(|| next.to_string());
+            result.is_last = Some(is_last);
         }
-
-        let result = response.json::<SearchResult>().await?;
         Ok(result)
     }
 
-    pub async fn get_issue(&self, issue_key: &str) -> Result<Issue> {
-        let url = format!("{}/rest/api/3/issue/{}", self.base_url, issue_key);
-
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Jira API error ({}): {}", status, error_text);
+    /// Walk search result pages until `limit` issues have been collected or the
+    /// results are exhausted.
+    ///
+    /// Pages are requested in chunks of at most [`SEARCH_PAGE_SIZE`] so that the
+    /// returned `next_page_token` always points at the first issue not included,
+    /// letting callers resume later.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let result = client.search_all_issues("project = PROJ", 500, None).await?;
+    /// if let Some(token) = result.next_page_token {
+    ///     // more issues remain
+    /// }
+    /// ```
+    pub async fn search_all_issues(
+        &self,
+        jql: &str,
+        limit: u32,
+        page_token: Option<&str>,
+    ) -> Result<SearchResult> {
+        self.search_all_issues_with_fields(jql, limit, page_token, ISSUE_LIST_FIELDS)
+            .await
+    }
+
+    /// Like [`search_all_issues`](Self::search_all_issues), returning the given fields.
+    pub async fn search_all_issues_with_fields(
+        &self,
+        jql: &str,
+        limit: u32,
+        page_token: Option<&str>,
+        fields: &[&str],
+    ) -> Result<SearchResult> {
+        let mut issues = Vec::new();
+        let mut next_page_token = page_token.map(|t| t.to_string());
+
+        loop {
+            let remaining = limit.saturating_sub(issues.len() as u32);
+            if remaining == 0 {
+                break;
+            }
+
+            let page = self
+                .search_page_with_fields(
+                    jql,
+                    remaining.min(SEARCH_PAGE_SIZE),
+                    next_page_token.as_deref(),
+                    fields,
+                )
+                .await?;
+            let page_len = page.issues.len();
+            issues.extend(page.issues);
+            next_page_token = page.next_page_token;
+
+            if page.is_last == Some(true) || next_page_token.is_none() || page_len == 0 {
+                next_page_token = None;
+                break;
+            }
         }
 
-        let issue = response.json::<Issue>().await?;
-        Ok(issue)
+        Ok(SearchResult {
+            total: None,
+            max_results: Some(limit),
+            start_at: None,
+            is_last: Some(next_page_token.is_none()),
+            next_page_token,
+            issues,
+        })
+    }
+
+    pub async fn get_issue(&self, issue_key: &str) -> Result<Issue> {
+        let url = self.api_url(&format!("issue/{}", issue_key));
+
+        self.get_json(&url).await
     }
 
     /// Update an issue's fields.
@@ -94,24 +383,10 @@ impl JiraClient {
     /// client.update_issue("PROJ-456", update).await?;
     /// ```
     pub async fn update_issue(&self, issue_key: &str, update: UpdateIssueRequest) -> Result<()> {
-        let url = format!("{}/rest/api/3/issue/{}", self.base_url, issue_key);
-
-        let response = self
-            .client
-            .put(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .json(&update)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Jira API error ({}): {}", status, error_text);
-        }
+        let url = self.api_url(&format!("issue/{}", issue_key));
 
-        Ok(())
+        self.send_json(Method::PUT, &url, &update.for_deployment(self.deployment))
+            .await
     }
 
     /// Get child issues of a parent issue (epic's stories or issue's subtasks).
@@ -149,27 +424,12 @@ impl JiraClient {
         start_at: u32,
         max_results: u32,
     ) -> Result<CommentResponse> {
-        let url = format!(
-            "{}/rest/api/3/issue/{}/comment?startAt={}&maxResults={}",
-            self.base_url, issue_key, start_at, max_results
-        );
-
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Jira API error ({}): {}", status, error_text);
-        }
+        let url = self.api_url(&format!(
+            "issue/{}/comment?startAt={}&maxResults={}",
+            issue_key, start_at, max_results
+        ));
 
-        let result = response.json::<CommentResponse>().await?;
-        Ok(result)
+        self.get_json(&url).await
     }
 
     /// Create a new Jira issue.
@@ -184,72 +444,380 @@ impl JiraClient {
     /// println!("Created: {}", created.key);
     /// ```
     pub async fn create_issue(&self, request: CreateIssueRequest) -> Result<CreatedIssue> {
-        let url = format!("{}/rest/api/3/issue", self.base_url);
-
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Jira API error ({}): {}", status, error_text);
-        }
+        let url = self.api_url("issue");
 
-        let created = response.json::<CreatedIssue>().await?;
-        Ok(created)
+        self.send_json(Method::POST, &url, &request.for_deployment(self.deployment))
+            .await
     }
 
-    pub async fn add_comment(&self, issue_key: &str, comment: &str) -> Result<Comment> {
-        let url = format!("{}/rest/api/3/issue/{}/comment", self.base_url, issue_key);
+    /// Add a comment to an issue.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Markdown is converted to Atlassian Document Format
+    /// client.add_comment("PROJ-123", AddCommentRequest::new("Fixed in **v1.2**")).await?;
+    ///
+    /// // Post text verbatim
+    /// client.add_comment("PROJ-123", AddCommentRequest::plain("*not emphasis*")).await?;
+    /// ```
+    pub async fn add_comment(
+        &self,
+        issue_key: &str,
+        request: AddCommentRequest,
+    ) -> Result<Comment> {
+        let url = self.api_url(&format!("issue/{}/comment", issue_key));
 
-        let request_body = AddCommentRequest {
-            body: serde_json::json!({
-                "type": "doc",
-                "version": 1,
-                "content": [
-                    {
-                        "type": "paragraph",
-                        "content": [
-                            {
-                                "type": "text",
-                                "text": comment
-                            }
-                        ]
-                    }
-                ]
-            }),
+        self.send_json(Method::POST, &url, &request.for_deployment(self.deployment))
+            .await
+    }
+
+    pub async fn get_comment(&self, issue_key: &str, comment_id: &str) -> Result<Comment> {
+        let url = self.api_url(&format!("issue/{}/comment/{}", issue_key, comment_id));
+
+        self.get_json(&url).await
+    }
+
+    /// Replace the body of a comment.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-id-put
+    pub async fn update_comment(
+        &self,
+        issue_key: &str,
+        comment_id: &str,
+        request: AddCommentRequest,
+    ) -> Result<Comment> {
+        let url = self.api_url(&format!("issue/{}/comment/{}", issue_key, comment_id));
+
+        self.send_json(Method::PUT, &url, &request.for_deployment(self.deployment))
+            .await
+    }
+
+    pub async fn delete_comment(&self, issue_key: &str, comment_id: &str) -> Result<()> {
+        let url = self.api_url(&format!("issue/{}/comment/{}", issue_key, comment_id));
+
+        let response = self.send(self.request(Method::DELETE, &url)).await?;
+        self.decode(response).await
+    }
+
+    /// Delete an issue. Jira refuses to delete an issue with sub-tasks unless
+    /// `delete_subtasks` is set.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-delete
+    pub async fn delete_issue(&self, issue_key: &str, delete_subtasks: bool) -> Result<()> {
+        let url = self.api_url(&format!("issue/{}", issue_key));
+
+        let request = self
+            .request(Method::DELETE, &url)
+            .query(&[("deleteSubtasks", delete_subtasks)]);
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// Get the workflow transitions currently available for an issue.
+    ///
+    /// Expands `transitions.fields` so callers can see which fields the
+    /// transition screen requires.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-transitions-get
+    pub async fn get_transitions(&self, issue_key: &str) -> Result<TransitionsResponse> {
+        let url = self.api_url(&format!(
+            "issue/{}/transitions?expand=transitions.fields",
+            issue_key
+        ));
+
+        self.get_json(&url).await
+    }
+
+    /// Move an issue through its workflow.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let request = TransitionIssueRequest::new("31")
+    ///     .resolution("Done")
+    ///     .comment("Released in v1.2");
+    ///
+    /// client.transition_issue("PROJ-123", request).await?;
+    /// ```
+    pub async fn transition_issue(
+        &self,
+        issue_key: &str,
+        request: TransitionIssueRequest,
+    ) -> Result<()> {
+        let url = self.api_url(&format!("issue/{}/transitions", issue_key));
+
+        self.send_json(Method::POST, &url, &request.for_deployment(self.deployment))
+            .await
+    }
+
+    /// Get the worklogs recorded on an issue.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/
+    pub async fn get_worklogs(
+        &self,
+        issue_key: &str,
+        start_at: u32,
+        max_results: u32,
+    ) -> Result<WorklogResponse> {
+        let url = self.api_url(&format!(
+            "issue/{}/worklog?startAt={}&maxResults={}",
+            issue_key, start_at, max_results
+        ));
+
+        self.get_json(&url).await
+    }
+
+    /// Log time on an issue.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let request = WorklogRequest::new()
+    ///     .time_spent_seconds(parse_duration("1h 30m")?)
+    ///     .started(&jira_timestamp_now())
+    ///     .comment("Pairing on the **login** fix");
+    ///
+    /// client.add_worklog("PROJ-123", request, &EstimateAdjustment::Auto).await?;
+    /// ```
+    pub async fn add_worklog(
+        &self,
+        issue_key: &str,
+        request: WorklogRequest,
+        adjustment: &EstimateAdjustment,
+    ) -> Result<Worklog> {
+        let url = self.api_url(&format!("issue/{}/worklog", issue_key));
+
+        let request = self
+            .request(Method::POST, &url)
+            .query(&adjustment.query(false))
+            .json(&request.for_deployment(self.deployment));
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// Change the time, start or comment of an existing worklog.
+    /// `EstimateAdjustment::Manual` is not supported by Jira here.
+    pub async fn update_worklog(
+        &self,
+        issue_key: &str,
+        worklog_id: &str,
+        request: WorklogRequest,
+        adjustment: &EstimateAdjustment,
+    ) -> Result<Worklog> {
+        let url = self.api_url(&format!("issue/{}/worklog/{}", issue_key, worklog_id));
+
+        let request = self
+            .request(Method::PUT, &url)
+            .query(&adjustment.query(false))
+            .json(&request.for_deployment(self.deployment));
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// Delete a worklog, adjusting the remaining estimate as requested.
+    pub async fn delete_worklog(
+        &self,
+        issue_key: &str,
+        worklog_id: &str,
+        adjustment: &EstimateAdjustment,
+    ) -> Result<()> {
+        let url = self.api_url(&format!("issue/{}/worklog/{}", issue_key, worklog_id));
+
+        let request = self
+            .request(Method::DELETE, &url)
+            .query(&adjustment.query(true));
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// Upload a file as an attachment. Returns the created attachments.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-issue-issueidorkey-attachments-post
+    pub async fn add_attachment(
+        &self,
+        issue_key: &str,
+        filename: &str,
+        data: Vec<u8>,
+    ) -> Result<Vec<Attachment>> {
+        let url = self.api_url(&format!("issue/{}/attachments", issue_key));
+        let form = Form::new().part("file", Part::bytes(data).file_name(filename.to_string()));
+
+        let request = self
+            .request(Method::POST, &url)
+            // Jira rejects multipart uploads without this XSRF opt-out
+            .header("X-Atlassian-Token", "no-check")
+            .multipart(form);
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// List the attachments on an issue.
+    pub async fn get_attachments(&self, issue_key: &str) -> Result<Vec<Attachment>> {
+        let url = self.api_url(&format!("issue/{}?fields=attachment", issue_key));
+
+        let issue: Issue = self.get_json(&url).await?;
+        Ok(issue.fields.attachment.unwrap_or_default())
+    }
+
+    /// Download an attachment's metadata and content.
+    ///
+    /// Attachments larger than the client's response size limit are refused
+    /// before their content is requested.
+    pub async fn download_attachment(&self, attachment_id: &str) -> Result<(Attachment, Vec<u8>)> {
+        let url = self.api_url(&format!("attachment/{}", attachment_id));
+        let attachment: Attachment = self.get_json(&url).await?;
+
+        if attachment.size > self.max_response_bytes as u64 {
+            return Err(JiraError::ResponseTooLarge {
+                limit: self.max_response_bytes,
+            });
+        }
+
+        // Cloud serves content through the API (also reachable via the OAuth
+        // gateway); Data Center only via the `content` URL
+        let content_url = match (self.deployment, &attachment.content) {
+            (Deployment::DataCenter, Some(content)) => content.clone(),
+            _ => self.api_url(&format!("attachment/content/{}", attachment_id)),
         };
+        let request = self.request_accepting(Method::GET, &content_url, "*/*");
+        let response = self.send(request).await?;
+        let data = self.read_body(response).await?;
+        Ok((attachment, data))
+    }
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", &self.auth_header)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Jira API error ({}): {}", status, error_text);
+    /// List the kinds of links that can be created between issues.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-link-types/
+    pub async fn get_issue_link_types(&self) -> Result<IssueLinkTypesResponse> {
+        let url = self.api_url("issueLinkType");
+
+        self.get_json(&url).await
+    }
+
+    /// Link two issues.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // PROJ-1 blocks PROJ-2
+    /// let request = LinkIssuesRequest::new("Blocks", "PROJ-1", "PROJ-2", LinkDirection::Outward);
+    /// client.link_issues(request).await?;
+    /// ```
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-links/#api-rest-api-3-issuelink-post
+    pub async fn link_issues(&self, request: LinkIssuesRequest) -> Result<()> {
+        let url = self.api_url("issueLink");
+
+        self.send_json(Method::POST, &url, &request).await
+    }
+
+    /// Delete an issue link by ID (as listed in the issue's `issuelinks`).
+    pub async fn delete_issue_link(&self, link_id: &str) -> Result<()> {
+        let url = self.api_url(&format!("issueLink/{}", link_id));
+
+        let response = self.send(self.request(Method::DELETE, &url)).await?;
+        self.decode(response).await
+    }
+
+    /// Get the site's system and custom fields. The catalogue is fetched once
+    /// and cached; pass `refresh` to fetch it again (e.g. when a field was
+    /// just added).
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-fields/#api-rest-api-3-field-get
+    pub async fn get_fields(&self, refresh: bool) -> Result<Arc<FieldCatalogue>> {
+        if !refresh && let Some(catalogue) = self.field_catalogue.read().await.as_ref() {
+            return Ok(catalogue.clone());
+        }
+
+        let url = self.api_url("field");
+        let fields: Vec<Field> = self.get_json(&url).await?;
+        let catalogue = Arc::new(FieldCatalogue::new(fields));
+        *self.field_catalogue.write().await = Some(catalogue.clone());
+        Ok(catalogue)
+    }
+
+    /// Get an issue's complete change history, oldest first.
+    ///
+    /// Cloud pages through the changelog endpoint; Data Center has none and
+    /// returns the history with `expand=changelog` instead.
+    /// Reference: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-changelog-get
+    pub async fn get_changelog(&self, issue_key: &str) -> Result<Vec<ChangelogEntry>> {
+        if self.deployment == Deployment::DataCenter {
+            let url = self.api_url(&format!(
+                "issue/{}?fields=summary&expand=changelog",
+                issue_key
+            ));
+            let issue: IssueWithChangelog = self.get_json(&url).await?;
+            return Ok(issue.changelog.values);
+        }
+
+        let mut entries = Vec::new();
+        loop {
+            let url = self.api_url(&format!(
+                "issue/{}/changelog?startAt={}&maxResults={}",
+                issue_key,
+                entries.len(),
+                CHANGELOG_PAGE_SIZE
+            ));
+            let page: ChangelogPage = self.get_json(&url).await?;
+            let page_len = page.values.len();
+            entries.extend(page.values);
+
+            let is_last = page.is_last.unwrap_or(entries.len() >= page.total as usize);
+            if is_last || page_len == 0 {
+                break;
+            }
         }
+        Ok(entries)
+    }
+
+    /// Issue types that can be created in a project (Cloud, Data Center 8.4+).
+    pub async fn get_create_issue_types(&self, project_key: &str) -> Result<CreateMetaIssueTypes> {
+        let mut issue_types = CreateMetaIssueTypes::default();
+        loop {
+            let url = self.api_url(&format!(
+                "issue/createmeta/{}/issuetypes?startAt={}&maxResults={}",
+                project_key,
+                issue_types.issue_types.len(),
+                CREATEMETA_PAGE_SIZE
+            ));
+            let page: CreateMetaIssueTypes = self.get_json(&url).await?;
+            let page_len = page.issue_types.len();
+            issue_types.total = page.total;
+            issue_types.issue_types.extend(page.issue_types);
+
+            if page_len == 0 || issue_types.issue_types.len() >= page.total as usize {
+                break;
+            }
+        }
+        Ok(issue_types)
+    }
 
-        let comment = response.json::<Comment>().await?;
-        Ok(comment)
+    /// Fields on the create screen of an issue type, with required flags and
+    /// allowed values.
+    pub async fn get_create_fields(
+        &self,
+        project_key: &str,
+        issue_type_id: &str,
+    ) -> Result<CreateMetaFields> {
+        let mut fields = CreateMetaFields::default();
+        loop {
+            let url = self.api_url(&format!(
+                "issue/createmeta/{}/issuetypes/{}?startAt={}&maxResults={}",
+                project_key,
+                issue_type_id,
+                fields.fields.len(),
+                CREATEMETA_PAGE_SIZE
+            ));
+            let page: CreateMetaFields = self.get_json(&url).await?;
+            let page_len = page.fields.len();
+            fields.total = page.total;
+            fields.fields.extend(page.fields);
+
+            if page_len == 0 || fields.fields.len() >= page.total as usize {
+                break;
+            }
+        }
+        Ok(fields)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use wiremock::matchers::{header, method, path};
+    use wiremock::matchers::{
+        body_json, body_partial_json, body_string_contains, header, header_regex, method, path,
+        query_param,
+    };
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     fn create_test_issue(key: &str, summary: &str, status: &str) -> Issue {
@@ -261,11 +829,13 @@ mod tests {
                 summary: Some(summary.to_string()),
                 status: Some(Status {
                     name: status.to_string(),
+                    status_category: None,
                 }),
                 assignee: Some(User {
                     display_name: "Test User".to_string(),
                     email_address: Some("test@example.com".to_string()),
                     account_id: Some("test-account-id".to_string()),
+                    name: None,
                 }),
                 priority: Some(Priority {
                     name: "Medium".to_string(),
@@ -277,6 +847,7 @@ mod tests {
                     name: "Story".to_string(),
                     subtask: false,
                 }),
+                ..Default::default()
             },
         }
     }
@@ -289,6 +860,8 @@ mod tests {
             total: Some(1),
             max_results: Some(50),
             start_at: Some(0),
+            next_page_token: None,
+            is_last: None,
             issues: vec![expected_issue],
         };
 
@@ -322,6 +895,8 @@ mod tests {
             total: Some(0),
             max_results: Some(50),
             start_at: Some(0),
+            next_page_token: None,
+            is_last: None,
             issues: vec![],
         };
 
@@ -358,6 +933,107 @@ mod tests {
         assert!(error_message.contains("401"));
     }
 
+    #[tokio::test]
+    async fn search_issues_page_sends_next_page_token() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(body_partial_json(serde_json::json!({
+                "jql": "project = PROJ",
+                "maxResults": 25,
+                "nextPageToken": "page-2"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [create_test_issue("PROJ-26", "Later issue", "Open")],
+                "isLast": true
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let result = client
+            .search_issues_page("project = PROJ", 25, Some("page-2"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.issues[0].key, "PROJ-26");
+        assert_eq!(result.is_last, Some(true));
+        assert!(result.next_page_token.is_none());
+    }
+
+    #[tokio::test]
+    async fn search_all_issues_walks_pages_until_last() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(body_partial_json(
+                serde_json::json!({ "nextPageToken": "page-2" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [create_test_issue("PROJ-2", "Second", "Open")],
+                "isLast": true
+            })))
+            .with_priority(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [create_test_issue("PROJ-1", "First", "Open")],
+                "nextPageToken": "page-2",
+                "isLast": false
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let result = client
+            .search_all_issues("project = PROJ", 500, None)
+            .await
+            .unwrap();
+
+        let keys: Vec<&str> = result.issues.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(keys, vec!["PROJ-1", "PROJ-2"]);
+        assert!(result.next_page_token.is_none());
+        assert_eq!(result.is_last, Some(true));
+    }
+
+    #[tokio::test]
+    async fn search_all_issues_stops_at_limit_and_returns_token() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(body_partial_json(serde_json::json!({ "maxResults": 1 })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [create_test_issue("PROJ-1", "First", "Open")],
+                "nextPageToken": "page-2",
+                "isLast": false
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let result = client
+            .search_all_issues("project = PROJ", 1, None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.next_page_token.as_deref(), Some("page-2"));
+        assert_eq!(result.is_last, Some(false));
+    }
+
     #[tokio::test]
     async fn get_issue_returns_issue_details() {
         let mock_server = MockServer::start().await;
@@ -418,8 +1094,10 @@ mod tests {
                 display_name: "Test User".to_string(),
                 email_address: Some("test@example.com".to_string()),
                 account_id: Some("test-account-id".to_string()),
+                name: None,
             }),
             created: Some("2024-01-17T09:00:00.000+0000".to_string()),
+            updated: None,
             body: None,
         };
 
@@ -436,7 +1114,7 @@ mod tests {
         let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
 
         let comment = client
-            .add_comment("PROJ-123", "This is a test comment")
+            .add_comment("PROJ-123", AddCommentRequest::new("This is a test comment"))
             .await
             .unwrap();
 
@@ -448,175 +1126,1008 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn add_comment_returns_error_when_issue_not_found() {
+    async fn add_comment_posts_markdown_as_adf() {
         let mock_server = MockServer::start().await;
 
         Mock::given(method("POST"))
-            .and(path("/rest/api/3/issue/PROJ-999/comment"))
-            .respond_with(ResponseTemplate::new(404).set_body_string("Issue not found"))
+            .and(path("/rest/api/3/issue/PROJ-123/comment"))
+            .and(body_json(serde_json::json!({
+                "body": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [
+                        {
+                            "type": "paragraph",
+                            "content": [
+                                { "type": "text", "text": "Fixed", "marks": [{ "type": "strong" }] }
+                            ]
+                        },
+                        {
+                            "type": "bulletList",
+                            "content": [{
+                                "type": "listItem",
+                                "content": [{
+                                    "type": "paragraph",
+                                    "content": [
+                                        { "type": "text", "text": "run " },
+                                        { "type": "text", "text": "cargo test", "marks": [{ "type": "code" }] }
+                                    ]
+                                }]
+                            }]
+                        }
+                    ]
+                }
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "10101",
+                "self": "https://example.atlassian.net/rest/api/3/issue/PROJ-123/comment/10101"
+            })))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
         let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
 
-        let result = client.add_comment("PROJ-999", "Test comment").await;
+        let comment = client
+            .add_comment(
+                "PROJ-123",
+                AddCommentRequest::new("**Fixed**\n\n- run `cargo test`"),
+            )
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
-        let error_message = result.unwrap_err().to_string();
-        assert!(error_message.contains("404"));
+        assert_eq!(comment.id, "10101");
     }
 
     #[tokio::test]
-    async fn update_issue_updates_fields_successfully() {
+    async fn update_comment_puts_markdown_as_adf() {
         let mock_server = MockServer::start().await;
 
         Mock::given(method("PUT"))
-            .and(path("/rest/api/3/issue/PROJ-123"))
-            .and(header(
-                "Authorization",
-                "Basic dGVzdEBleGFtcGxlLmNvbTp0ZXN0LXRva2Vu",
-            ))
+            .and(path("/rest/api/3/issue/PROJ-123/comment/10101"))
+            .and(body_json(serde_json::json!({
+                "body": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "text", "text": "Deployed", "marks": [{ "type": "em" }] }
+                        ]
+                    }]
+                }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "10101",
+                "self": "https://example.atlassian.net/rest/api/3/issue/PROJ-123/comment/10101",
+                "created": "2024-01-17T09:00:00.000+0000",
+                "updated": "2024-01-18T11:30:00.000+0000"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let comment = client
+            .update_comment("PROJ-123", "10101", AddCommentRequest::new("*Deployed*"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            comment.updated.as_deref(),
+            Some("2024-01-18T11:30:00.000+0000")
+        );
+    }
+
+    #[tokio::test]
+    async fn add_comment_posts_plain_text_verbatim() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue/PROJ-123/comment"))
+            .and(body_json(serde_json::json!({
+                "body": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [{ "type": "text", "text": "**literal** `text`" }]
+                    }]
+                }
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "10102",
+                "self": "https://example.atlassian.net/rest/api/3/issue/PROJ-123/comment/10102"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let comment = client
+            .add_comment("PROJ-123", AddCommentRequest::plain("**literal** `text`"))
+            .await
+            .unwrap();
+
+        assert_eq!(comment.id, "10102");
+    }
+
+    #[tokio::test]
+    async fn add_comment_returns_error_when_issue_not_found() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue/PROJ-999/comment"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Issue not found"))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let result = client
+            .add_comment("PROJ-999", AddCommentRequest::new("Test comment"))
+            .await;
+
+        assert!(result.is_err());
+        let error_message = result.unwrap_err().to_string();
+        assert!(error_message.contains("404"));
+    }
+
+    #[tokio::test]
+    async fn update_issue_updates_fields_successfully() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/issue/PROJ-123"))
+            .and(header(
+                "Authorization",
+                "Basic dGVzdEBleGFtcGxlLmNvbTp0ZXN0LXRva2Vu",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let update = UpdateIssueRequest::new()
+            .due_date("2025-02-01")
+            .priority("High");
+
+        let result = client.update_issue("PROJ-123", update).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_issue_with_parent_epic() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/issue/PROJ-456"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let update = UpdateIssueRequest::new().parent("EPIC-100");
+
+        let result = client.update_issue("PROJ-456", update).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_issue_returns_error_when_issue_not_found() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/issue/PROJ-999"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Issue not found"))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let update = UpdateIssueRequest::new().summary("New summary");
+
+        let result = client.update_issue("PROJ-999", update).await;
+
+        assert!(result.is_err());
+        let error_message = result.unwrap_err().to_string();
+        assert!(error_message.contains("404"));
+    }
+
+    #[test]
+    fn client_trims_trailing_slash_from_base_url() {
+        let client = JiraClient::new(
+            "https://example.atlassian.net/",
+            "test@example.com",
+            "token",
+        );
+
+        assert_eq!(client.base_url, "https://example.atlassian.net");
+    }
+
+    #[test]
+    fn browse_url_links_to_the_site() {
+        let client = JiraClient::new("https://example.atlassian.net/", "a@example.com", "token");
+
+        assert_eq!(
+            client.browse_url("PROJ-1").as_deref(),
+            Some("https://example.atlassian.net/browse/PROJ-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn create_issue_returns_created_issue_with_key() {
+        let mock_server = MockServer::start().await;
+        let response_body = CreatedIssue {
+            id: "10200".to_string(),
+            key: "PROJ-200".to_string(),
+            self_url: "https://example.atlassian.net/rest/api/3/issue/10200".to_string(),
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .and(header(
+                "Authorization",
+                "Basic dGVzdEBleGFtcGxlLmNvbTp0ZXN0LXRva2Vu",
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+        let request = CreateIssueRequest::new("PROJ", "Fix login bug", "Story");
+
+        let result = client.create_issue(request).await.unwrap();
+
+        assert_eq!(result.id, "10200");
+        assert_eq!(result.key, "PROJ-200");
+    }
+
+    #[tokio::test]
+    async fn create_issue_with_optional_fields_succeeds() {
+        let mock_server = MockServer::start().await;
+        let response_body = CreatedIssue {
+            id: "10201".to_string(),
+            key: "PROJ-201".to_string(),
+            self_url: "https://example.atlassian.net/rest/api/3/issue/10201".to_string(),
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+        let request = CreateIssueRequest::new("PROJ", "New task", "Task")
+            .description("Task description")
+            .priority("High")
+            .assignee("account-123")
+            .parent("EPIC-10")
+            .labels(vec!["backend", "urgent"])
+            .due_date("2025-12-31");
+
+        let result = client.create_issue(request).await.unwrap();
+
+        assert_eq!(result.key, "PROJ-201");
+    }
+
+    #[tokio::test]
+    async fn create_issue_returns_error_on_api_failure() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("Bad Request"))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+        let request = CreateIssueRequest::new("INVALID", "Test", "Story");
+
+        let result = client.create_issue(request).await;
+
+        assert!(result.is_err());
+        let error_message = result.unwrap_err().to_string();
+        assert!(error_message.contains("400"));
+    }
+
+    #[tokio::test]
+    async fn create_issue_reports_field_validation_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "errorMessages": [],
+                "errors": { "summary": "You must specify a summary of the issue." }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+        let request = CreateIssueRequest::new("PROJ", "", "Story");
+
+        let error = client.create_issue(request).await.unwrap_err();
+
+        let JiraError::Validation { fields, .. } = error else {
+            panic!("expected validation error, got {:?}", error);
+        };
+        assert_eq!(
+            fields["summary"],
+            "You must specify a summary of the issue."
+        );
+    }
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 2,
+            base_delay: std::time::Duration::from_millis(10),
+            max_delay: std::time::Duration::from_secs(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_issue_retries_server_errors_then_succeeds() {
+        let mock_server = MockServer::start().await;
+        let issue = create_test_issue("PROJ-1", "Test", "Open");
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&issue))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_retry_policy(fast_retries());
+
+        let result = client.get_issue("PROJ-1").await.unwrap();
+
+        assert_eq!(result.key, "PROJ-1");
+    }
+
+    #[tokio::test]
+    async fn get_issue_gives_up_after_max_retries() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .respond_with(ResponseTemplate::new(502).set_body_string("Bad Gateway"))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_retry_policy(fast_retries());
+
+        let error = client.get_issue("PROJ-1").await.unwrap_err();
+
+        assert!(matches!(error, JiraError::Server { .. }));
+    }
+
+    #[tokio::test]
+    async fn create_issue_is_not_retried_on_server_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_retry_policy(fast_retries());
+
+        let result = client
+            .create_issue(CreateIssueRequest::new("PROJ", "Test", "Story"))
+            .await;
+
+        assert!(matches!(result, Err(JiraError::Server { .. })));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_requests_honour_retry_after() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .expect(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "10000",
+                "key": "PROJ-1",
+                "self": "https://example.atlassian.net/rest/api/3/issue/10000"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_retry_policy(fast_retries());
+
+        let started = std::time::Instant::now();
+        let created = client
+            .create_issue(CreateIssueRequest::new("PROJ", "Test", "Story"))
+            .await
+            .unwrap();
+
+        assert_eq!(created.key, "PROJ-1");
+        assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_longer_than_max_delay_is_returned() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_retry_policy(fast_retries());
+
+        let error = client.get_issue("PROJ-1").await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            JiraError::RateLimited { retry_after: Some(d), .. } if d.as_secs() == 3600
+        ));
+    }
+
+    #[tokio::test]
+    async fn requests_send_configured_user_agent() {
+        let mock_server = MockServer::start().await;
+        let issue = create_test_issue("PROJ-1", "Test", "Open");
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .and(header("User-Agent", "acme-bot/2.0"))
+            .and(header("Accept", "application/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&issue))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_user_agent("acme-bot/2.0");
+
+        client.get_issue("PROJ-1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn oversized_responses_are_rejected() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(2048)))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_max_response_bytes(1024);
+
+        let error = client.get_issue("PROJ-1").await.unwrap_err();
+
+        assert!(matches!(error, JiraError::ResponseTooLarge { limit: 1024 }));
+    }
+
+    #[tokio::test]
+    async fn slow_responses_time_out() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue"))
+            .respond_with(
+                ResponseTemplate::new(201).set_delay(std::time::Duration::from_millis(500)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token")
+            .with_timeout(std::time::Duration::from_millis(50));
+
+        let error = client
+            .create_issue(CreateIssueRequest::new("PROJ", "Test", "Story"))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, JiraError::Transport(e) if e.is_timeout()));
+    }
+
+    #[tokio::test]
+    async fn add_worklog_sends_seconds_and_estimate_adjustment() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue/PROJ-1/worklog"))
+            .and(query_param("adjustEstimate", "new"))
+            .and(query_param("newEstimate", "2d"))
+            .and(body_partial_json(serde_json::json!({
+                "timeSpentSeconds": 5400,
+                "started": "2024-01-05T09:30:00.000+0000"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "10100",
+                "started": "2024-01-05T09:30:00.000+0000",
+                "timeSpentSeconds": 5400
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+        let request = WorklogRequest::new()
+            .time_spent_seconds(5400)
+            .started("2024-01-05T09:30:00.000+0000");
+
+        let worklog = client
+            .add_worklog(
+                "PROJ-1",
+                request,
+                &EstimateAdjustment::New("2d".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(worklog.id, "10100");
+        assert_eq!(worklog.time_spent_seconds, 5400);
+    }
+
+    #[tokio::test]
+    async fn get_worklogs_returns_paginated_worklogs() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1/worklog"))
+            .and(query_param("startAt", "0"))
+            .and(query_param("maxResults", "50"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 0,
+                "maxResults": 50,
+                "total": 1,
+                "worklogs": [{
+                    "id": "10100",
+                    "author": { "displayName": "Alice", "accountId": "abc" },
+                    "started": "2024-01-05T09:30:00.000+0000",
+                    "timeSpentSeconds": 3600
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let response = client.get_worklogs("PROJ-1", 0, 50).await.unwrap();
+
+        assert_eq!(response.total, 1);
+        assert_eq!(
+            response.worklogs[0].author.as_ref().unwrap().display_name,
+            "Alice"
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_worklog_increases_estimate_manually() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/rest/api/3/issue/PROJ-1/worklog/10100"))
+            .and(query_param("adjustEstimate", "manual"))
+            .and(query_param("increaseBy", "1h"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        client
+            .delete_worklog(
+                "PROJ-1",
+                "10100",
+                &EstimateAdjustment::Manual("1h".to_string()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn add_attachment_uploads_multipart_with_xsrf_header() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue/PROJ-1/attachments"))
+            .and(header("X-Atlassian-Token", "no-check"))
+            .and(header_regex(
+                "Content-Type",
+                "^multipart/form-data; boundary=",
+            ))
+            .and(body_string_contains("filename=\"build.log\""))
+            .and(body_string_contains("all tests passed"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": "10001",
+                    "filename": "build.log",
+                    "size": 16,
+                    "mimeType": "text/plain"
+                }])),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let attachments = client
+            .add_attachment("PROJ-1", "build.log", b"all tests passed".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(attachments[0].id, "10001");
+    }
+
+    #[tokio::test]
+    async fn get_attachments_reads_attachment_field() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .and(query_param("fields", "attachment"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "10001",
+                "key": "PROJ-1",
+                "self": "https://example.atlassian.net/rest/api/3/issue/10001",
+                "fields": {
+                    "attachment": [
+                        { "id": "1", "filename": "a.png", "size": 2048, "mimeType": "image/png" }
+                    ]
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let attachments = client.get_attachments("PROJ-1").await.unwrap();
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[tokio::test]
+    async fn download_attachment_fetches_metadata_and_content() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/attachment/10001"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "10001",
+                "filename": "build.log",
+                "size": 16,
+                "mimeType": "text/plain",
+                "content": "https://example.atlassian.net/rest/api/3/attachment/content/10001"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/attachment/content/10001"))
+            .and(header("Accept", "*/*"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"all tests passed".to_vec()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let (attachment, data) = client.download_attachment("10001").await.unwrap();
+
+        assert_eq!(attachment.filename, "build.log");
+        assert_eq!(data, b"all tests passed");
+    }
+
+    #[tokio::test]
+    async fn download_attachment_refuses_oversized_files() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/attachment/10001"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "10001",
+                "filename": "video.mp4",
+                "size": 50_000_000,
+                "mimeType": "video/mp4"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/attachment/content/10001"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let error = client.download_attachment("10001").await.unwrap_err();
+
+        assert!(matches!(error, JiraError::ResponseTooLarge { .. }));
+    }
+
+    #[tokio::test]
+    async fn link_issues_posts_link_and_delete_removes_it() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issueLink"))
+            .and(body_json(serde_json::json!({
+                "type": { "name": "Blocks" },
+                "inwardIssue": { "key": "PROJ-1" },
+                "outwardIssue": { "key": "PROJ-2" }
+            })))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/rest/api/3/issueLink/10050"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        client
+            .link_issues(LinkIssuesRequest::new(
+                "Blocks",
+                "PROJ-1",
+                "PROJ-2",
+                LinkDirection::Outward,
+            ))
+            .await
+            .unwrap();
+        client.delete_issue_link("10050").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_issue_and_comment_send_deletes() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/rest/api/3/issue/PROJ-9"))
+            .and(query_param("deleteSubtasks", "true"))
             .respond_with(ResponseTemplate::new(204))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
-        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
-
-        let update = UpdateIssueRequest::new()
-            .due_date("2025-02-01")
-            .priority("High");
+        Mock::given(method("DELETE"))
+            .and(path("/rest/api/3/issue/PROJ-1/comment/10100"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
 
-        let result = client.update_issue("PROJ-123", update).await;
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
 
-        assert!(result.is_ok());
+        client.delete_issue("PROJ-9", true).await.unwrap();
+        client.delete_comment("PROJ-1", "10100").await.unwrap();
     }
 
     #[tokio::test]
-    async fn update_issue_with_parent_epic() {
+    async fn get_issue_link_types_lists_types() {
         let mock_server = MockServer::start().await;
 
-        Mock::given(method("PUT"))
-            .and(path("/rest/api/3/issue/PROJ-456"))
-            .respond_with(ResponseTemplate::new(204))
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issueLinkType"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issueLinkTypes": [
+                    { "id": "10000", "name": "Blocks", "inward": "is blocked by", "outward": "blocks" }
+                ]
+            })))
             .mount(&mock_server)
             .await;
 
         let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
 
-        let update = UpdateIssueRequest::new().parent("EPIC-100");
-
-        let result = client.update_issue("PROJ-456", update).await;
+        let response = client.get_issue_link_types().await.unwrap();
 
-        assert!(result.is_ok());
+        assert_eq!(response.issue_link_types.len(), 1);
+        assert_eq!(response.issue_link_types[0].outward, "blocks");
     }
 
     #[tokio::test]
-    async fn update_issue_returns_error_when_issue_not_found() {
+    async fn get_fields_is_cached_until_refreshed() {
         let mock_server = MockServer::start().await;
 
-        Mock::given(method("PUT"))
-            .and(path("/rest/api/3/issue/PROJ-999"))
-            .respond_with(ResponseTemplate::new(404).set_body_string("Issue not found"))
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/field"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": "customfield_10016", "name": "Story Points", "custom": true,
+                  "schema": { "type": "number", "custom": "com.atlassian.jira.plugin.system.customfieldtypes:float" } }
+            ])))
+            .expect(2)
             .mount(&mock_server)
             .await;
 
         let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
 
-        let update = UpdateIssueRequest::new().summary("New summary");
-
-        let result = client.update_issue("PROJ-999", update).await;
-
-        assert!(result.is_err());
-        let error_message = result.unwrap_err().to_string();
-        assert!(error_message.contains("404"));
-    }
-
-    #[test]
-    fn client_trims_trailing_slash_from_base_url() {
-        let client = JiraClient::new(
-            "https://example.atlassian.net/",
-            "test@example.com",
-            "token",
+        let catalogue = client.get_fields(false).await.unwrap();
+        assert_eq!(
+            catalogue.resolve("story points").unwrap().id,
+            "customfield_10016"
         );
 
-        assert_eq!(client.base_url, "https://example.atlassian.net");
+        // Clones share the cache
+        client.clone().get_fields(false).await.unwrap();
+        client.get_fields(true).await.unwrap();
     }
 
     #[tokio::test]
-    async fn create_issue_returns_created_issue_with_key() {
+    async fn get_changelog_pages_until_last() {
         let mock_server = MockServer::start().await;
-        let response_body = CreatedIssue {
-            id: "10200".to_string(),
-            key: "PROJ-200".to_string(),
-            self_url: "https://example.atlassian.net/rest/api/3/issue/10200".to_string(),
+        let entry = |id: &str| {
+            serde_json::json!({
+                "id": id,
+                "created": "2025-01-10T09:00:00.000+0000",
+                "items": [{ "field": "status", "fromString": "To Do", "toString": "Done" }]
+            })
         };
 
-        Mock::given(method("POST"))
-            .and(path("/rest/api/3/issue"))
-            .and(header(
-                "Authorization",
-                "Basic dGVzdEBleGFtcGxlLmNvbTp0ZXN0LXRva2Vu",
-            ))
-            .respond_with(ResponseTemplate::new(201).set_body_json(&response_body))
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1/changelog"))
+            .and(query_param("startAt", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 0, "maxResults": 2, "total": 3, "isLast": false,
+                "values": [entry("1"), entry("2")]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1/changelog"))
+            .and(query_param("startAt", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 2, "maxResults": 2, "total": 3, "isLast": true,
+                "values": [entry("3")]
+            })))
             .mount(&mock_server)
             .await;
 
         let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
-        let request = CreateIssueRequest::new("PROJ", "Fix login bug", "Story");
 
-        let result = client.create_issue(request).await.unwrap();
+        let entries = client.get_changelog("PROJ-1").await.unwrap();
 
-        assert_eq!(result.id, "10200");
-        assert_eq!(result.key, "PROJ-200");
+        let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
     }
 
     #[tokio::test]
-    async fn create_issue_with_optional_fields_succeeds() {
+    async fn data_center_get_changelog_expands_issue() {
         let mock_server = MockServer::start().await;
-        let response_body = CreatedIssue {
-            id: "10201".to_string(),
-            key: "PROJ-201".to_string(),
-            self_url: "https://example.atlassian.net/rest/api/3/issue/10201".to_string(),
-        };
 
-        Mock::given(method("POST"))
-            .and(path("/rest/api/3/issue"))
-            .respond_with(ResponseTemplate::new(201).set_body_json(&response_body))
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/issue/PROJ-1"))
+            .and(query_param("expand", "changelog"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "key": "PROJ-1",
+                "changelog": {
+                    "startAt": 0, "maxResults": 1, "total": 1,
+                    "histories": [{
+                        "id": "1",
+                        "created": "2025-01-10T09:00:00.000+0000",
+                        "items": [{ "field": "assignee", "from": "alice", "fromString": "Alice", "to": "bob", "toString": "Bob" }]
+                    }]
+                }
+            })))
             .mount(&mock_server)
             .await;
 
-        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
-        let request = CreateIssueRequest::new("PROJ", "New task", "Task")
-            .description("Task description")
-            .priority("High")
-            .assignee("account-123")
-            .parent("EPIC-10")
-            .labels(vec!["backend", "urgent"])
-            .due_date("2025-12-31");
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat")
+            .with_deployment(Deployment::DataCenter);
 
-        let result = client.create_issue(request).await.unwrap();
+        let entries = client.get_changelog("PROJ-1").await.unwrap();
 
-        assert_eq!(result.key, "PROJ-201");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].items[0].to_text.as_deref(), Some("Bob"));
     }
 
     #[tokio::test]
-    async fn create_issue_returns_error_on_api_failure() {
+    async fn get_create_issue_types_pages_through_results() {
         let mock_server = MockServer::start().await;
 
-        Mock::given(method("POST"))
-            .and(path("/rest/api/3/issue"))
-            .respond_with(ResponseTemplate::new(400).set_body_string("Bad Request"))
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/createmeta/PROJ/issuetypes"))
+            .and(query_param("startAt", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 0, "maxResults": 1, "total": 2,
+                "issueTypes": [{ "id": "1", "name": "Bug", "subtask": false }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/createmeta/PROJ/issuetypes"))
+            .and(query_param("startAt", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 1, "maxResults": 1, "total": 2,
+                "issueTypes": [{ "id": "5", "name": "Sub-task", "subtask": true }]
+            })))
             .mount(&mock_server)
             .await;
 
         let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
-        let request = CreateIssueRequest::new("INVALID", "Test", "Story");
 
-        let result = client.create_issue(request).await;
+        let issue_types = client.get_create_issue_types("PROJ").await.unwrap();
 
-        assert!(result.is_err());
-        let error_message = result.unwrap_err().to_string();
-        assert!(error_message.contains("400"));
+        let names: Vec<&str> = issue_types
+            .issue_types
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Bug", "Sub-task"]);
+        assert!(issue_types.issue_types[1].subtask);
+    }
+
+    #[tokio::test]
+    async fn data_center_get_create_fields_reads_values() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/issue/createmeta/PROJ/issuetypes/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 0, "maxResults": 50, "total": 1, "isLast": true,
+                "values": [{
+                    "fieldId": "priority",
+                    "name": "Priority",
+                    "required": true,
+                    "hasDefaultValue": true,
+                    "schema": { "type": "priority", "system": "priority" },
+                    "allowedValues": [{ "id": "1", "name": "High" }, { "id": "2", "name": "Low" }]
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat")
+            .with_deployment(Deployment::DataCenter);
+
+        let fields = client.get_create_fields("PROJ", "1").await.unwrap();
+
+        assert_eq!(fields.fields.len(), 1);
+        assert!(!fields.fields[0].needs_value());
+        assert_eq!(fields.fields[0].allowed_names(), vec!["High", "Low"]);
     }
 
     #[test]
@@ -627,10 +2138,10 @@ mod tests {
             "api-token",
         );
 
-        assert_eq!(
-            client.auth_header,
-            "Basic dXNlckBleGFtcGxlLmNvbTphcGktdG9rZW4="
-        );
+        assert!(matches!(
+            &client.credentials,
+            Credentials::Static(header) if header == "Basic dXNlckBleGFtcGxlLmNvbTphcGktdG9rZW4="
+        ));
     }
 
     #[tokio::test]
@@ -649,8 +2160,10 @@ mod tests {
                         display_name: "Alice".to_string(),
                         email_address: Some("alice@example.com".to_string()),
                         account_id: Some("alice-account-id".to_string()),
+                        name: None,
                     }),
                     created: Some("2024-01-15T10:00:00.000+0000".to_string()),
+                    updated: None,
                     body: Some(serde_json::json!({
                         "type": "doc",
                         "version": 1,
@@ -668,8 +2181,10 @@ mod tests {
                         display_name: "Bob".to_string(),
                         email_address: Some("bob@example.com".to_string()),
                         account_id: Some("bob-account-id".to_string()),
+                        name: None,
                     }),
                     created: Some("2024-01-16T14:00:00.000+0000".to_string()),
+                    updated: None,
                     body: Some(serde_json::json!({
                         "type": "doc",
                         "version": 1,
@@ -744,6 +2259,289 @@ mod tests {
         let error_message = result.unwrap_err().to_string();
         assert!(error_message.contains("404"));
     }
+
+    #[tokio::test]
+    async fn get_transitions_returns_available_transitions() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-123/transitions"))
+            .and(query_param("expand", "transitions.fields"))
+            .and(header(
+                "Authorization",
+                "Basic dGVzdEBleGFtcGxlLmNvbTp0ZXN0LXRva2Vu",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "transitions": [
+                    { "id": "11", "name": "Start Progress", "to": { "name": "In Progress" } },
+                    {
+                        "id": "31",
+                        "name": "Done",
+                        "to": { "name": "Done" },
+                        "hasScreen": true,
+                        "fields": {
+                            "resolution": { "required": true, "name": "Resolution" }
+                        }
+                    }
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let result = client.get_transitions("PROJ-123").await.unwrap();
+
+        assert_eq!(result.transitions.len(), 2);
+        assert_eq!(result.transitions[0].id, "11");
+        assert!(result.transitions[1].has_screen);
+        assert!(result.transitions[1].fields["resolution"].required);
+    }
+
+    #[tokio::test]
+    async fn transition_issue_posts_transition_id() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue/PROJ-123/transitions"))
+            .and(body_partial_json(serde_json::json!({
+                "transition": { "id": "31" },
+                "fields": { "resolution": { "name": "Done" } }
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+        let request = TransitionIssueRequest::new("31").resolution("Done");
+
+        let result = client.transition_issue("PROJ-123", request).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn transition_issue_returns_error_on_invalid_transition() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/issue/PROJ-123/transitions"))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .set_body_string("Transition id '999' is not valid for this issue."),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let result = client
+            .transition_issue("PROJ-123", TransitionIssueRequest::new("999"))
+            .await;
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("400"));
+    }
+
+    #[test]
+    fn client_with_personal_access_token_uses_bearer_auth() {
+        let client =
+            JiraClient::with_personal_access_token("https://jira.example.com/", "pat-token");
+
+        assert!(matches!(
+            &client.credentials,
+            Credentials::Static(header) if header == "Bearer pat-token"
+        ));
+        assert_eq!(client.deployment, Deployment::DataCenter);
+        assert_eq!(
+            client.api_url("issue/PROJ-1"),
+            "https://jira.example.com/rest/api/2/issue/PROJ-1"
+        );
+    }
+
+    #[test]
+    fn deployment_parses_names() {
+        assert_eq!(Deployment::from_name("cloud"), Some(Deployment::Cloud));
+        assert_eq!(
+            Deployment::from_name("Server"),
+            Some(Deployment::DataCenter)
+        );
+        assert_eq!(
+            Deployment::from_name("datacenter"),
+            Some(Deployment::DataCenter)
+        );
+        assert_eq!(Deployment::from_name("other"), None);
+    }
+
+    #[tokio::test]
+    async fn data_center_get_issue_uses_api_v2_and_bearer_token() {
+        let mock_server = MockServer::start().await;
+        let expected_issue = create_test_issue("PROJ-456", "On prem", "Open");
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/issue/PROJ-456"))
+            .and(header("Authorization", "Bearer pat-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&expected_issue))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat-token");
+
+        let issue = client.get_issue("PROJ-456").await.unwrap();
+
+        assert_eq!(issue.key, "PROJ-456");
+    }
+
+    #[tokio::test]
+    async fn data_center_search_pages_by_offset() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/2/search"))
+            .and(body_partial_json(
+                serde_json::json!({ "startAt": 1, "maxResults": 1 }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 1,
+                "maxResults": 1,
+                "total": 3,
+                "issues": [create_test_issue("PROJ-2", "Second", "Open")]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat-token");
+
+        let result = client
+            .search_issues_page("project = PROJ", 1, Some("1"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.issues[0].key, "PROJ-2");
+        assert_eq!(result.next_page_token.as_deref(), Some("2"));
+        assert_eq!(result.is_last, Some(false));
+    }
+
+    #[tokio::test]
+    async fn data_center_create_issue_sends_wiki_markup_and_username() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/2/issue"))
+            .and(body_partial_json(serde_json::json!({
+                "fields": {
+                    "description": "h2. Steps\n\n* *first*",
+                    "assignee": { "name": "jsmith" }
+                }
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "10300",
+                "key": "PROJ-300",
+                "self": "https://jira.example.com/rest/api/2/issue/10300"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat-token");
+        let request = CreateIssueRequest::new("PROJ", "On prem", "Task")
+            .description("## Steps\n\n- **first**")
+            .assignee("jsmith");
+
+        let created = client.create_issue(request).await.unwrap();
+
+        assert_eq!(created.key, "PROJ-300");
+    }
+
+    #[tokio::test]
+    async fn data_center_add_comment_sends_wiki_markup() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/2/issue/PROJ-1/comment"))
+            .and(body_json(
+                serde_json::json!({ "body": "Fixed in {{v1.2}}" }),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "10400",
+                "self": "https://jira.example.com/rest/api/2/issue/PROJ-1/comment/10400",
+                "body": "Fixed in {{v1.2}}"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat-token");
+
+        let comment = client
+            .add_comment("PROJ-1", AddCommentRequest::new("Fixed in `v1.2`"))
+            .await
+            .unwrap();
+
+        assert_eq!(comment.id, "10400");
+    }
+
+    #[tokio::test]
+    async fn oauth_client_refreshes_token_after_unauthorized() {
+        let mock_server = MockServer::start().await;
+        let issue = create_test_issue("PROJ-1", "Test", "Open");
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .and(header("Authorization", "Bearer revoked-access"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "fresh-access",
+                "refresh_token": "refresh-2",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .and(header("Authorization", "Bearer fresh-access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&issue))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let token_file = std::env::temp_dir()
+            .join(format!("jira-mcp-rs-test-{}-client", std::process::id()))
+            .join("oauth-token.json");
+        let mut config = oauth::OAuthConfig::new("client", "secret", token_file.clone());
+        config.token_url = format!("{}/oauth/token", mock_server.uri());
+        let token = oauth::StoredToken {
+            access_token: "revoked-access".to_string(),
+            refresh_token: "refresh-1".to_string(),
+            expires_at: u64::MAX,
+            cloud_id: "cloud-123".to_string(),
+            site_url: None,
+        };
+
+        let mut client = JiraClient::with_oauth(OAuthSession::new(config, token));
+        assert_eq!(
+            client.base_url,
+            "https://api.atlassian.com/ex/jira/cloud-123"
+        );
+        client.base_url = mock_server.uri();
+
+        let result = client.get_issue("PROJ-1").await.unwrap();
+
+        assert_eq!(result.key, "PROJ-1");
+        std::fs::remove_dir_all(token_file.parent().unwrap()).ok();
+    }
+
     #[test]
     fn test_deserialization_with_missing_fields() {
         // Simulating a response from search_issues where "fields" are restricted
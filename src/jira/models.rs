@@ -1,177 +1,359 @@
-use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::time::{parse_timestamp, to_jira_timestamp};
+use super::{Deployment, markdown_to_wiki};
+
 /// Convert Markdown text to Atlassian Document Format (ADF) JSON
-fn markdown_to_adf(markdown: &str) -> serde_json::Value {
-    let parser = Parser::new_ext(markdown, Options::all());
-
-    let mut doc_content: Vec<serde_json::Value> = Vec::new();
-    let mut current_paragraph: Vec<serde_json::Value> = Vec::new();
-    let mut current_list_items: Vec<serde_json::Value> = Vec::new();
-    let mut current_list_item_content: Vec<serde_json::Value> = Vec::new();
-    let mut in_list = false;
-    let mut in_list_item = false;
-    let mut bold = false;
-    let mut italic = false;
-    let mut code = false;
-    let mut heading_level: Option<u32> = None;
-    let mut current_heading: Vec<serde_json::Value> = Vec::new();
-
-    let flush_paragraph =
-        |content: &mut Vec<serde_json::Value>, doc: &mut Vec<serde_json::Value>| {
-            if !content.is_empty() {
-                doc.push(serde_json::json!({
-                    "type": "paragraph",
-                    "content": content.clone()
-                }));
-                content.clear();
-            }
-        };
+///
+/// Supports headings, paragraphs, bullet/ordered/task lists (with nesting),
+/// blockquotes, code blocks with language, tables, horizontal rules and the
+/// strong/em/strike/code/link marks. Images become links since ADF media
+/// must be uploaded as attachments first.
+pub fn markdown_to_adf(markdown: &str) -> serde_json::Value {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut builder = AdfBuilder::new();
+
+    for event in Parser::new_ext(markdown, options) {
+        builder.event(event);
+    }
+
+    builder.finish()
+}
+
+/// A block node under construction.
+struct AdfFrame {
+    node_type: &'static str,
+    attrs: Option<serde_json::Value>,
+    content: Vec<serde_json::Value>,
+    /// Paragraph opened automatically to hold inline content (e.g. in tight list items)
+    implicit: bool,
+    /// For list items, the checkbox state from a task list marker.
+    /// For lists, set when any item is a task.
+    task: Option<bool>,
+}
+
+impl AdfFrame {
+    fn new(node_type: &'static str, attrs: Option<serde_json::Value>) -> Self {
+        Self {
+            node_type,
+            attrs,
+            content: Vec::new(),
+            implicit: false,
+            task: None,
+        }
+    }
+}
+
+/// Builds an ADF tree from pulldown-cmark events using a stack of open block nodes.
+struct AdfBuilder {
+    stack: Vec<AdfFrame>,
+    marks: Vec<serde_json::Value>,
+    in_table_head: bool,
+    next_local_id: u32,
+}
 
-    for event in parser {
+impl AdfBuilder {
+    fn new() -> Self {
+        Self {
+            stack: vec![AdfFrame::new("doc", None)],
+            marks: Vec::new(),
+            in_table_head: false,
+            next_local_id: 0,
+        }
+    }
+
+    fn event(&mut self, event: Event) {
         match event {
-            Event::Start(Tag::Heading { level, .. }) => {
-                flush_paragraph(&mut current_paragraph, &mut doc_content);
-                heading_level = Some(match level {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => self.text(&text),
+            Event::Code(text) => {
+                // ADF only allows the code mark to be combined with links
+                let mut marks = vec![serde_json::json!({ "type": "code" })];
+                marks.extend(self.marks.iter().filter(|m| m["type"] == "link").cloned());
+                self.push_inline(text_node(&text, marks));
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                self.push_inline(serde_json::json!({ "type": "hardBreak" }))
+            }
+            Event::Rule => {
+                self.close_implicit();
+                self.top()
+                    .content
+                    .push(serde_json::json!({ "type": "rule" }));
+            }
+            Event::TaskListMarker(checked) => self.mark_task(checked),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.open("paragraph", None),
+            Tag::Heading { level, .. } => {
+                let level = match level {
                     HeadingLevel::H1 => 1,
                     HeadingLevel::H2 => 2,
                     HeadingLevel::H3 => 3,
                     HeadingLevel::H4 => 4,
                     HeadingLevel::H5 => 5,
                     HeadingLevel::H6 => 6,
-                });
-                current_heading.clear();
-            }
-            Event::End(TagEnd::Heading(_)) => {
-                if let Some(level) = heading_level.take() {
-                    doc_content.push(serde_json::json!({
-                        "type": "heading",
-                        "attrs": { "level": level },
-                        "content": current_heading.clone()
-                    }));
-                    current_heading.clear();
-                }
+                };
+                self.open("heading", Some(serde_json::json!({ "level": level })));
             }
-            Event::Start(Tag::Paragraph) => {
-                if !in_list {
-                    current_paragraph.clear();
-                }
+            Tag::BlockQuote(_) => self.open("blockquote", None),
+            Tag::CodeBlock(kind) => {
+                let attrs = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .map(|language| serde_json::json!({ "language": language })),
+                    CodeBlockKind::Indented => None,
+                };
+                self.open("codeBlock", attrs);
             }
-            Event::End(TagEnd::Paragraph) => {
-                if !in_list {
-                    flush_paragraph(&mut current_paragraph, &mut doc_content);
-                }
+            Tag::List(Some(start)) => {
+                self.open("orderedList", Some(serde_json::json!({ "order": start })))
             }
-            Event::Start(Tag::List(_)) => {
-                flush_paragraph(&mut current_paragraph, &mut doc_content);
-                in_list = true;
-                current_list_items.clear();
-            }
-            Event::End(TagEnd::List(_)) => {
-                if !current_list_item_content.is_empty() {
-                    current_list_items.push(serde_json::json!({
-                        "type": "listItem",
-                        "content": [{
-                            "type": "paragraph",
-                            "content": current_list_item_content.clone()
-                        }]
-                    }));
-                    current_list_item_content.clear();
-                }
-                doc_content.push(serde_json::json!({
-                    "type": "bulletList",
-                    "content": current_list_items.clone()
+            Tag::List(None) => self.open("bulletList", None),
+            Tag::Item => self.open("listItem", None),
+            Tag::Table(_) => self.open("table", None),
+            Tag::TableHead => {
+                self.in_table_head = true;
+                self.open("tableRow", None);
+            }
+            Tag::TableRow => self.open("tableRow", None),
+            Tag::TableCell => {
+                let cell_type = if self.in_table_head {
+                    "tableHeader"
+                } else {
+                    "tableCell"
+                };
+                self.open(cell_type, None);
+            }
+            Tag::Strong => self.marks.push(serde_json::json!({ "type": "strong" })),
+            Tag::Emphasis => self.marks.push(serde_json::json!({ "type": "em" })),
+            Tag::Strikethrough => self.marks.push(serde_json::json!({ "type": "strike" })),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.marks.push(serde_json::json!({
+                    "type": "link",
+                    "attrs": { "href": dest_url.as_ref() }
                 }));
-                current_list_items.clear();
-                in_list = false;
-            }
-            Event::Start(Tag::Item) => {
-                if !current_list_item_content.is_empty() {
-                    current_list_items.push(serde_json::json!({
-                        "type": "listItem",
-                        "content": [{
-                            "type": "paragraph",
-                            "content": current_list_item_content.clone()
-                        }]
-                    }));
-                    current_list_item_content.clear();
-                }
-                in_list_item = true;
             }
-            Event::End(TagEnd::Item) => {
-                in_list_item = false;
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Strong
+            | TagEnd::Emphasis
+            | TagEnd::Strikethrough
+            | TagEnd::Link
+            | TagEnd::Image => {
+                self.marks.pop();
             }
-            Event::Start(Tag::Strong) => bold = true,
-            Event::End(TagEnd::Strong) => bold = false,
-            Event::Start(Tag::Emphasis) => italic = true,
-            Event::End(TagEnd::Emphasis) => italic = false,
-            Event::Start(Tag::CodeBlock(_)) => code = true,
-            Event::End(TagEnd::CodeBlock) => code = false,
-            Event::Code(text) => {
-                let node = serde_json::json!({
-                    "type": "text",
-                    "text": text.as_ref(),
-                    "marks": [{ "type": "code" }]
-                });
-                if heading_level.is_some() {
-                    current_heading.push(node);
-                } else if in_list_item {
-                    current_list_item_content.push(node);
-                } else {
-                    current_paragraph.push(node);
-                }
+            TagEnd::TableHead => {
+                self.close();
+                self.in_table_head = false;
             }
-            Event::Text(text) => {
-                if code {
-                    doc_content.push(serde_json::json!({
-                        "type": "codeBlock",
-                        "content": [{ "type": "text", "text": text.as_ref() }]
-                    }));
-                    continue;
-                }
-                let mut marks: Vec<serde_json::Value> = Vec::new();
-                if bold {
-                    marks.push(serde_json::json!({ "type": "strong" }));
-                }
-                if italic {
-                    marks.push(serde_json::json!({ "type": "em" }));
-                }
-                let node = if marks.is_empty() {
-                    serde_json::json!({ "type": "text", "text": text.as_ref() })
-                } else {
-                    serde_json::json!({ "type": "text", "text": text.as_ref(), "marks": marks })
-                };
-                if heading_level.is_some() {
-                    current_heading.push(node);
-                } else if in_list_item {
-                    current_list_item_content.push(node);
-                } else {
-                    current_paragraph.push(node);
+            TagEnd::Paragraph
+            | TagEnd::Heading(_)
+            | TagEnd::BlockQuote(_)
+            | TagEnd::CodeBlock
+            | TagEnd::List(_)
+            | TagEnd::Item
+            | TagEnd::Table
+            | TagEnd::TableRow
+            | TagEnd::TableCell => self.close(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let top = self.top();
+        if top.node_type == "codeBlock" {
+            match top.content.last_mut().and_then(|n| n.get_mut("text")) {
+                Some(serde_json::Value::String(existing)) => existing.push_str(text),
+                _ => top.content.push(text_node(text, Vec::new())),
+            }
+            return;
+        }
+        let marks = self.marks.clone();
+        self.push_inline(text_node(text, marks));
+    }
+
+    fn top(&mut self) -> &mut AdfFrame {
+        self.stack.last_mut().expect("doc frame is never popped")
+    }
+
+    fn open(&mut self, node_type: &'static str, attrs: Option<serde_json::Value>) {
+        self.close_implicit();
+        self.stack.push(AdfFrame::new(node_type, attrs));
+    }
+
+    fn push_inline(&mut self, node: serde_json::Value) {
+        if !matches!(self.top().node_type, "paragraph" | "heading") {
+            let mut paragraph = AdfFrame::new("paragraph", None);
+            paragraph.implicit = true;
+            self.stack.push(paragraph);
+        }
+        self.top().content.push(node);
+    }
+
+    fn close_implicit(&mut self) {
+        if self.top().implicit {
+            self.pop_frame();
+        }
+    }
+
+    fn close(&mut self) {
+        self.close_implicit();
+        if self.stack.len() > 1 {
+            self.pop_frame();
+        }
+    }
+
+    fn pop_frame(&mut self) {
+        let frame = self.stack.pop().expect("frame to pop");
+        let nodes = self.finalize(frame);
+        self.top().content.extend(nodes);
+    }
+
+    /// Mark the innermost list item (and its list) as a task.
+    fn mark_task(&mut self, checked: bool) {
+        if let Some(index) = self.stack.iter().rposition(|f| f.node_type == "listItem") {
+            self.stack[index].task = Some(checked);
+            if index > 0 {
+                self.stack[index - 1].task = Some(true);
+            }
+        }
+    }
+
+    fn local_id(&mut self) -> String {
+        self.next_local_id += 1;
+        self.next_local_id.to_string()
+    }
+
+    /// Turn a finished frame into the node(s) to append to its parent.
+    fn finalize(&mut self, frame: AdfFrame) -> Vec<serde_json::Value> {
+        let AdfFrame {
+            node_type,
+            attrs,
+            mut content,
+            task,
+            ..
+        } = frame;
+
+        match node_type {
+            "listItem" if task.is_some() => {
+                return self.task_item(content, task == Some(true));
+            }
+            "bulletList" | "orderedList" if task.is_some() => {
+                let mut items = Vec::new();
+                for node in content {
+                    if node["type"] == "listItem" {
+                        let blocks = node["content"].as_array().cloned().unwrap_or_default();
+                        items.extend(self.task_item(blocks, false));
+                    } else {
+                        items.push(node);
+                    }
                 }
+                return vec![serde_json::json!({
+                    "type": "taskList",
+                    "attrs": { "localId": self.local_id() },
+                    "content": items
+                })];
             }
-            Event::SoftBreak | Event::HardBreak => {
-                let node = serde_json::json!({ "type": "hardBreak" });
-                if heading_level.is_some() {
-                    current_heading.push(node);
-                } else if in_list_item {
-                    current_list_item_content.push(node);
-                } else {
-                    current_paragraph.push(node);
+            "codeBlock" => {
+                if let Some(serde_json::Value::String(text)) =
+                    content.last_mut().and_then(|n| n.get_mut("text"))
+                {
+                    let trimmed = text.trim_end_matches('\n').len();
+                    text.truncate(trimmed);
                 }
+                content.retain(|n| n["text"] != "");
+            }
+            "listItem" | "tableCell" | "tableHeader" if content.is_empty() => {
+                content.push(serde_json::json!({ "type": "paragraph", "content": [] }));
             }
             _ => {}
         }
+
+        let mut node = serde_json::json!({ "type": node_type, "content": content });
+        if let Some(attrs) = attrs {
+            node["attrs"] = attrs;
+        }
+        vec![node]
+    }
+
+    /// Build a `taskItem` from list item blocks. Task items only hold inline
+    /// content, so paragraphs are joined with hard breaks and nested task lists
+    /// are emitted as siblings, as ADF expects.
+    fn task_item(
+        &mut self,
+        blocks: Vec<serde_json::Value>,
+        checked: bool,
+    ) -> Vec<serde_json::Value> {
+        let mut inline = Vec::new();
+        let mut siblings = Vec::new();
+
+        for block in blocks {
+            if block["type"] == "taskList" {
+                siblings.push(block);
+                continue;
+            }
+            if !inline.is_empty() {
+                inline.push(serde_json::json!({ "type": "hardBreak" }));
+            }
+            collect_inline(&block, &mut inline);
+        }
+
+        let state = if checked { "DONE" } else { "TODO" };
+        let mut nodes = vec![serde_json::json!({
+            "type": "taskItem",
+            "attrs": { "localId": self.local_id(), "state": state },
+            "content": inline
+        })];
+        nodes.extend(siblings);
+        nodes
+    }
+
+    fn finish(mut self) -> serde_json::Value {
+        while self.stack.len() > 1 {
+            self.pop_frame();
+        }
+        let doc = self.stack.pop().expect("doc frame");
+
+        serde_json::json!({
+            "type": "doc",
+            "version": 1,
+            "content": doc.content
+        })
     }
+}
 
-    // flush remaining paragraph
-    flush_paragraph(&mut current_paragraph, &mut doc_content);
+fn text_node(text: &str, marks: Vec<serde_json::Value>) -> serde_json::Value {
+    if marks.is_empty() {
+        serde_json::json!({ "type": "text", "text": text })
+    } else {
+        serde_json::json!({ "type": "text", "text": text, "marks": marks })
+    }
+}
 
-    serde_json::json!({
-        "type": "doc",
-        "version": 1,
-        "content": doc_content
-    })
+/// Collect the inline leaves (text, hard breaks) of a block node.
+fn collect_inline(node: &serde_json::Value, out: &mut Vec<serde_json::Value>) {
+    match node["type"].as_str() {
+        Some("text") | Some("hardBreak") => out.push(node.clone()),
+        _ => {
+            for child in node["content"].as_array().into_iter().flatten() {
+                collect_inline(child, out);
+            }
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -180,8 +362,18 @@ pub struct SearchRequest {
     pub jql: String,
     pub max_results: u32,
     pub fields: Vec<String>,
+    /// Token from a previous response's `nextPageToken` to fetch the following page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    /// Offset for Data Center's `/rest/api/2/search`, which has no page tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<u32>,
 }
 
+/// Response from POST /rest/api/3/search/jql
+///
+/// The enhanced search endpoint pages with `nextPageToken`/`isLast` and no longer
+/// reports `total`; the offset fields are kept for older responses.
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchResult {
@@ -189,6 +381,11 @@ pub struct SearchResult {
     pub max_results: Option<u32>,
     pub start_at: Option<u32>,
     pub issues: Vec<Issue>,
+    /// Token for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_last: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -200,7 +397,7 @@ pub struct Issue {
     pub fields: IssueFields,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct IssueFields {
     pub summary: Option<String>,
     pub status: Option<Status>,
@@ -211,6 +408,49 @@ pub struct IssueFields {
     pub created: Option<String>,
     pub updated: Option<String>,
     pub description: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timetracking: Option<TimeTracking>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<Vec<Attachment>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuelinks: Option<Vec<IssueLink>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+    #[serde(
+        default,
+        rename = "fixVersions",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fix_versions: Option<Vec<Version>>,
+    /// Affects versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<Vec<Version>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+}
+
+/// Attachment metadata, from the `attachment` issue field or an upload response.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: String,
+    pub filename: String,
+    pub author: Option<User>,
+    pub created: Option<String>,
+    #[serde(default)]
+    pub size: u64,
+    pub mime_type: Option<String>,
+    /// Download URL
+    pub content: Option<String>,
+}
+
+/// Estimates and logged time, in seconds.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeTracking {
+    pub original_estimate_seconds: Option<u64>,
+    pub remaining_estimate_seconds: Option<u64>,
+    pub time_spent_seconds: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -232,6 +472,20 @@ pub struct CommentResponse {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Status {
     pub name: String,
+    #[serde(
+        default,
+        rename = "statusCategory",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_category: Option<StatusCategory>,
+}
+
+/// The workflow stage a status belongs to.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StatusCategory {
+    /// "new" (To Do), "indeterminate" (In Progress) or "done"
+    pub key: String,
+    pub name: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -241,6 +495,16 @@ pub struct User {
     pub email_address: Option<String>,
     #[serde(rename = "accountId")]
     pub account_id: Option<String>, // Account ID is optional as some users (like apps) might not have it in the same context, or for backward compatibility
+    /// Username (Jira Data Center / Server only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl User {
+    /// The identifier used to reference this user: account ID on Cloud, username on Data Center.
+    pub fn id(&self) -> Option<&str> {
+        self.account_id.as_deref().or(self.name.as_deref())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -251,6 +515,59 @@ pub struct Priority {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AddCommentRequest {
     pub body: serde_json::Value,
+    /// Source text, kept to re-render the body as wiki markup for Data Center
+    #[serde(skip)]
+    text: String,
+    #[serde(skip)]
+    plain: bool,
+}
+
+impl AddCommentRequest {
+    /// Create a comment from Markdown (converted to Atlassian Document Format)
+    pub fn new(comment: &str) -> Self {
+        Self {
+            body: markdown_to_adf(comment),
+            text: comment.to_string(),
+            plain: false,
+        }
+    }
+
+    /// Create a comment from verbatim text, without Markdown conversion.
+    /// Line breaks are preserved as hard breaks.
+    pub fn plain(comment: &str) -> Self {
+        let mut content = Vec::new();
+        for (i, line) in comment.split('\n').enumerate() {
+            if i > 0 {
+                content.push(serde_json::json!({ "type": "hardBreak" }));
+            }
+            if !line.is_empty() {
+                content.push(serde_json::json!({ "type": "text", "text": line }));
+            }
+        }
+
+        Self {
+            body: serde_json::json!({
+                "type": "doc",
+                "version": 1,
+                "content": [{ "type": "paragraph", "content": content }]
+            }),
+            text: comment.to_string(),
+            plain: true,
+        }
+    }
+
+    /// Render the body for the target deployment (wiki markup on Data Center).
+    pub fn for_deployment(mut self, deployment: Deployment) -> Self {
+        if deployment == Deployment::DataCenter {
+            let wiki = if self.plain {
+                escape_wiki_text(&self.text)
+            } else {
+                markdown_to_wiki(&self.text)
+            };
+            self.body = serde_json::json!(wiki);
+        }
+        self
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -260,6 +577,8 @@ pub struct Comment {
     pub self_url: String,
     pub author: Option<User>,
     pub created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
     pub body: Option<serde_json::Value>,
 }
 
@@ -267,6 +586,9 @@ pub struct Comment {
 #[derive(Debug, Serialize, Default)]
 pub struct CreateIssueRequest {
     pub fields: HashMap<String, serde_json::Value>,
+    /// Markdown source of rich-text fields, kept to re-render them for Data Center
+    #[serde(skip)]
+    markdown_fields: HashMap<String, String>,
 }
 
 impl CreateIssueRequest {
@@ -281,13 +603,26 @@ impl CreateIssueRequest {
             "issuetype".to_string(),
             serde_json::json!({"name": issue_type}),
         );
-        Self { fields }
+        Self {
+            fields,
+            markdown_fields: HashMap::new(),
+        }
     }
 
     /// Set the description (Markdown converted to Atlassian Document Format)
     pub fn description(mut self, description: &str) -> Self {
         self.fields
             .insert("description".to_string(), markdown_to_adf(description));
+        self.markdown_fields
+            .insert("description".to_string(), description.to_string());
+        self
+    }
+
+    /// Render fields for the target deployment (see [`adapt_fields_for_data_center`]).
+    pub fn for_deployment(mut self, deployment: Deployment) -> Self {
+        if deployment == Deployment::DataCenter {
+            adapt_fields_for_data_center(&mut self.fields, &self.markdown_fields);
+        }
         self
     }
 
@@ -309,6 +644,27 @@ impl CreateIssueRequest {
         self
     }
 
+    /// Set the components by name
+    pub fn components(mut self, names: &[&str]) -> Self {
+        self.fields
+            .insert("components".to_string(), named_values(names).into());
+        self
+    }
+
+    /// Set the fix versions by name
+    pub fn fix_versions(mut self, names: &[&str]) -> Self {
+        self.fields
+            .insert("fixVersions".to_string(), named_values(names).into());
+        self
+    }
+
+    /// Set the affects versions by name
+    pub fn affects_versions(mut self, names: &[&str]) -> Self {
+        self.fields
+            .insert("versions".to_string(), named_values(names).into());
+        self
+    }
+
     /// Set the parent issue (for subtasks or epic)
     pub fn parent(mut self, parent_key: &str) -> Self {
         self.fields.insert(
@@ -331,6 +687,17 @@ impl CreateIssueRequest {
             .insert("duedate".to_string(), serde_json::json!(date));
         self
     }
+
+    /// Set any field from the field catalogue, coercing `value` to the
+    /// field's type (see [`Field::coerce`]). Rich-text values are Markdown.
+    pub fn custom_field(
+        mut self,
+        field: &Field,
+        value: &serde_json::Value,
+    ) -> anyhow::Result<Self> {
+        set_field(&mut self.fields, &mut self.markdown_fields, field, value)?;
+        Ok(self)
+    }
 }
 
 /// Response from POST /rest/api/3/issue
@@ -348,6 +715,12 @@ pub struct CreatedIssue {
 pub struct UpdateIssueRequest {
     /// Fields to update (e.g., "summary", "duedate", "priority", "assignee", "parent")
     pub fields: HashMap<String, serde_json::Value>,
+    /// Verb operations on multi-value fields, e.g. `{"components": [{"add": {"name": "API"}}]}`
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub update: HashMap<String, Vec<serde_json::Value>>,
+    /// Markdown source of rich-text fields, kept to re-render them for Data Center
+    #[serde(skip)]
+    markdown_fields: HashMap<String, String>,
 }
 
 impl UpdateIssueRequest {
@@ -407,109 +780,2243 @@ impl UpdateIssueRequest {
     pub fn description(mut self, description: &str) -> Self {
         self.fields
             .insert("description".to_string(), markdown_to_adf(description));
+        self.markdown_fields
+            .insert("description".to_string(), description.to_string());
         self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Add labels, keeping the existing ones
+    pub fn add_labels(self, labels: &[&str]) -> Self {
+        self.operation("labels", FieldOperation::Add, string_values(labels))
+    }
 
-    fn get_content(adf: &serde_json::Value) -> &Vec<serde_json::Value> {
-        adf["content"].as_array().unwrap()
+    /// Remove labels, keeping the others
+    pub fn remove_labels(self, labels: &[&str]) -> Self {
+        self.operation("labels", FieldOperation::Remove, string_values(labels))
     }
 
-    #[test]
-    fn converts_plain_text_to_paragraph() {
-        let adf = markdown_to_adf("Hello world");
-        let content = get_content(&adf);
-        assert_eq!(content.len(), 1);
-        assert_eq!(content[0]["type"], "paragraph");
-        assert_eq!(content[0]["content"][0]["text"], "Hello world");
+    /// Set, add or remove components by name
+    pub fn components(self, operation: FieldOperation, names: &[&str]) -> Self {
+        self.named_operation("components", operation, names)
     }
 
-    #[test]
-    fn converts_heading_levels() {
-        let adf = markdown_to_adf("## Overview\n\nsome text");
-        let content = get_content(&adf);
-        let heading = content.iter().find(|n| n["type"] == "heading").unwrap();
-        assert_eq!(heading["attrs"]["level"], 2);
-        assert_eq!(heading["content"][0]["text"], "Overview");
+    /// Set, add or remove fix versions by name
+    pub fn fix_versions(self, operation: FieldOperation, names: &[&str]) -> Self {
+        self.named_operation("fixVersions", operation, names)
     }
 
-    #[test]
-    fn converts_bold_text() {
-        let adf = markdown_to_adf("**bold** text");
-        let content = get_content(&adf);
-        let para = &content[0];
-        let bold_node = &para["content"][0];
-        assert_eq!(bold_node["text"], "bold");
-        let marks = bold_node["marks"].as_array().unwrap();
-        assert!(marks.iter().any(|m| m["type"] == "strong"));
+    /// Set, add or remove affects versions by name
+    pub fn affects_versions(self, operation: FieldOperation, names: &[&str]) -> Self {
+        self.named_operation("versions", operation, names)
     }
 
-    #[test]
-    fn converts_italic_text() {
-        let adf = markdown_to_adf("*italic* text");
-        let content = get_content(&adf);
-        let italic_node = &content[0]["content"][0];
-        assert_eq!(italic_node["text"], "italic");
-        let marks = italic_node["marks"].as_array().unwrap();
-        assert!(marks.iter().any(|m| m["type"] == "em"));
+    /// Set any field from the field catalogue, coercing `value` to the
+    /// field's type (see [`Field::coerce`]). Rich-text values are Markdown.
+    pub fn custom_field(
+        mut self,
+        field: &Field,
+        value: &serde_json::Value,
+    ) -> anyhow::Result<Self> {
+        set_field(&mut self.fields, &mut self.markdown_fields, field, value)?;
+        Ok(self)
     }
 
-    #[test]
-    fn converts_bullet_list() {
-        let adf = markdown_to_adf("- item one\n- item two\n- item three");
-        let content = get_content(&adf);
-        let list = content.iter().find(|n| n["type"] == "bulletList").unwrap();
-        let items = list["content"].as_array().unwrap();
-        assert_eq!(items.len(), 3);
-        assert_eq!(items[0]["type"], "listItem");
+    /// Add values to or remove values from a multi-value field from the field
+    /// catalogue (multi-selects, user pickers, ...), coercing `value` like
+    /// [`Field::coerce`].
+    pub fn custom_field_operation(
+        self,
+        field: &Field,
+        operation: FieldOperation,
+        value: &serde_json::Value,
+    ) -> anyhow::Result<Self> {
+        if field
+            .schema
+            .as_ref()
+            .is_none_or(|s| s.field_type != "array")
+        {
+            anyhow::bail!(
+                "Field '{}' holds a single value; set it with custom_fields instead",
+                field.name
+            );
+        }
+        let values = match field.coerce(value)? {
+            serde_json::Value::Array(values) => values,
+            value => vec![value],
+        };
+        Ok(self.operation(&field.id, operation, values))
     }
 
-    #[test]
-    fn converts_inline_code() {
-        let adf = markdown_to_adf("use `cargo build` to compile");
-        let content = get_content(&adf);
-        let nodes = content[0]["content"].as_array().unwrap();
-        let code_node = nodes.iter().find(|n| {
-            n["marks"]
-                .as_array()
-                .map(|m| m.iter().any(|mark| mark["type"] == "code"))
-                .unwrap_or(false)
-        });
-        assert!(code_node.is_some());
-        assert_eq!(code_node.unwrap()["text"], "cargo build");
+    /// Render fields for the target deployment (see [`adapt_fields_for_data_center`]).
+    pub fn for_deployment(mut self, deployment: Deployment) -> Self {
+        if deployment == Deployment::DataCenter {
+            adapt_fields_for_data_center(&mut self.fields, &self.markdown_fields);
+            self.update
+                .values_mut()
+                .flatten()
+                .filter_map(serde_json::Value::as_object_mut)
+                .flat_map(|operation| operation.values_mut())
+                .for_each(account_id_to_name);
+        }
+        self
     }
 
-    #[test]
-    fn converts_mixed_markdown() {
-        let md = "## Title\n\nsome paragraph\n\n- item 1\n- item 2";
-        let adf = markdown_to_adf(md);
-        let content = get_content(&adf);
-        assert!(content.iter().any(|n| n["type"] == "heading"));
-        assert!(content.iter().any(|n| n["type"] == "paragraph"));
-        assert!(content.iter().any(|n| n["type"] == "bulletList"));
+    fn named_operation(self, field: &str, operation: FieldOperation, names: &[&str]) -> Self {
+        self.operation(field, operation, named_values(names))
     }
 
-    #[test]
-    fn wraps_adf_in_doc_node() {
-        let adf = markdown_to_adf("test");
-        assert_eq!(adf["type"], "doc");
-        assert_eq!(adf["version"], 1);
+    /// Queue a verb operation. `set` replaces the whole value with `values`;
+    /// `add` and `remove` apply to each value in turn.
+    ///
+    /// Jira rejects a field that is both in `fields` and `update`, so a value
+    /// already set through `fields` becomes the first `set` operation.
+    fn operation(
+        mut self,
+        field: &str,
+        operation: FieldOperation,
+        values: Vec<serde_json::Value>,
+    ) -> Self {
+        let verb = operation.verb();
+        let set_value = self.fields.remove(field);
+        let operations = self.update.entry(field.to_string()).or_default();
+        if let Some(value) = set_value {
+            operations.push(serde_json::json!({ "set": value }));
+        }
+        match operation {
+            FieldOperation::Set => operations.push(serde_json::json!({ verb: values })),
+            FieldOperation::Add | FieldOperation::Remove => operations.extend(
+                values
+                    .into_iter()
+                    .map(|value| serde_json::json!({ verb: value })),
+            ),
+        }
+        self
     }
 }
 
-#[cfg(test)]
-mod serialization_tests {
-    use super::*;
+/// How an update changes a multi-value field such as components or fix versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldOperation {
+    /// Replace all values
+    Set,
+    /// Add values, keeping the existing ones
+    Add,
+    /// Remove values, keeping the others
+    Remove,
+}
 
-    #[test]
-    fn print_update_request_json() {
-        let update = UpdateIssueRequest::new()
-            .description("## 概要\nテスト内容\n\n- item 1\n- item 2");
-        let json = serde_json::to_string_pretty(&update).unwrap();
-        println!("{}", json);
+impl FieldOperation {
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Set => "set",
+            Self::Add => "add",
+            Self::Remove => "remove",
+        }
+    }
+}
+
+/// Plain string values, the shape of labels.
+fn string_values(values: &[&str]) -> Vec<serde_json::Value> {
+    values
+        .iter()
+        .map(|value| serde_json::json!(value))
+        .collect()
+}
+
+/// `[{"name": ..}, ...]`, the shape of components and versions.
+fn named_values(names: &[&str]) -> Vec<serde_json::Value> {
+    names
+        .iter()
+        .map(|name| serde_json::json!({ "name": name }))
+        .collect()
+}
+
+fn set_field(
+    fields: &mut HashMap<String, serde_json::Value>,
+    markdown_fields: &mut HashMap<String, String>,
+    field: &Field,
+    value: &serde_json::Value,
+) -> anyhow::Result<()> {
+    if field.is_rich_text()
+        && let Some(markdown) = value.as_str()
+    {
+        fields.insert(field.id.clone(), markdown_to_adf(markdown));
+        markdown_fields.insert(field.id.clone(), markdown.to_string());
+    } else {
+        fields.insert(field.id.clone(), field.coerce(value)?);
+        markdown_fields.remove(&field.id);
+    }
+    Ok(())
+}
+
+/// Rewrite Cloud-style field values for Jira Data Center: Markdown fields become
+/// wiki markup and `{"accountId": ..}` user references become `{"name": ..}`.
+fn adapt_fields_for_data_center(
+    fields: &mut HashMap<String, serde_json::Value>,
+    markdown_fields: &HashMap<String, String>,
+) {
+    for (field, markdown) in markdown_fields {
+        fields.insert(field.clone(), serde_json::json!(markdown_to_wiki(markdown)));
+    }
+    for value in fields.values_mut() {
+        account_id_to_name(value);
+    }
+}
+
+fn account_id_to_name(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) if map.len() == 1 => {
+            if let Some(id) = map.remove("accountId") {
+                map.insert("name".to_string(), id);
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(account_id_to_name),
+        _ => {}
+    }
+}
+
+/// Escape text so Jira wiki markup shows it verbatim.
+fn escape_wiki_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '*' | '_' | '-' | '+' | '^' | '~' | '{' | '}' | '[' | ']' | '|' | '!' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Response from GET /rest/api/3/issue/{issueIdOrKey}/transitions
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TransitionsResponse {
+    pub transitions: Vec<Transition>,
+}
+
+impl TransitionsResponse {
+    /// Find a transition by its target status name or transition name (case-insensitive).
+    ///
+    /// The target status is preferred, since that is what users usually refer to
+    /// ("move it to Done") while transition names are workflow-specific.
+    pub fn find_by_status(&self, name: &str) -> Option<&Transition> {
+        self.transitions
+            .iter()
+            .find(|t| {
+                t.to.as_ref()
+                    .is_some_and(|s| s.name.eq_ignore_ascii_case(name))
+            })
+            .or_else(|| {
+                self.transitions
+                    .iter()
+                    .find(|t| t.name.eq_ignore_ascii_case(name))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    /// The status the issue moves to after this transition
+    pub to: Option<Status>,
+    #[serde(default)]
+    pub has_screen: bool,
+    /// Fields on the transition screen (only present with `expand=transitions.fields`)
+    #[serde(default)]
+    pub fields: HashMap<String, TransitionField>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TransitionField {
+    pub required: bool,
+    pub name: String,
+}
+
+/// Request body for POST /rest/api/3/issue/{issueIdOrKey}/transitions
+#[derive(Debug, Serialize)]
+pub struct TransitionIssueRequest {
+    pub transition: serde_json::Value,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub fields: HashMap<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub update: HashMap<String, serde_json::Value>,
+    /// Markdown source of the comment, kept to re-render it for Data Center
+    #[serde(skip)]
+    comment_markdown: Option<String>,
+}
+
+impl TransitionIssueRequest {
+    pub fn new(transition_id: &str) -> Self {
+        Self {
+            transition: serde_json::json!({"id": transition_id}),
+            fields: HashMap::new(),
+            update: HashMap::new(),
+            comment_markdown: None,
+        }
+    }
+
+    /// Set the resolution by name (e.g., "Done", "Won't Do")
+    pub fn resolution(mut self, resolution_name: &str) -> Self {
+        self.fields.insert(
+            "resolution".to_string(),
+            serde_json::json!({"name": resolution_name}),
+        );
+        self
+    }
+
+    /// Set an arbitrary field required by the transition screen
+    pub fn field(mut self, field_id: &str, value: serde_json::Value) -> Self {
+        self.fields.insert(field_id.to_string(), value);
+        self
+    }
+
+    /// Add a comment as part of the transition (Markdown converted to Atlassian Document Format)
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.update.insert(
+            "comment".to_string(),
+            serde_json::json!([{ "add": { "body": markdown_to_adf(comment) } }]),
+        );
+        self.comment_markdown = Some(comment.to_string());
+        self
+    }
+
+    /// Render the comment and user references for the target deployment.
+    pub fn for_deployment(mut self, deployment: Deployment) -> Self {
+        if deployment == Deployment::DataCenter {
+            if let Some(markdown) = &self.comment_markdown {
+                self.update.insert(
+                    "comment".to_string(),
+                    serde_json::json!([{ "add": { "body": markdown_to_wiki(markdown) } }]),
+                );
+            }
+            adapt_fields_for_data_center(&mut self.fields, &HashMap::new());
+        }
+        self
+    }
+}
+
+/// Response from GET /rest/api/3/issue/{issueIdOrKey}/worklog
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorklogResponse {
+    pub start_at: u32,
+    pub max_results: u32,
+    pub total: u32,
+    pub worklogs: Vec<Worklog>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Worklog {
+    pub id: String,
+    pub author: Option<User>,
+    /// ADF on Cloud, wiki markup on Data Center
+    pub comment: Option<serde_json::Value>,
+    pub started: Option<String>,
+    #[serde(default)]
+    pub time_spent_seconds: u64,
+}
+
+/// Request body for adding or updating a worklog.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorklogRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent_seconds: Option<u64>,
+    /// Start time in Jira's format, e.g. `2024-01-05T09:30:00.000+0000`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<serde_json::Value>,
+    /// Markdown source of the comment, kept to re-render it for Data Center
+    #[serde(skip)]
+    comment_markdown: Option<String>,
+}
+
+impl WorklogRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn time_spent_seconds(mut self, seconds: u64) -> Self {
+        self.time_spent_seconds = Some(seconds);
+        self
+    }
+
+    /// Set when the work started (`2024-01-05T09:30:00.000+0000`)
+    pub fn started(mut self, started: &str) -> Self {
+        self.started = Some(started.to_string());
+        self
+    }
+
+    /// Set the comment (Markdown converted to Atlassian Document Format)
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.comment = Some(markdown_to_adf(comment));
+        self.comment_markdown = Some(comment.to_string());
+        self
+    }
+
+    /// Render the comment for the target deployment (wiki markup on Data Center).
+    pub fn for_deployment(mut self, deployment: Deployment) -> Self {
+        if deployment == Deployment::DataCenter
+            && let Some(markdown) = &self.comment_markdown
+        {
+            self.comment = Some(serde_json::json!(markdown_to_wiki(markdown)));
+        }
+        self
+    }
+}
+
+/// How the issue's remaining estimate changes when a worklog is added, updated or deleted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EstimateAdjustment {
+    /// Reduce (or, on delete, increase) the estimate by the time logged
+    #[default]
+    Auto,
+    /// Leave the estimate unchanged
+    Leave,
+    /// Set the remaining estimate to this value (e.g. "2d")
+    New(String),
+    /// Reduce (on add) or increase (on delete) the estimate by this amount;
+    /// not supported when updating
+    Manual(String),
+}
+
+impl EstimateAdjustment {
+    /// Parse an adjustment mode ("auto", "leave", "new" or "manual") and the
+    /// estimate or amount that "new" and "manual" require.
+    pub fn from_mode(mode: Option<&str>, estimate: Option<&str>) -> anyhow::Result<Self> {
+        let require_estimate = |mode: &str| {
+            estimate
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("adjust_estimate '{}' requires 'estimate'", mode))
+        };
+
+        match mode.map(|m| m.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("auto") => Ok(Self::Auto),
+            Some("leave") => Ok(Self::Leave),
+            Some("new") => Ok(Self::New(require_estimate("new")?)),
+            Some("manual") => Ok(Self::Manual(require_estimate("manual")?)),
+            Some(other) => anyhow::bail!(
+                "Unknown adjust_estimate '{}': use auto, leave, new or manual",
+                other
+            ),
+        }
+    }
+
+    /// Query parameters for the worklog endpoints. `deleting` selects
+    /// `increaseBy` instead of `reduceBy` for manual adjustments.
+    pub fn query(&self, deleting: bool) -> Vec<(&'static str, String)> {
+        match self {
+            Self::Auto => vec![("adjustEstimate", "auto".to_string())],
+            Self::Leave => vec![("adjustEstimate", "leave".to_string())],
+            Self::New(estimate) => vec![
+                ("adjustEstimate", "new".to_string()),
+                ("newEstimate", estimate.clone()),
+            ],
+            Self::Manual(amount) => vec![
+                ("adjustEstimate", "manual".to_string()),
+                (
+                    if deleting { "increaseBy" } else { "reduceBy" },
+                    amount.clone(),
+                ),
+            ],
+        }
+    }
+}
+
+/// Response from GET /rest/api/3/issueLinkType
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueLinkTypesResponse {
+    pub issue_link_types: Vec<IssueLinkType>,
+}
+
+impl IssueLinkTypesResponse {
+    /// Find a link type by its name or by one of its relationship descriptions
+    /// (case-insensitive). A description match also tells which way the link
+    /// reads: "blocks" is outward, "is blocked by" inward.
+    pub fn find(&self, name: &str) -> Option<(&IssueLinkType, Option<LinkDirection>)> {
+        let name = name.trim();
+        self.issue_link_types
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+            .map(|t| (t, None))
+            .or_else(|| {
+                self.issue_link_types.iter().find_map(|t| {
+                    if t.outward.eq_ignore_ascii_case(name) {
+                        Some((t, Some(LinkDirection::Outward)))
+                    } else if t.inward.eq_ignore_ascii_case(name) {
+                        Some((t, Some(LinkDirection::Inward)))
+                    } else {
+                        None
+                    }
+                })
+            })
+    }
+}
+
+/// A kind of relationship between issues, e.g. "Blocks" (outward "blocks",
+/// inward "is blocked by").
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssueLinkType {
+    pub id: Option<String>,
+    pub name: String,
+    /// Description from the inward issue's side, e.g. "is blocked by"
+    pub inward: String,
+    /// Description from the outward issue's side, e.g. "blocks"
+    pub outward: String,
+}
+
+/// A link on an issue, from the `issuelinks` field. Only the other end of the
+/// link is present: `outward_issue` when this issue "blocks" it,
+/// `inward_issue` when this issue "is blocked by" it.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueLink {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub link_type: IssueLinkType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inward_issue: Option<Issue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outward_issue: Option<Issue>,
+}
+
+impl IssueLink {
+    /// How this issue relates to the linked one and the linked issue itself,
+    /// e.g. `("blocks", PROJ-2)`.
+    pub fn relationship(&self) -> Option<(&str, &Issue)> {
+        match (&self.outward_issue, &self.inward_issue) {
+            (Some(issue), _) => Some((self.link_type.outward.as_str(), issue)),
+            (None, Some(issue)) => Some((self.link_type.inward.as_str(), issue)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Which way a link reads from the first issue named in a request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LinkDirection {
+    /// The issue relates to the other issue with the outward description
+    /// ("PROJ-1 blocks PROJ-2")
+    #[default]
+    Outward,
+    /// The issue relates to the other issue with the inward description
+    /// ("PROJ-1 is blocked by PROJ-2")
+    Inward,
+}
+
+impl LinkDirection {
+    /// Parse "outward" or "inward".
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "outward" => Ok(Self::Outward),
+            "inward" => Ok(Self::Inward),
+            other => anyhow::bail!("Unknown direction '{}': use outward or inward", other),
+        }
+    }
+}
+
+/// Request body for POST /rest/api/3/issueLink
+///
+/// Jira reads the link as "`inwardIssue` <outward description> `outwardIssue`",
+/// e.g. inward PROJ-1, outward PROJ-2 with type "Blocks" is "PROJ-1 blocks PROJ-2".
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkIssuesRequest {
+    #[serde(rename = "type")]
+    pub link_type: serde_json::Value,
+    pub inward_issue: serde_json::Value,
+    pub outward_issue: serde_json::Value,
+}
+
+impl LinkIssuesRequest {
+    /// Link `issue_key` to `other_issue_key` so that, reading in `direction`,
+    /// "`issue_key` <relationship> `other_issue_key`".
+    pub fn new(
+        link_type_name: &str,
+        issue_key: &str,
+        other_issue_key: &str,
+        direction: LinkDirection,
+    ) -> Self {
+        let (inward, outward) = match direction {
+            LinkDirection::Outward => (issue_key, other_issue_key),
+            LinkDirection::Inward => (other_issue_key, issue_key),
+        };
+        Self {
+            link_type: serde_json::json!({ "name": link_type_name }),
+            inward_issue: serde_json::json!({ "key": inward }),
+            outward_issue: serde_json::json!({ "key": outward }),
+        }
+    }
+}
+
+/// Response from GET /rest/api/3/issue/{issueIdOrKey}/changelog, or the
+/// `changelog` of an issue fetched with `expand=changelog` (Data Center).
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogPage {
+    #[serde(default)]
+    pub start_at: u32,
+    #[serde(default)]
+    pub max_results: u32,
+    #[serde(default)]
+    pub total: u32,
+    pub is_last: Option<bool>,
+    #[serde(alias = "histories")]
+    pub values: Vec<ChangelogEntry>,
+}
+
+/// An issue fetched with `expand=changelog`.
+#[derive(Debug, Deserialize)]
+pub struct IssueWithChangelog {
+    pub changelog: ChangelogPage,
+}
+
+/// One edit of an issue: who changed what, and when.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangelogEntry {
+    pub id: String,
+    pub author: Option<User>,
+    pub created: String,
+    pub items: Vec<ChangeItem>,
+}
+
+/// A single field change within a changelog entry.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeItem {
+    /// Field name as shown in Jira, e.g. "status", "assignee", "Sprint"
+    pub field: String,
+    pub field_id: Option<String>,
+    /// Raw previous value (IDs for statuses, users, sprints)
+    pub from: Option<String>,
+    /// Display form of the previous value
+    #[serde(rename = "fromString")]
+    pub from_text: Option<String>,
+    pub to: Option<String>,
+    #[serde(rename = "toString")]
+    pub to_text: Option<String>,
+}
+
+/// Narrows a changelog to some fields and a period.
+#[derive(Debug, Default)]
+pub struct ChangelogFilter {
+    /// Field names or IDs to keep (case-insensitive); empty keeps all
+    fields: Vec<String>,
+    /// Unix seconds, inclusive
+    since: Option<u64>,
+    /// Unix seconds, exclusive
+    until: Option<u64>,
+}
+
+impl ChangelogFilter {
+    /// Build a filter from field names and ISO 8601 bounds. A date-only
+    /// `until` (`2025-01-31`) includes that whole day.
+    pub fn new(
+        fields: &[String],
+        since: Option<&str>,
+        until: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let parse = |value: &str| {
+            parse_timestamp(value).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid date '{}': expected e.g. '2025-01-31' or '2025-01-31T09:00:00Z'",
+                    value
+                )
+            })
+        };
+        let until = match until {
+            Some(value) if value.contains('-') && !value.contains('T') => {
+                Some(parse(value)? + 86_400)
+            }
+            Some(value) => Some(parse(value)?),
+            None => None,
+        };
+
+        Ok(Self {
+            fields: fields.iter().map(|f| f.trim().to_lowercase()).collect(),
+            since: since.map(parse).transpose()?,
+            until,
+        })
+    }
+
+    /// Keep the matching items of entries within the period, dropping entries
+    /// left without items.
+    pub fn apply(&self, entries: Vec<ChangelogEntry>) -> Vec<ChangelogEntry> {
+        entries
+            .into_iter()
+            .filter(|entry| {
+                let Some(created) = parse_timestamp(&entry.created) else {
+                    return true;
+                };
+                self.since.is_none_or(|since| created >= since)
+                    && self.until.is_none_or(|until| created < until)
+            })
+            .filter_map(|mut entry| {
+                entry.items.retain(|item| self.matches_field(item));
+                (!entry.items.is_empty()).then_some(entry)
+            })
+            .collect()
+    }
+
+    fn matches_field(&self, item: &ChangeItem) -> bool {
+        self.fields.is_empty()
+            || self.fields.iter().any(|field| {
+                item.field.to_lowercase() == *field
+                    || item
+                        .field_id
+                        .as_ref()
+                        .is_some_and(|id| id.to_lowercase() == *field)
+            })
+    }
+}
+
+/// A page of results from the Agile API (`/rest/agile/1.0`).
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgilePage<T> {
+    #[serde(default)]
+    pub start_at: u32,
+    #[serde(default)]
+    pub max_results: u32,
+    pub total: Option<u32>,
+    pub is_last: Option<bool>,
+    pub values: Vec<T>,
+}
+
+/// A Scrum or Kanban board.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Board {
+    pub id: u64,
+    pub name: String,
+    /// "scrum", "kanban" or "simple"
+    #[serde(rename = "type")]
+    pub board_type: String,
+    pub location: Option<BoardLocation>,
+}
+
+/// The project a board belongs to.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardLocation {
+    pub project_key: Option<String>,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sprint {
+    pub id: u64,
+    pub name: String,
+    /// "future", "active" or "closed"
+    pub state: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub complete_date: Option<String>,
+    pub goal: Option<String>,
+    pub origin_board_id: Option<u64>,
+}
+
+/// Request body for creating a sprint (POST /rest/agile/1.0/sprint) or
+/// changing one (POST /rest/agile/1.0/sprint/{sprintId}, a partial update).
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SprintRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin_board_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+}
+
+impl SprintRequest {
+    /// A new (future) sprint on a board.
+    pub fn create(board_id: u64, name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            origin_board_id: Some(board_id),
+            ..Self::default()
+        }
+    }
+
+    /// Start a future sprint. Jira requires start and end dates, either
+    /// already set on the sprint or given here.
+    pub fn start() -> Self {
+        Self {
+            state: Some("active".to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Close an active sprint.
+    pub fn close() -> Self {
+        Self {
+            state: Some("closed".to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the start date (ISO 8601, e.g. `2025-01-06T09:00:00.000Z`)
+    pub fn start_date(mut self, date: &str) -> Self {
+        self.start_date = Some(date.to_string());
+        self
+    }
+
+    /// Set the end date (ISO 8601)
+    pub fn end_date(mut self, date: &str) -> Self {
+        self.end_date = Some(date.to_string());
+        self
+    }
+
+    pub fn goal(mut self, goal: &str) -> Self {
+        self.goal = Some(goal.to_string());
+        self
+    }
+}
+
+/// Request body for moving issues to a sprint or to the backlog.
+#[derive(Debug, Serialize)]
+pub struct MoveIssuesRequest {
+    pub issues: Vec<String>,
+}
+
+/// A system or custom field, from GET /rest/api/3/field
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Field {
+    /// e.g. "duedate" or "customfield_10016"
+    pub id: String,
+    /// Display name, e.g. "Story Points"
+    pub name: String,
+    #[serde(default)]
+    pub custom: bool,
+    pub schema: Option<FieldSchema>,
+}
+
+/// The kind of value a field holds.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FieldSchema {
+    /// "string", "number", "date", "datetime", "option", "user", "array", ...
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// Element type of array fields, e.g. "option" for multi-selects
+    pub items: Option<String>,
+    /// System field ID, for system fields
+    pub system: Option<String>,
+    /// Custom field type key, e.g. "com.atlassian.jira.plugin.system.customfieldtypes:select"
+    pub custom: Option<String>,
+}
+
+impl FieldSchema {
+    /// Short description of the value type, e.g. "number" or "array<option>".
+    pub fn type_name(&self) -> String {
+        match &self.items {
+            Some(items) => format!("{}<{}>", self.field_type, items),
+            None => self.field_type.clone(),
+        }
+    }
+}
+
+impl Field {
+    /// Short description of the value type, e.g. "number" or "array<option>".
+    pub fn type_name(&self) -> String {
+        self.schema
+            .as_ref()
+            .map_or_else(|| "unknown".to_string(), FieldSchema::type_name)
+    }
+
+    /// Whether the field holds rich text (ADF on Cloud, wiki markup on Data Center).
+    pub fn is_rich_text(&self) -> bool {
+        self.schema.as_ref().is_some_and(|schema| {
+            schema
+                .custom
+                .as_deref()
+                .is_some_and(|c| c.ends_with(":textarea"))
+                || matches!(
+                    schema.system.as_deref(),
+                    Some("description" | "environment")
+                )
+        })
+    }
+
+    /// Whether the field holds users, alone or in an array.
+    pub fn holds_users(&self) -> bool {
+        self.schema.as_ref().is_some_and(|schema| {
+            schema.field_type == "user" || schema.items.as_deref() == Some("user")
+        })
+    }
+
+    /// Convert a plain value to the shape Jira expects for this field, e.g.
+    /// `"High"` to `{"value": "High"}` for a select list, `"3"` to `3` for a
+    /// number or `"a, b"` to `[{"value": "a"}, {"value": "b"}]` for a
+    /// multi-select. Objects and `null` are passed through unchanged.
+    pub fn coerce(&self, value: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let Some(schema) = &self.schema else {
+            return Ok(value.clone());
+        };
+        if value.is_null() || value.is_object() {
+            return Ok(value.clone());
+        }
+
+        let custom = schema.custom.as_deref().unwrap_or_default();
+        if custom.ends_with(":gh-sprint") {
+            // Sprint is an array field but is set with a single sprint ID
+            return self.coerce_scalar("number", value);
+        }
+        if custom.ends_with(":cascadingselect") {
+            return self.coerce_cascading(value);
+        }
+
+        if schema.field_type != "array" {
+            return self.coerce_scalar(&schema.field_type, value);
+        }
+        let items_type = schema.items.as_deref().unwrap_or("any");
+        let items: Vec<serde_json::Value> = match value {
+            serde_json::Value::Array(items) => items.clone(),
+            serde_json::Value::String(text) => text
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| serde_json::json!(item))
+                .collect(),
+            other => vec![other.clone()],
+        };
+        items
+            .iter()
+            .map(|item| self.coerce_scalar(items_type, item))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(serde_json::Value::Array)
+    }
+
+    fn coerce_scalar(
+        &self,
+        value_type: &str,
+        value: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        use serde_json::{Value, json};
+
+        if value.is_object() {
+            return Ok(value.clone());
+        }
+        let text = match value {
+            Value::String(text) => text.trim(),
+            Value::Bool(_) if value_type == "number" => {
+                anyhow::bail!("Field '{}' expects a number, got '{}'", self.name, value)
+            }
+            Value::Number(_) if value_type != "string" => return Ok(value.clone()),
+            Value::Number(_) | Value::Bool(_) => return Ok(json!(value.to_string())),
+            _ => return Ok(value.clone()),
+        };
+
+        match value_type {
+            "number" => {
+                if let Ok(integer) = text.parse::<i64>() {
+                    Ok(json!(integer))
+                } else if let Ok(number) = text.parse::<f64>() {
+                    Ok(json!(number))
+                } else {
+                    anyhow::bail!("Field '{}' expects a number, got '{}'", self.name, text)
+                }
+            }
+            "option" => Ok(json!({ "value": text })),
+            "user" => Ok(json!({ "accountId": text })),
+            "group" | "version" | "component" | "priority" | "resolution" | "issuetype"
+            | "securitylevel" => Ok(json!({ "name": text })),
+            "project" => Ok(json!({ "key": text })),
+            "date" => {
+                let is_date_shaped = text.len() == 10
+                    && text.bytes().enumerate().all(|(i, b)| match i {
+                        4 | 7 => b == b'-',
+                        _ => b.is_ascii_digit(),
+                    });
+                if !is_date_shaped || parse_timestamp(text).is_none() {
+                    anyhow::bail!(
+                        "Field '{}' expects a date like '2025-01-31', got '{}'",
+                        self.name,
+                        text
+                    );
+                }
+                Ok(json!(text))
+            }
+            "datetime" => to_jira_timestamp(text)
+                .map(|timestamp| json!(timestamp))
+                .map_err(|e| anyhow::anyhow!("Field '{}': {}", self.name, e)),
+            _ => Ok(json!(text)),
+        }
+    }
+
+    /// Cascading selects take "Parent" or "Parent > Child".
+    fn coerce_cascading(&self, value: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let Some(text) = value.as_str() else {
+            anyhow::bail!(
+                "Field '{}' expects 'Parent' or 'Parent > Child', got {}",
+                self.name,
+                value
+            );
+        };
+        Ok(match text.split_once('>') {
+            Some((parent, child)) => serde_json::json!({
+                "value": parent.trim(),
+                "child": { "value": child.trim() }
+            }),
+            None => serde_json::json!({ "value": text.trim() }),
+        })
+    }
+}
+
+/// All fields of a Jira site, for looking fields up by display name.
+#[derive(Debug, Default)]
+pub struct FieldCatalogue {
+    fields: Vec<Field>,
+}
+
+impl FieldCatalogue {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self { fields }
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Find a field by ID (`customfield_10016`) or display name
+    /// (case-insensitive). Names shared by several fields must be given by ID.
+    pub fn resolve(&self, name_or_id: &str) -> anyhow::Result<&Field> {
+        let key = name_or_id.trim();
+        if let Some(field) = self.fields.iter().find(|f| f.id == key) {
+            return Ok(field);
+        }
+
+        let matches: Vec<&Field> = self
+            .fields
+            .iter()
+            .filter(|f| f.name.eq_ignore_ascii_case(key))
+            .collect();
+        match matches.as_slice() {
+            [field] => Ok(field),
+            [] => anyhow::bail!(
+                "Unknown field '{}'. Use list_fields to find its name or ID.",
+                key
+            ),
+            several => anyhow::bail!(
+                "Several fields are named '{}' ({}); use the field ID instead",
+                key,
+                several
+                    .iter()
+                    .map(|f| f.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// A page of projects from GET /rest/api/3/project/search
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPage {
+    #[serde(default)]
+    pub start_at: u32,
+    #[serde(default)]
+    pub max_results: u32,
+    #[serde(default)]
+    pub total: u32,
+    #[serde(default)]
+    pub is_last: bool,
+    pub values: Vec<Project>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    /// "software", "service_desk" or "business"
+    pub project_type_key: Option<String>,
+    pub description: Option<String>,
+    pub lead: Option<User>,
+    #[serde(default)]
+    pub issue_types: Vec<IssueType>,
+}
+
+/// A project component, from GET /rest/api/3/project/{projectIdOrKey}/components
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Component {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// A project version (release), from GET /rest/api/3/project/{projectIdOrKey}/versions
+/// or GET /rest/api/3/version/{id}
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Version {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub released: bool,
+    #[serde(default)]
+    pub archived: bool,
+    pub start_date: Option<String>,
+    pub release_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<u64>,
+}
+
+/// Issue counts of a version by status category.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct VersionProgress {
+    pub done: u32,
+    pub in_progress: u32,
+    pub to_do: u32,
+}
+
+impl VersionProgress {
+    /// Count issues by the category of their status; issues without one
+    /// count as to do.
+    pub fn from_issues(issues: &[Issue]) -> Self {
+        let mut progress = Self::default();
+        for issue in issues {
+            let category = issue
+                .fields
+                .status
+                .as_ref()
+                .and_then(|s| s.status_category.as_ref())
+                .map(|c| c.key.as_str());
+            match category {
+                Some("done") => progress.done += 1,
+                Some("indeterminate") => progress.in_progress += 1,
+                _ => progress.to_do += 1,
+            }
+        }
+        progress
+    }
+
+    pub fn total(&self) -> u32 {
+        self.done + self.in_progress + self.to_do
+    }
+}
+
+/// Request body for creating a version (POST /rest/api/3/version) or
+/// changing one (PUT /rest/api/3/version/{id}, a partial update).
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub released: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+impl VersionRequest {
+    /// A new, unreleased version in a project.
+    pub fn create(project_id: u64, name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            project_id: Some(project_id),
+            ..Self::default()
+        }
+    }
+
+    /// Mark a version released on `date` (`YYYY-MM-DD`).
+    pub fn release(date: &str) -> Self {
+        Self {
+            released: Some(true),
+            release_date: Some(date.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Archive a version, hiding it from version pickers.
+    pub fn archive() -> Self {
+        Self {
+            archived: Some(true),
+            ..Self::default()
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Set the start date (`YYYY-MM-DD`)
+    pub fn start_date(mut self, date: &str) -> Self {
+        self.start_date = Some(date.to_string());
+        self
+    }
+
+    /// Set the planned or actual release date (`YYYY-MM-DD`)
+    pub fn release_date(mut self, date: &str) -> Self {
+        self.release_date = Some(date.to_string());
+        self
+    }
+}
+
+/// Issue types that can be created in a project, from
+/// GET /rest/api/3/issue/createmeta/{projectIdOrKey}/issuetypes
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMetaIssueTypes {
+    #[serde(default)]
+    pub start_at: u32,
+    #[serde(default)]
+    pub total: u32,
+    /// `values` on Data Center
+    #[serde(alias = "values")]
+    pub issue_types: Vec<CreateMetaIssueType>,
+}
+
+impl CreateMetaIssueTypes {
+    /// Find an issue type by name (case-insensitive) or ID, suggesting close
+    /// matches when there is none.
+    pub fn find(&self, name: &str) -> anyhow::Result<&CreateMetaIssueType> {
+        let name = name.trim();
+        if let Some(issue_type) = self
+            .issue_types
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name) || t.id == name)
+        {
+            return Ok(issue_type);
+        }
+
+        let names: Vec<&str> = self.issue_types.iter().map(|t| t.name.as_str()).collect();
+        anyhow::bail!(
+            "Unknown issue type '{}'.{} Available: {}",
+            name,
+            did_you_mean(name, &names),
+            names.join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateMetaIssueType {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub subtask: bool,
+    pub description: Option<String>,
+}
+
+/// Fields of an issue type's create screen, from
+/// GET /rest/api/3/issue/createmeta/{projectIdOrKey}/issuetypes/{issueTypeId}
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMetaFields {
+    #[serde(default)]
+    pub start_at: u32,
+    #[serde(default)]
+    pub total: u32,
+    /// `values` on Data Center
+    #[serde(alias = "values")]
+    pub fields: Vec<CreateMetaField>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMetaField {
+    pub field_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub has_default_value: bool,
+    pub schema: Option<FieldSchema>,
+    /// Options, priorities, components, versions, ... when the field has a fixed set
+    pub allowed_values: Option<Vec<serde_json::Value>>,
+}
+
+impl CreateMetaField {
+    /// Short description of the value type, e.g. "number" or "array<option>".
+    pub fn type_name(&self) -> String {
+        self.schema
+            .as_ref()
+            .map_or_else(|| "unknown".to_string(), FieldSchema::type_name)
+    }
+
+    /// Whether the caller must supply a value.
+    pub fn needs_value(&self) -> bool {
+        self.required && !self.has_default_value
+    }
+
+    /// Display names of the allowed values.
+    pub fn allowed_names(&self) -> Vec<&str> {
+        self.allowed_values
+            .iter()
+            .flatten()
+            .filter_map(allowed_value_name)
+            .collect()
+    }
+}
+
+/// The human-readable name of an allowed value or a value being set
+/// (`{"name": ..}`, `{"value": ..}` or `{"key": ..}`).
+fn allowed_value_name(value: &serde_json::Value) -> Option<&str> {
+    ["name", "value", "key"]
+        .iter()
+        .find_map(|key| value.get(key).and_then(|v| v.as_str()))
+}
+
+impl CreateIssueRequest {
+    /// Check the request against the issue type's create metadata. Returns one
+    /// message per missing required field or value that is not allowed.
+    pub fn validate(&self, metadata: &CreateMetaFields) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for field in &metadata.fields {
+            let Some(value) = self.fields.get(&field.field_id) else {
+                if field.needs_value() {
+                    problems.push(format!(
+                        "Missing required field '{}' ({})",
+                        field.name, field.field_id
+                    ));
+                }
+                continue;
+            };
+            let Some(allowed) = &field.allowed_values else {
+                continue;
+            };
+
+            let values = match value {
+                serde_json::Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            };
+            for value in values {
+                let is_allowed = allowed.iter().any(|candidate| {
+                    ["id", "name", "value", "key"].iter().any(|key| {
+                        match (value.get(key).and_then(|v| v.as_str()), candidate.get(key)) {
+                            (Some(wanted), Some(serde_json::Value::String(actual))) => {
+                                actual.eq_ignore_ascii_case(wanted)
+                            }
+                            _ => false,
+                        }
+                    })
+                });
+                if is_allowed {
+                    continue;
+                }
+
+                let wanted = allowed_value_name(value)
+                    .or_else(|| value.get("id").and_then(|v| v.as_str()))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| value.to_string());
+                let names = field.allowed_names();
+                problems.push(format!(
+                    "Invalid value '{}' for '{}'.{} Allowed: {}",
+                    wanted,
+                    field.name,
+                    did_you_mean(&wanted, &names),
+                    names.join(", ")
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+/// " Did you mean 'X'?" for the candidates closest to `input`, or an empty string.
+fn did_you_mean(input: &str, candidates: &[&str]) -> String {
+    let matches = close_matches(input, candidates);
+    if matches.is_empty() {
+        return String::new();
+    }
+    let quoted: Vec<String> = matches.iter().map(|m| format!("'{}'", m)).collect();
+    format!(" Did you mean {}?", quoted.join(" or "))
+}
+
+/// Up to three candidates that differ from `input` by a few edits or contain
+/// it, closest first (case-insensitive).
+fn close_matches<'a>(input: &str, candidates: &[&'a str]) -> Vec<&'a str> {
+    let input = input.to_lowercase();
+    let max_distance = (input.chars().count() / 3).max(2);
+
+    let mut scored: Vec<(usize, &str)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let distance = edit_distance(&input, &lower);
+            let contains = !input.is_empty() && (lower.contains(&input) || input.contains(&lower));
+            (distance <= max_distance || contains).then_some((distance, *candidate))
+        })
+        .collect();
+    scored.sort();
+    scored.into_iter().take(3).map(|(_, c)| c).collect()
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_content(adf: &serde_json::Value) -> &Vec<serde_json::Value> {
+        adf["content"].as_array().unwrap()
+    }
+
+    #[test]
+    fn converts_plain_text_to_paragraph() {
+        let adf = markdown_to_adf("Hello world");
+        let content = get_content(&adf);
+        assert_eq!(content.len(), 1);
+        assert_eq!(content[0]["type"], "paragraph");
+        assert_eq!(content[0]["content"][0]["text"], "Hello world");
+    }
+
+    #[test]
+    fn converts_heading_levels() {
+        let adf = markdown_to_adf("## Overview\n\nsome text");
+        let content = get_content(&adf);
+        let heading = content.iter().find(|n| n["type"] == "heading").unwrap();
+        assert_eq!(heading["attrs"]["level"], 2);
+        assert_eq!(heading["content"][0]["text"], "Overview");
+    }
+
+    #[test]
+    fn converts_bold_text() {
+        let adf = markdown_to_adf("**bold** text");
+        let content = get_content(&adf);
+        let para = &content[0];
+        let bold_node = &para["content"][0];
+        assert_eq!(bold_node["text"], "bold");
+        let marks = bold_node["marks"].as_array().unwrap();
+        assert!(marks.iter().any(|m| m["type"] == "strong"));
+    }
+
+    #[test]
+    fn converts_italic_text() {
+        let adf = markdown_to_adf("*italic* text");
+        let content = get_content(&adf);
+        let italic_node = &content[0]["content"][0];
+        assert_eq!(italic_node["text"], "italic");
+        let marks = italic_node["marks"].as_array().unwrap();
+        assert!(marks.iter().any(|m| m["type"] == "em"));
+    }
+
+    #[test]
+    fn converts_bullet_list() {
+        let adf = markdown_to_adf("- item one\n- item two\n- item three");
+        let content = get_content(&adf);
+        let list = content.iter().find(|n| n["type"] == "bulletList").unwrap();
+        let items = list["content"].as_array().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["type"], "listItem");
+    }
+
+    #[test]
+    fn converts_inline_code() {
+        let adf = markdown_to_adf("use `cargo build` to compile");
+        let content = get_content(&adf);
+        let nodes = content[0]["content"].as_array().unwrap();
+        let code_node = nodes.iter().find(|n| {
+            n["marks"]
+                .as_array()
+                .map(|m| m.iter().any(|mark| mark["type"] == "code"))
+                .unwrap_or(false)
+        });
+        assert!(code_node.is_some());
+        assert_eq!(code_node.unwrap()["text"], "cargo build");
+    }
+
+    #[test]
+    fn converts_mixed_markdown() {
+        let md = "## Title\n\nsome paragraph\n\n- item 1\n- item 2";
+        let adf = markdown_to_adf(md);
+        let content = get_content(&adf);
+        assert!(content.iter().any(|n| n["type"] == "heading"));
+        assert!(content.iter().any(|n| n["type"] == "paragraph"));
+        assert!(content.iter().any(|n| n["type"] == "bulletList"));
+    }
+
+    #[test]
+    fn converts_ordered_list_with_start() {
+        let adf = markdown_to_adf("3. third\n4. fourth");
+        let list = &get_content(&adf)[0];
+        assert_eq!(list["type"], "orderedList");
+        assert_eq!(list["attrs"]["order"], 3);
+        assert_eq!(list["content"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn converts_nested_lists() {
+        let adf = markdown_to_adf("- parent\n  1. child\n- sibling");
+        let list = &get_content(&adf)[0];
+        let first_item = &list["content"][0];
+        assert_eq!(first_item["content"][0]["type"], "paragraph");
+        assert_eq!(first_item["content"][0]["content"][0]["text"], "parent");
+        assert_eq!(first_item["content"][1]["type"], "orderedList");
+        assert_eq!(
+            first_item["content"][1]["content"][0]["content"][0]["content"][0]["text"],
+            "child"
+        );
+        assert_eq!(list["content"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn converts_links_to_link_marks() {
+        let adf = markdown_to_adf("see [the docs](https://example.com)");
+        let link = &get_content(&adf)[0]["content"][1];
+        assert_eq!(link["text"], "the docs");
+        assert_eq!(link["marks"][0]["type"], "link");
+        assert_eq!(link["marks"][0]["attrs"]["href"], "https://example.com");
+    }
+
+    #[test]
+    fn keeps_marks_inside_headings_and_list_items() {
+        let adf = markdown_to_adf("## **Bold** title\n\n- *em* item");
+        let content = get_content(&adf);
+        assert_eq!(content[0]["content"][0]["marks"][0]["type"], "strong");
+        let item_text = &content[1]["content"][0]["content"][0]["content"][0];
+        assert_eq!(item_text["marks"][0]["type"], "em");
+    }
+
+    #[test]
+    fn converts_strikethrough() {
+        let adf = markdown_to_adf("~~gone~~");
+        let node = &get_content(&adf)[0]["content"][0];
+        assert_eq!(node["marks"][0]["type"], "strike");
+    }
+
+    #[test]
+    fn converts_blockquote() {
+        let adf = markdown_to_adf("> quoted text");
+        let quote = &get_content(&adf)[0];
+        assert_eq!(quote["type"], "blockquote");
+        assert_eq!(quote["content"][0]["type"], "paragraph");
+        assert_eq!(quote["content"][0]["content"][0]["text"], "quoted text");
+    }
+
+    #[test]
+    fn converts_code_block_with_language() {
+        let adf = markdown_to_adf("```rust\nfn main() {}\nlet x = 1;\n```");
+        let block = &get_content(&adf)[0];
+        assert_eq!(block["type"], "codeBlock");
+        assert_eq!(block["attrs"]["language"], "rust");
+        assert_eq!(block["content"].as_array().unwrap().len(), 1);
+        assert_eq!(block["content"][0]["text"], "fn main() {}\nlet x = 1;");
+    }
+
+    #[test]
+    fn converts_rule() {
+        let adf = markdown_to_adf("above\n\n---\n\nbelow");
+        let content = get_content(&adf);
+        assert_eq!(content[1]["type"], "rule");
+        assert_eq!(content.len(), 3);
+    }
+
+    #[test]
+    fn converts_table() {
+        let adf = markdown_to_adf("| Name | Value |\n| --- | --- |\n| a | 1 |");
+        let table = &get_content(&adf)[0];
+        assert_eq!(table["type"], "table");
+        let rows = table["content"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["content"][0]["type"], "tableHeader");
+        assert_eq!(rows[1]["content"][0]["type"], "tableCell");
+        assert_eq!(
+            rows[1]["content"][1]["content"][0]["content"][0]["text"],
+            "1"
+        );
+    }
+
+    #[test]
+    fn converts_task_list() {
+        let adf = markdown_to_adf("- [x] done\n- [ ] todo");
+        let list = &get_content(&adf)[0];
+        assert_eq!(list["type"], "taskList");
+        assert!(list["attrs"]["localId"].is_string());
+        let items = list["content"].as_array().unwrap();
+        assert_eq!(items[0]["type"], "taskItem");
+        assert_eq!(items[0]["attrs"]["state"], "DONE");
+        assert_eq!(items[0]["content"][0]["text"], "done");
+        assert_eq!(items[1]["attrs"]["state"], "TODO");
+    }
+
+    #[test]
+    fn nests_task_lists_as_siblings() {
+        let adf = markdown_to_adf("- [ ] parent\n  - [x] child");
+        let items = get_content(&adf)[0]["content"].as_array().unwrap();
+        assert_eq!(items[0]["type"], "taskItem");
+        assert_eq!(items[1]["type"], "taskList");
+        assert_eq!(items[1]["content"][0]["attrs"]["state"], "DONE");
+    }
+
+    #[test]
+    fn wraps_adf_in_doc_node() {
+        let adf = markdown_to_adf("test");
+        assert_eq!(adf["type"], "doc");
+        assert_eq!(adf["version"], 1);
+    }
+}
+
+#[cfg(test)]
+mod serialization_tests {
+    use super::*;
+
+    #[test]
+    fn print_update_request_json() {
+        let update = UpdateIssueRequest::new()
+            .description("## 概要\nテスト内容\n\n- item 1\n- item 2");
+        let json = serde_json::to_string_pretty(&update).unwrap();
+        println!("{}", json);
+    }
+
+    #[test]
+    fn add_comment_request_converts_markdown() {
+        let request = AddCommentRequest::new("**Done**\n\n- item");
+        let content = request.body["content"].as_array().unwrap();
+        assert_eq!(content[0]["content"][0]["marks"][0]["type"], "strong");
+        assert_eq!(content[1]["type"], "bulletList");
+    }
+
+    #[test]
+    fn add_comment_request_plain_keeps_text_verbatim() {
+        let request = AddCommentRequest::plain("**not bold**\nnext line");
+        let paragraph = &request.body["content"][0];
+        assert_eq!(paragraph["content"][0]["text"], "**not bold**");
+        assert!(paragraph["content"][0].get("marks").is_none());
+        assert_eq!(paragraph["content"][1]["type"], "hardBreak");
+        assert_eq!(paragraph["content"][2]["text"], "next line");
+    }
+
+    #[test]
+    fn update_request_for_data_center_uses_wiki_and_names() {
+        let update = UpdateIssueRequest::new()
+            .description("**bold**")
+            .assignee("jsmith")
+            .priority("High")
+            .for_deployment(Deployment::DataCenter);
+
+        assert_eq!(update.fields["description"], "*bold*");
+        assert_eq!(
+            update.fields["assignee"],
+            serde_json::json!({ "name": "jsmith" })
+        );
+        assert_eq!(
+            update.fields["priority"],
+            serde_json::json!({ "name": "High" })
+        );
+    }
+
+    #[test]
+    fn update_request_serializes_verb_operations() {
+        let update = UpdateIssueRequest::new()
+            .summary("Renamed")
+            .components(FieldOperation::Add, &["API", "Web"])
+            .components(FieldOperation::Remove, &["Legacy"])
+            .fix_versions(FieldOperation::Set, &["2.0"])
+            .affects_versions(FieldOperation::Set, &[]);
+
+        let json = serde_json::to_value(&update).unwrap();
+
+        assert_eq!(json["fields"], serde_json::json!({ "summary": "Renamed" }));
+        assert_eq!(
+            json["update"],
+            serde_json::json!({
+                "components": [
+                    { "add": { "name": "API" } },
+                    { "add": { "name": "Web" } },
+                    { "remove": { "name": "Legacy" } }
+                ],
+                "fixVersions": [{ "set": [{ "name": "2.0" }] }],
+                "versions": [{ "set": [] }]
+            })
+        );
+
+        let json = serde_json::to_value(UpdateIssueRequest::new().summary("x")).unwrap();
+        assert!(json.get("update").is_none());
+    }
+
+    #[test]
+    fn create_request_sets_components_and_versions() {
+        let request = CreateIssueRequest::new("PROJ", "Crash", "Bug")
+            .components(&["API"])
+            .fix_versions(&["2.0"])
+            .affects_versions(&["1.4", "1.5"]);
+
+        assert_eq!(
+            request.fields["components"],
+            serde_json::json!([{ "name": "API" }])
+        );
+        assert_eq!(
+            request.fields["fixVersions"],
+            serde_json::json!([{ "name": "2.0" }])
+        );
+        assert_eq!(
+            request.fields["versions"],
+            serde_json::json!([{ "name": "1.4" }, { "name": "1.5" }])
+        );
+    }
+
+    #[test]
+    fn update_request_for_cloud_is_unchanged() {
+        let update = UpdateIssueRequest::new()
+            .description("**bold**")
+            .assignee("abc-123")
+            .for_deployment(Deployment::Cloud);
+
+        assert_eq!(update.fields["description"]["type"], "doc");
+        assert_eq!(
+            update.fields["assignee"],
+            serde_json::json!({ "accountId": "abc-123" })
+        );
+    }
+
+    #[test]
+    fn plain_comment_for_data_center_escapes_wiki_markup() {
+        let request =
+            AddCommentRequest::plain("*not bold* [x]").for_deployment(Deployment::DataCenter);
+
+        assert_eq!(request.body, "\\*not bold\\* \\[x\\]");
+    }
+
+    #[test]
+    fn transition_request_serializes_resolution_and_comment() {
+        let request = TransitionIssueRequest::new("31")
+            .resolution("Done")
+            .comment("Fixed in **v1.2**");
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["transition"]["id"], "31");
+        assert_eq!(json["fields"]["resolution"]["name"], "Done");
+        assert_eq!(json["update"]["comment"][0]["add"]["body"]["type"], "doc");
+    }
+
+    #[test]
+    fn transition_request_omits_empty_fields_and_update() {
+        let json = serde_json::to_value(TransitionIssueRequest::new("11")).unwrap();
+
+        assert!(json.get("fields").is_none());
+        assert!(json.get("update").is_none());
+    }
+
+    #[test]
+    fn find_by_status_prefers_target_status_over_transition_name() {
+        let response: TransitionsResponse = serde_json::from_value(serde_json::json!({
+            "transitions": [
+                { "id": "11", "name": "Done", "to": { "name": "Closed" } },
+                { "id": "21", "name": "Finish", "to": { "name": "Done" } }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(response.find_by_status("done").unwrap().id, "21");
+        assert_eq!(response.find_by_status("closed").unwrap().id, "11");
+        assert!(response.find_by_status("In Review").is_none());
+    }
+
+    #[test]
+    fn worklog_request_serializes_seconds_and_adf_comment() {
+        let request = WorklogRequest::new()
+            .time_spent_seconds(5400)
+            .started("2024-01-05T09:30:00.000+0000")
+            .comment("Pairing on **login**");
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["timeSpentSeconds"], 5400);
+        assert_eq!(json["started"], "2024-01-05T09:30:00.000+0000");
+        assert_eq!(json["comment"]["type"], "doc");
+
+        let json = serde_json::to_value(request.for_deployment(Deployment::DataCenter)).unwrap();
+        assert_eq!(json["comment"], "Pairing on *login*");
+    }
+
+    #[test]
+    fn estimate_adjustment_parses_modes() {
+        assert_eq!(
+            EstimateAdjustment::from_mode(None, None).unwrap(),
+            EstimateAdjustment::Auto
+        );
+        assert_eq!(
+            EstimateAdjustment::from_mode(Some("New"), Some("3d")).unwrap(),
+            EstimateAdjustment::New("3d".to_string())
+        );
+        assert!(EstimateAdjustment::from_mode(Some("manual"), None).is_err());
+        assert!(EstimateAdjustment::from_mode(Some("sometimes"), None).is_err());
+    }
+
+    #[test]
+    fn estimate_adjustment_builds_query_parameters() {
+        assert_eq!(
+            EstimateAdjustment::New("2d".to_string()).query(false),
+            vec![
+                ("adjustEstimate", "new".to_string()),
+                ("newEstimate", "2d".to_string())
+            ]
+        );
+        assert_eq!(
+            EstimateAdjustment::Manual("1h".to_string()).query(false)[1],
+            ("reduceBy", "1h".to_string())
+        );
+        assert_eq!(
+            EstimateAdjustment::Manual("1h".to_string()).query(true)[1],
+            ("increaseBy", "1h".to_string())
+        );
+    }
+
+    fn blocks_link_types() -> IssueLinkTypesResponse {
+        serde_json::from_value(serde_json::json!({
+            "issueLinkTypes": [
+                { "id": "1", "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                { "id": "2", "name": "Relates", "inward": "relates to", "outward": "relates to" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn link_types_match_name_or_description() {
+        let types = blocks_link_types();
+
+        let (found, direction) = types.find("blocks").unwrap();
+        assert_eq!(found.name, "Blocks");
+        assert_eq!(direction, None);
+
+        let (found, direction) = types.find("Is Blocked By").unwrap();
+        assert_eq!(found.name, "Blocks");
+        assert_eq!(direction, Some(LinkDirection::Inward));
+
+        assert_eq!(
+            types.find("relates to").unwrap().1,
+            Some(LinkDirection::Outward)
+        );
+        assert!(types.find("duplicates").is_none());
+    }
+
+    #[test]
+    fn link_issues_request_orders_issues_by_direction() {
+        let json = serde_json::to_value(LinkIssuesRequest::new(
+            "Blocks",
+            "PROJ-1",
+            "PROJ-2",
+            LinkDirection::Outward,
+        ))
+        .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": { "name": "Blocks" },
+                "inwardIssue": { "key": "PROJ-1" },
+                "outwardIssue": { "key": "PROJ-2" }
+            })
+        );
+
+        let json = serde_json::to_value(LinkIssuesRequest::new(
+            "Blocks",
+            "PROJ-1",
+            "PROJ-2",
+            LinkDirection::Inward,
+        ))
+        .unwrap();
+        assert_eq!(json["inwardIssue"]["key"], "PROJ-2");
+        assert_eq!(json["outwardIssue"]["key"], "PROJ-1");
+    }
+
+    #[test]
+    fn issue_link_reads_relationship_from_present_end() {
+        let link: IssueLink = serde_json::from_value(serde_json::json!({
+            "id": "10050",
+            "type": { "id": "1", "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+            "inwardIssue": {
+                "id": "10002",
+                "key": "PROJ-2",
+                "self": "https://example.atlassian.net/rest/api/3/issue/10002",
+                "fields": { "summary": "Upstream work", "status": { "name": "Open" } }
+            }
+        }))
+        .unwrap();
+
+        let (relationship, issue) = link.relationship().unwrap();
+        assert_eq!(relationship, "is blocked by");
+        assert_eq!(issue.key, "PROJ-2");
+    }
+
+    fn sample_changelog() -> Vec<ChangelogEntry> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "id": "1",
+                "created": "2025-01-10T09:00:00.000+0000",
+                "items": [
+                    { "field": "status", "fieldId": "status", "fromString": "To Do", "toString": "In Progress" },
+                    { "field": "assignee", "fieldId": "assignee", "from": null, "toString": "Alice" }
+                ]
+            },
+            {
+                "id": "2",
+                "created": "2025-01-31T23:00:00.000+0000",
+                "items": [
+                    { "field": "Sprint", "fieldId": "customfield_10020", "fromString": "", "toString": "Sprint 4" }
+                ]
+            },
+            {
+                "id": "3",
+                "created": "2025-02-01T08:00:00.000+0000",
+                "items": [
+                    { "field": "status", "fieldId": "status", "fromString": "In Progress", "toString": "Done" }
+                ]
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn changelog_filter_keeps_matching_fields() {
+        let filter = ChangelogFilter::new(&["Status".to_string()], None, None).unwrap();
+
+        let entries = filter.apply(sample_changelog());
+
+        assert_eq!(entries.len(), 2);
+        assert!(
+            entries
+                .iter()
+                .all(|e| e.items.len() == 1 && e.items[0].field == "status")
+        );
+    }
+
+    #[test]
+    fn changelog_filter_matches_field_ids_and_date_ranges() {
+        let filter = ChangelogFilter::new(&["customfield_10020".to_string()], None, None).unwrap();
+        assert_eq!(filter.apply(sample_changelog())[0].id, "2");
+
+        // A date-only upper bound includes that whole day
+        let filter = ChangelogFilter::new(&[], Some("2025-01-15"), Some("2025-01-31")).unwrap();
+        let entries = filter.apply(sample_changelog());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "2");
+
+        assert!(ChangelogFilter::new(&[], Some("last week"), None).is_err());
+    }
+
+    fn field(id: &str, name: &str, schema: serde_json::Value) -> Field {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": name, "custom": true, "schema": schema
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn field_coercion_follows_schema_type() {
+        let points = field(
+            "customfield_10016",
+            "Story Points",
+            serde_json::json!({ "type": "number" }),
+        );
+        assert_eq!(
+            points.coerce(&serde_json::json!("5")).unwrap(),
+            serde_json::json!(5)
+        );
+        assert_eq!(
+            points.coerce(&serde_json::json!("2.5")).unwrap(),
+            serde_json::json!(2.5)
+        );
+        assert!(points.coerce(&serde_json::json!("lots")).is_err());
+        assert!(points.coerce(&serde_json::json!(true)).is_err());
+
+        let select = field(
+            "customfield_1",
+            "Severity",
+            serde_json::json!({ "type": "option" }),
+        );
+        assert_eq!(
+            select.coerce(&serde_json::json!("High")).unwrap(),
+            serde_json::json!({ "value": "High" })
+        );
+        // Values already in Jira's shape pass through
+        assert_eq!(
+            select
+                .coerce(&serde_json::json!({ "id": "10001" }))
+                .unwrap(),
+            serde_json::json!({ "id": "10001" })
+        );
+
+        let multi = field(
+            "customfield_2",
+            "Platforms",
+            serde_json::json!({ "type": "array", "items": "option" }),
+        );
+        assert_eq!(
+            multi.coerce(&serde_json::json!("iOS, Android")).unwrap(),
+            serde_json::json!([{ "value": "iOS" }, { "value": "Android" }])
+        );
+
+        let reviewers = field(
+            "customfield_3",
+            "Reviewers",
+            serde_json::json!({ "type": "array", "items": "user" }),
+        );
+        assert_eq!(
+            reviewers.coerce(&serde_json::json!(["abc"])).unwrap(),
+            serde_json::json!([{ "accountId": "abc" }])
+        );
+
+        let date = field(
+            "customfield_4",
+            "Launch",
+            serde_json::json!({ "type": "date" }),
+        );
+        assert_eq!(
+            date.coerce(&serde_json::json!("2025-03-01")).unwrap(),
+            "2025-03-01"
+        );
+        assert!(date.coerce(&serde_json::json!("March")).is_err());
+        assert!(date.coerce(&serde_json::json!("1700000000")).is_err());
+        assert!(date.coerce(&serde_json::json!("2025-02-30")).is_err());
+    }
+
+    #[test]
+    fn field_coercion_handles_sprint_and_cascading_select() {
+        let sprint = field(
+            "customfield_10020",
+            "Sprint",
+            serde_json::json!({
+                "type": "array", "items": "json",
+                "custom": "com.pyxis.greenhopper.jira:gh-sprint"
+            }),
+        );
+        assert_eq!(
+            sprint.coerce(&serde_json::json!("12")).unwrap(),
+            serde_json::json!(12)
+        );
+
+        let region = field(
+            "customfield_5",
+            "Region",
+            serde_json::json!({
+                "type": "option-with-child",
+                "custom": "com.atlassian.jira.plugin.system.customfieldtypes:cascadingselect"
+            }),
+        );
+        assert_eq!(
+            region.coerce(&serde_json::json!("EMEA > Germany")).unwrap(),
+            serde_json::json!({ "value": "EMEA", "child": { "value": "Germany" } })
+        );
+    }
+
+    #[test]
+    fn label_operations_keep_a_previously_set_value_first() {
+        let update = UpdateIssueRequest::new()
+            .labels(vec!["a"])
+            .add_labels(&["b"])
+            .remove_labels(&["c"]);
+
+        assert!(!update.fields.contains_key("labels"));
+        assert_eq!(
+            update.update["labels"],
+            vec![
+                serde_json::json!({ "set": ["a"] }),
+                serde_json::json!({ "add": "b" }),
+                serde_json::json!({ "remove": "c" })
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_field_operations_apply_to_multi_value_fields() {
+        let reviewers = field(
+            "customfield_7",
+            "Reviewers",
+            serde_json::json!({ "type": "array", "items": "user" }),
+        );
+        let points = field(
+            "customfield_10016",
+            "Story Points",
+            serde_json::json!({ "type": "number" }),
+        );
+
+        let update = UpdateIssueRequest::new()
+            .custom_field_operation(
+                &reviewers,
+                FieldOperation::Add,
+                &serde_json::json!("abc, def"),
+            )
+            .unwrap();
+        assert_eq!(
+            update.update["customfield_7"],
+            vec![
+                serde_json::json!({ "add": { "accountId": "abc" } }),
+                serde_json::json!({ "add": { "accountId": "def" } })
+            ]
+        );
+
+        let update = update.for_deployment(Deployment::DataCenter);
+        assert_eq!(
+            update.update["customfield_7"][0],
+            serde_json::json!({ "add": { "name": "abc" } })
+        );
+
+        let error = UpdateIssueRequest::new()
+            .custom_field_operation(&points, FieldOperation::Remove, &serde_json::json!(3))
+            .unwrap_err();
+        assert!(error.to_string().contains("single value"));
+    }
+
+    #[test]
+    fn field_catalogue_resolves_names_and_ids() {
+        let catalogue = FieldCatalogue::new(vec![
+            field(
+                "customfield_10016",
+                "Story Points",
+                serde_json::json!({ "type": "number" }),
+            ),
+            field(
+                "customfield_1",
+                "Team",
+                serde_json::json!({ "type": "string" }),
+            ),
+            field(
+                "customfield_2",
+                "Team",
+                serde_json::json!({ "type": "team" }),
+            ),
+        ]);
+
+        assert_eq!(
+            catalogue.resolve("story points").unwrap().id,
+            "customfield_10016"
+        );
+        assert_eq!(
+            catalogue.resolve("customfield_2").unwrap().id,
+            "customfield_2"
+        );
+        assert!(
+            catalogue
+                .resolve("Team")
+                .unwrap_err()
+                .to_string()
+                .contains("customfield_1")
+        );
+        assert!(catalogue.resolve("Velocity").is_err());
+    }
+
+    #[test]
+    fn custom_rich_text_fields_are_rendered_per_deployment() {
+        let notes = field(
+            "customfield_6",
+            "Release Notes",
+            serde_json::json!({
+                "type": "string",
+                "custom": "com.atlassian.jira.plugin.system.customfieldtypes:textarea"
+            }),
+        );
+
+        let request = UpdateIssueRequest::new()
+            .custom_field(&notes, &serde_json::json!("Fixes **login**"))
+            .unwrap();
+        assert_eq!(request.fields["customfield_6"]["type"], "doc");
+
+        let request = request.for_deployment(Deployment::DataCenter);
+        assert_eq!(request.fields["customfield_6"], "Fixes *login*");
+    }
+
+    fn create_metadata() -> CreateMetaFields {
+        serde_json::from_value(serde_json::json!({
+            "fields": [
+                { "fieldId": "summary", "name": "Summary", "required": true },
+                { "fieldId": "reporter", "name": "Reporter", "required": true, "hasDefaultValue": true },
+                { "fieldId": "customfield_10016", "name": "Story Points", "required": true },
+                {
+                    "fieldId": "priority", "name": "Priority", "required": false,
+                    "allowedValues": [{ "id": "1", "name": "Highest" }, { "id": "2", "name": "High" }, { "id": "3", "name": "Low" }]
+                },
+                {
+                    "fieldId": "components", "name": "Components", "required": false,
+                    "allowedValues": [{ "id": "10", "name": "Backend" }, { "id": "11", "name": "Frontend" }]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn create_request_validation_reports_missing_and_invalid_fields() {
+        let mut request = CreateIssueRequest::new("PROJ", "Broken login", "Bug").priority("Hihg");
+        request.fields.insert(
+            "components".to_string(),
+            serde_json::json!([{ "name": "backend" }, { "name": "Mobile" }]),
+        );
+
+        let problems = request.validate(&create_metadata());
+
+        assert_eq!(problems.len(), 3);
+        assert_eq!(
+            problems[0],
+            "Missing required field 'Story Points' (customfield_10016)"
+        );
+        assert!(
+            problems[1].starts_with("Invalid value 'Hihg' for 'Priority'. Did you mean 'High'")
+        );
+        assert!(problems[1].ends_with("Allowed: Highest, High, Low"));
+        assert_eq!(
+            problems[2],
+            "Invalid value 'Mobile' for 'Components'. Allowed: Backend, Frontend"
+        );
+    }
+
+    #[test]
+    fn create_request_validation_accepts_valid_fields() {
+        let mut request = CreateIssueRequest::new("PROJ", "Broken login", "Bug").priority("high");
+        request
+            .fields
+            .insert("customfield_10016".to_string(), serde_json::json!(3));
+
+        assert!(request.validate(&create_metadata()).is_empty());
+    }
+
+    #[test]
+    fn create_issue_types_suggest_close_matches() {
+        let issue_types: CreateMetaIssueTypes = serde_json::from_value(serde_json::json!({
+            "values": [
+                { "id": "1", "name": "Bug" },
+                { "id": "2", "name": "Story" },
+                { "id": "3", "name": "Sub-task", "subtask": true }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(issue_types.find("story").unwrap().id, "2");
+        assert_eq!(issue_types.find("3").unwrap().name, "Sub-task");
+        assert_eq!(
+            issue_types.find("Subtask").unwrap_err().to_string(),
+            "Unknown issue type 'Subtask'. Did you mean 'Sub-task'? Available: Bug, Story, Sub-task"
+        );
     }
 }
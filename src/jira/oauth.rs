@@ -0,0 +1,629 @@
+//! Atlassian OAuth 2.0 (3LO) authorization-code flow and token refresh.
+//!
+//! Reference: https://developer.atlassian.com/cloud/jira/platform/oauth-2-3lo-apps/
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+use super::random::{random_bytes, random_token};
+use super::time::now_secs;
+
+const AUTHORIZE_URL: &str = "https://auth.atlassian.com/authorize";
+const TOKEN_URL: &str = "https://auth.atlassian.com/oauth/token";
+const ACCESSIBLE_RESOURCES_URL: &str = "https://api.atlassian.com/oauth/token/accessible-resources";
+const DEFAULT_SCOPES: &str = "read:jira-work write:jira-work read:jira-user offline_access";
+const DEFAULT_REDIRECT_PORT: u16 = 8765;
+
+/// Refresh access tokens this many seconds before they expire.
+const EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// OAuth app settings and endpoints.
+#[derive(Debug, Clone)]
+pub struct OAuthConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub scopes: String,
+    /// Port of the loopback redirect listener (`http://localhost:{port}/callback`)
+    pub redirect_port: u16,
+    /// Where tokens are stored between runs
+    pub token_file: PathBuf,
+    /// Site to pick from the accessible resources (e.g. `https://your-domain.atlassian.net`)
+    pub site_url: Option<String>,
+    pub authorize_url: String,
+    pub token_url: String,
+    pub resources_url: String,
+}
+
+impl OAuthConfig {
+    pub fn new(client_id: &str, client_secret: &str, token_file: PathBuf) -> Self {
+        Self {
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            scopes: DEFAULT_SCOPES.to_string(),
+            redirect_port: DEFAULT_REDIRECT_PORT,
+            token_file,
+            site_url: None,
+            authorize_url: AUTHORIZE_URL.to_string(),
+            token_url: TOKEN_URL.to_string(),
+            resources_url: ACCESSIBLE_RESOURCES_URL.to_string(),
+        }
+    }
+
+    /// Read the configuration from `JIRA_OAUTH_*` environment variables.
+    pub fn from_env() -> Result<Self> {
+        let client_id = std::env::var("JIRA_OAUTH_CLIENT_ID")
+            .context("JIRA_OAUTH_CLIENT_ID environment variable is required")?;
+        let client_secret = std::env::var("JIRA_OAUTH_CLIENT_SECRET")
+            .context("JIRA_OAUTH_CLIENT_SECRET environment variable is required")?;
+        let token_file = match std::env::var("JIRA_OAUTH_TOKEN_FILE") {
+            Ok(path) => PathBuf::from(path),
+            Err(_) => default_token_file()?,
+        };
+
+        let mut config = Self::new(&client_id, &client_secret, token_file);
+        if let Ok(scopes) = std::env::var("JIRA_OAUTH_SCOPES") {
+            config.scopes = scopes;
+        }
+        if let Ok(port) = std::env::var("JIRA_OAUTH_REDIRECT_PORT") {
+            config.redirect_port = port
+                .parse()
+                .context("JIRA_OAUTH_REDIRECT_PORT must be a port number")?;
+        }
+        config.site_url = std::env::var("JIRA_BASE_URL").ok();
+        Ok(config)
+    }
+
+    pub fn redirect_uri(&self) -> String {
+        format!("http://localhost:{}/callback", self.redirect_port)
+    }
+}
+
+fn default_token_file() -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME is not set; set JIRA_OAUTH_TOKEN_FILE")?;
+    Ok(Path::new(&home)
+        .join(".config")
+        .join("jira-mcp-rs")
+        .join("oauth-token.json"))
+}
+
+/// Tokens persisted to disk between runs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StoredToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Unix timestamp (seconds) when the access token expires
+    pub expires_at: u64,
+    /// Cloud ID of the Jira site, used in `https://api.atlassian.com/ex/jira/{cloud_id}`
+    pub cloud_id: String,
+    pub site_url: Option<String>,
+}
+
+impl StoredToken {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).with_context(|| {
+            format!(
+                "No OAuth tokens at {}; run `jira-mcp-rs login` first",
+                path.display()
+            )
+        })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write tokens readable only by the current user.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)?;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            // `mode` only applies on creation; tighten files left by older runs too
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+
+        use std::io::Write;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+
+    /// Base URL for REST calls through the Atlassian API gateway.
+    pub fn api_base_url(&self) -> String {
+        format!("https://api.atlassian.com/ex/jira/{}", self.cloud_id)
+    }
+
+    fn is_expired(&self) -> bool {
+        now_secs() + EXPIRY_MARGIN_SECS >= self.expires_at
+    }
+}
+
+/// Response from POST https://auth.atlassian.com/oauth/token
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    /// Atlassian rotates refresh tokens; absent when rotation is disabled
+    refresh_token: Option<String>,
+    expires_in: u64,
+}
+
+/// Entry from GET https://api.atlassian.com/oauth/token/accessible-resources
+#[derive(Debug, Deserialize)]
+pub struct AccessibleResource {
+    pub id: String,
+    pub url: String,
+    pub name: String,
+}
+
+/// OAuth credentials for a `JiraClient`, refreshed transparently before expiry.
+pub struct OAuthSession {
+    config: OAuthConfig,
+    client: Client,
+    api_base_url: String,
+    site_url: Option<String>,
+    token: Mutex<StoredToken>,
+}
+
+impl OAuthSession {
+    pub fn new(config: OAuthConfig, token: StoredToken) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+            api_base_url: token.api_base_url(),
+            site_url: token.site_url.clone(),
+            token: Mutex::new(token),
+        }
+    }
+
+    /// Load stored tokens from the configured token file.
+    pub fn load(config: OAuthConfig) -> Result<Self> {
+        let token = StoredToken::load(&config.token_file)?;
+        Ok(Self::new(config, token))
+    }
+
+    /// Base URL of the authorized site on the Atlassian API gateway.
+    pub fn api_base_url(&self) -> &str {
+        &self.api_base_url
+    }
+
+    /// URL of the Jira site in the browser, e.g. `https://example.atlassian.net`.
+    pub fn site_url(&self) -> Option<&str> {
+        self.site_url.as_deref()
+    }
+
+    /// Return a valid `Authorization` header value, refreshing the access token
+    /// first if it is about to expire.
+    pub async fn authorization(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+        if token.is_expired() {
+            self.refresh_locked(&mut token).await?;
+        }
+        Ok(format!("Bearer {}", token.access_token))
+    }
+
+    /// Refresh after the API rejected `rejected_authorization` (the header
+    /// value returned by [`authorization`](Self::authorization)). Nothing is
+    /// done if a concurrent request has already replaced that token.
+    pub async fn refresh_rejected(&self, rejected_authorization: &str) -> Result<()> {
+        let mut token = self.token.lock().await;
+        if format!("Bearer {}", token.access_token) != rejected_authorization {
+            return Ok(());
+        }
+        self.refresh_locked(&mut token).await
+    }
+
+    async fn refresh_locked(&self, token: &mut StoredToken) -> Result<()> {
+        tracing::debug!("Refreshing OAuth access token");
+
+        let response = self
+            .client
+            .post(&self.config.token_url)
+            .json(&serde_json::json!({
+                "grant_type": "refresh_token",
+                "client_id": self.config.client_id,
+                "client_secret": self.config.client_secret,
+                "refresh_token": token.refresh_token,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "OAuth token refresh failed ({}): {}. Run `jira-mcp-rs login` again.",
+                status,
+                error_text
+            );
+        }
+
+        let refreshed = response.json::<TokenResponse>().await?;
+        token.access_token = refreshed.access_token;
+        if let Some(refresh_token) = refreshed.refresh_token {
+            token.refresh_token = refresh_token;
+        }
+        token.expires_at = now_secs() + refreshed.expires_in;
+        token.save(&self.config.token_file)?;
+        Ok(())
+    }
+}
+
+/// Run the interactive authorization-code flow and store the resulting tokens.
+///
+/// Prints the authorization URL, waits for the browser to hit the loopback
+/// redirect listener, exchanges the code, resolves the cloud ID via
+/// accessible-resources and writes the token file.
+pub async fn login(config: &OAuthConfig) -> Result<StoredToken> {
+    let state = random_token();
+    let code_verifier = URL_SAFE_NO_PAD.encode(random_bytes::<32>());
+    let code_challenge = pkce_challenge(&code_verifier);
+    let authorize_url = Url::parse_with_params(
+        &config.authorize_url,
+        &[
+            ("audience", "api.atlassian.com"),
+            ("client_id", config.client_id.as_str()),
+            ("scope", config.scopes.as_str()),
+            ("redirect_uri", config.redirect_uri().as_str()),
+            ("state", state.as_str()),
+            ("code_challenge", code_challenge.as_str()),
+            ("code_challenge_method", "S256"),
+            ("response_type", "code"),
+            ("prompt", "consent"),
+        ],
+    )?;
+
+    let listener = TcpListener::bind(("127.0.0.1", config.redirect_port))
+        .await
+        .with_context(|| format!("Cannot listen on port {}", config.redirect_port))?;
+
+    eprintln!(
+        "Open this URL in your browser to authorize jira-mcp-rs:\n\n{}\n",
+        authorize_url
+    );
+
+    let code = wait_for_callback(&listener, &state).await?;
+    let client = Client::new();
+
+    let response = client
+        .post(&config.token_url)
+        .json(&serde_json::json!({
+            "grant_type": "authorization_code",
+            "client_id": config.client_id,
+            "client_secret": config.client_secret,
+            "code": code,
+            "redirect_uri": config.redirect_uri(),
+            "code_verifier": code_verifier,
+        }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("OAuth code exchange failed ({}): {}", status, error_text);
+    }
+    let tokens = response.json::<TokenResponse>().await?;
+    let refresh_token = tokens
+        .refresh_token
+        .context("No refresh token returned; include the offline_access scope")?;
+
+    let resources =
+        get_accessible_resources(&client, &config.resources_url, &tokens.access_token).await?;
+    let resource = select_resource(&resources, config.site_url.as_deref())?;
+
+    let stored = StoredToken {
+        access_token: tokens.access_token,
+        refresh_token,
+        expires_at: now_secs() + tokens.expires_in,
+        cloud_id: resource.id.clone(),
+        site_url: Some(resource.url.clone()),
+    };
+    stored.save(&config.token_file)?;
+
+    eprintln!(
+        "Logged in to {} ({}). Tokens saved to {}",
+        resource.name,
+        resource.url,
+        config.token_file.display()
+    );
+    Ok(stored)
+}
+
+async fn get_accessible_resources(
+    client: &Client,
+    url: &str,
+    access_token: &str,
+) -> Result<Vec<AccessibleResource>> {
+    let response = client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", access_token))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!(
+            "Failed to list accessible resources ({}): {}",
+            status,
+            error_text
+        );
+    }
+    Ok(response.json().await?)
+}
+
+/// Pick the site matching `site_url`, or the only/first accessible site.
+fn select_resource<'a>(
+    resources: &'a [AccessibleResource],
+    site_url: Option<&str>,
+) -> Result<&'a AccessibleResource> {
+    let normalize = |url: &str| url.trim_end_matches('/').to_ascii_lowercase();
+
+    match site_url {
+        Some(site) => resources
+            .iter()
+            .find(|r| normalize(&r.url) == normalize(site))
+            .with_context(|| {
+                let available: Vec<&str> = resources.iter().map(|r| r.url.as_str()).collect();
+                format!(
+                    "{} is not accessible with this authorization. Available sites: {}",
+                    site,
+                    available.join(", ")
+                )
+            }),
+        None => resources
+            .first()
+            .context("The authorization grants access to no Jira sites"),
+    }
+}
+
+/// Accept connections until the browser is redirected to `/callback`, and
+/// return the authorization code.
+async fn wait_for_callback(listener: &TcpListener, expected_state: &str) -> Result<String> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+
+        let mut buffer = vec![0u8; 8192];
+        let read = stream.read(&mut buffer).await?;
+        let request = String::from_utf8_lossy(&buffer[..read]);
+        let request_line = request.lines().next().unwrap_or_default();
+
+        let Some(result) = parse_callback(request_line, expected_state) else {
+            stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .await?;
+            continue;
+        };
+
+        let message = match &result {
+            Ok(_) => "Login complete. You can close this window.",
+            Err(_) => "Login failed. Check the terminal for details.",
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            message.len(),
+            message
+        );
+        stream.write_all(response.as_bytes()).await?;
+        return result;
+    }
+}
+
+/// Parse `GET /callback?code=..&state=.. HTTP/1.1`. Returns `None` for other paths.
+fn parse_callback(request_line: &str, expected_state: &str) -> Option<Result<String>> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let url = Url::parse(&format!("http://localhost{}", target)).ok()?;
+    if url.path() != "/callback" {
+        return None;
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    if let Some(error) = param("error") {
+        let description = param("error_description").unwrap_or_default();
+        return Some(Err(anyhow::anyhow!(
+            "Authorization denied: {} {}",
+            error,
+            description
+        )));
+    }
+    if param("state").as_deref() != Some(expected_state) {
+        return Some(Err(anyhow::anyhow!(
+            "OAuth state mismatch; possible CSRF, aborting"
+        )));
+    }
+    Some(param("code").context("Callback is missing the authorization code"))
+}
+
+/// PKCE S256 code challenge: the base64url SHA-256 digest of the verifier.
+fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn temp_token_file(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("jira-mcp-rs-test-{}-{}", std::process::id(), name))
+            .join("oauth-token.json")
+    }
+
+    fn stored_token(expires_at: u64) -> StoredToken {
+        StoredToken {
+            access_token: "old-access".to_string(),
+            refresh_token: "refresh-1".to_string(),
+            expires_at,
+            cloud_id: "cloud-123".to_string(),
+            site_url: Some("https://example.atlassian.net".to_string()),
+        }
+    }
+
+    #[test]
+    fn parse_callback_returns_code_when_state_matches() {
+        let result = parse_callback("GET /callback?code=abc&state=xyz HTTP/1.1", "xyz");
+        assert_eq!(result.unwrap().unwrap(), "abc");
+    }
+
+    #[test]
+    fn parse_callback_rejects_state_mismatch() {
+        let result = parse_callback("GET /callback?code=abc&state=evil HTTP/1.1", "xyz");
+        assert!(result.unwrap().is_err());
+    }
+
+    #[test]
+    fn parse_callback_ignores_other_paths() {
+        assert!(parse_callback("GET /favicon.ico HTTP/1.1", "xyz").is_none());
+    }
+
+    #[test]
+    fn pkce_challenge_matches_rfc_7636_example() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn select_resource_matches_site_url() {
+        let resources = vec![
+            AccessibleResource {
+                id: "1".to_string(),
+                url: "https://one.atlassian.net".to_string(),
+                name: "one".to_string(),
+            },
+            AccessibleResource {
+                id: "2".to_string(),
+                url: "https://two.atlassian.net".to_string(),
+                name: "two".to_string(),
+            },
+        ];
+
+        let selected = select_resource(&resources, Some("https://TWO.atlassian.net/")).unwrap();
+        assert_eq!(selected.id, "2");
+        assert_eq!(select_resource(&resources, None).unwrap().id, "1");
+        assert!(select_resource(&resources, Some("https://three.atlassian.net")).is_err());
+    }
+
+    #[test]
+    fn stored_token_round_trips_with_restrictive_permissions() {
+        let file = temp_token_file("save");
+        let token = stored_token(1_000);
+
+        token.save(&file).unwrap();
+        let loaded = StoredToken::load(&file).unwrap();
+
+        assert_eq!(loaded.refresh_token, "refresh-1");
+        assert_eq!(
+            loaded.api_base_url(),
+            "https://api.atlassian.com/ex/jira/cloud-123"
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_dir_all(file.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn authorization_refreshes_expired_token_and_persists_it() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .and(body_partial_json(serde_json::json!({
+                "grant_type": "refresh_token",
+                "refresh_token": "refresh-1"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "new-access",
+                "refresh_token": "refresh-2",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let file = temp_token_file("refresh");
+        let mut config = OAuthConfig::new("client", "secret", file.clone());
+        config.token_url = format!("{}/oauth/token", mock_server.uri());
+        let session = OAuthSession::new(config, stored_token(0));
+
+        assert_eq!(session.authorization().await.unwrap(), "Bearer new-access");
+        // A fresh token is reused without another refresh
+        assert_eq!(session.authorization().await.unwrap(), "Bearer new-access");
+
+        let saved = StoredToken::load(&file).unwrap();
+        assert_eq!(saved.refresh_token, "refresh-2");
+        std::fs::remove_dir_all(file.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn refresh_rejected_skips_tokens_already_replaced() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "new-access",
+                "expires_in": 3600
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let file = temp_token_file("rejected");
+        let mut config = OAuthConfig::new("client", "secret", file.clone());
+        config.token_url = format!("{}/oauth/token", mock_server.uri());
+        let session = OAuthSession::new(config, stored_token(u64::MAX));
+
+        // Two requests rejected with the same token: only the first refreshes
+        session.refresh_rejected("Bearer old-access").await.unwrap();
+        session.refresh_rejected("Bearer old-access").await.unwrap();
+
+        assert_eq!(session.authorization().await.unwrap(), "Bearer new-access");
+        std::fs::remove_dir_all(file.parent().unwrap()).ok();
+    }
+
+    #[tokio::test]
+    async fn authorization_reports_failed_refresh() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("invalid_grant"))
+            .mount(&mock_server)
+            .await;
+
+        let mut config = OAuthConfig::new("client", "secret", temp_token_file("failed"));
+        config.token_url = format!("{}/oauth/token", mock_server.uri());
+        let session = OAuthSession::new(config, stored_token(0));
+
+        let error = session.authorization().await.unwrap_err().to_string();
+        assert!(error.contains("invalid_grant"));
+        assert!(error.contains("login"));
+    }
+}
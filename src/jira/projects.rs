@@ -0,0 +1,140 @@
+//! Projects and the issue types and components they define.
+
+use reqwest::Method;
+
+use super::error::Result;
+use super::{Component, Deployment, JiraClient, Project, ProjectPage};
+
+impl JiraClient {
+    /// A page of projects visible to the user, optionally filtered by a
+    /// case-insensitive match on key or name.
+    ///
+    /// Data Center has no paged project search, so all projects are fetched
+    /// and the page is cut from them.
+    pub async fn list_projects(
+        &self,
+        query: Option<&str>,
+        start_at: u32,
+        max_results: u32,
+    ) -> Result<ProjectPage> {
+        if self.deployment == Deployment::DataCenter {
+            let url = self.api_url("project");
+            let projects: Vec<Project> = self.get_json(&url).await?;
+            return Ok(project_page(projects, query, start_at, max_results));
+        }
+
+        let url = self.api_url("project/search");
+        let mut request = self
+            .request(Method::GET, &url)
+            .query(&[("startAt", start_at), ("maxResults", max_results)])
+            .query(&[("orderBy", "key")]);
+        if let Some(query) = query {
+            request = request.query(&[("query", query)]);
+        }
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// A project with its description, lead and issue types.
+    pub async fn get_project(&self, project_key: &str) -> Result<Project> {
+        let url = self.api_url(&format!(
+            "project/{}?expand=description,lead,issueTypes",
+            project_key
+        ));
+
+        self.get_json(&url).await
+    }
+
+    pub async fn get_project_components(&self, project_key: &str) -> Result<Vec<Component>> {
+        let url = self.api_url(&format!("project/{}/components", project_key));
+
+        self.get_json(&url).await
+    }
+}
+
+/// Filter and page a full project list the way project search does.
+fn project_page(
+    projects: Vec<Project>,
+    query: Option<&str>,
+    start_at: u32,
+    max_results: u32,
+) -> ProjectPage {
+    let query = query.map(str::to_lowercase);
+    let matching: Vec<Project> = projects
+        .into_iter()
+        .filter(|p| {
+            query.as_ref().is_none_or(|q| {
+                p.key.to_lowercase().contains(q) || p.name.to_lowercase().contains(q)
+            })
+        })
+        .collect();
+
+    let total = matching.len() as u32;
+    let values: Vec<Project> = matching
+        .into_iter()
+        .skip(start_at as usize)
+        .take(max_results as usize)
+        .collect();
+    ProjectPage {
+        start_at,
+        max_results,
+        total,
+        is_last: start_at + values.len() as u32 >= total,
+        values,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn list_projects_searches_on_cloud() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/project/search"))
+            .and(query_param("query", "pay"))
+            .and(query_param("startAt", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "startAt": 0, "maxResults": 50, "total": 1, "isLast": true,
+                "values": [{ "id": "10000", "key": "PAY", "name": "Payments", "projectTypeKey": "software" }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let page = client.list_projects(Some("pay"), 0, 50).await.unwrap();
+
+        assert_eq!(page.total, 1);
+        assert!(page.is_last);
+        assert_eq!(page.values[0].key, "PAY");
+    }
+
+    #[tokio::test]
+    async fn data_center_list_projects_filters_and_pages_locally() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/project"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "id": "1", "key": "OPS", "name": "Operations" },
+                { "id": "2", "key": "PAY", "name": "Payments" },
+                { "id": "3", "key": "PAYOUT", "name": "Payouts" }
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat");
+
+        let page = client.list_projects(Some("PAY"), 1, 1).await.unwrap();
+
+        assert_eq!(page.total, 2);
+        assert!(page.is_last);
+        let keys: Vec<&str> = page.values.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(keys, vec!["PAYOUT"]);
+    }
+}
@@ -0,0 +1,29 @@
+//! Unpredictable values from the operating system's random number generator,
+//! for OAuth state, PKCE verifiers and confirmation tokens.
+
+/// 128 random bits as hex.
+pub fn random_token() -> String {
+    random_bytes::<16>()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes).expect("the OS random number generator is unavailable");
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_tokens_are_unique_hex() {
+        let (first, second) = (random_token(), random_token());
+        assert_eq!(first.len(), 32);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,149 @@
+//! Backoff policy for rate-limited and transiently failing requests.
+//!
+//! Reference: https://developer.atlassian.com/cloud/jira/platform/rate-limiting/
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+
+use super::time::{now_secs, parse_timestamp};
+
+/// How often and how long `JiraClient` waits before retrying a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 disables retrying
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each further retry
+    pub base_delay: Duration,
+    /// Upper bound for a single wait. A server asking for a longer wait gets
+    /// its error returned instead.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Jittered exponential backoff for the given retry (0-based): a random
+    /// delay between half and all of `base_delay * 2^retry`, capped at `max_delay`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let half = delay / 2;
+        half + jitter(delay - half)
+    }
+}
+
+/// Methods that can be repeated without changing the outcome.
+pub fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+/// Whether a response status is worth retrying. Rate-limited requests were
+/// rejected before processing and are always safe to repeat; server errors
+/// only for idempotent requests.
+pub fn is_retryable(status: StatusCode, idempotent: bool) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || (idempotent && status.is_server_error())
+}
+
+/// Delay requested by the server through `Retry-After` (seconds) or
+/// `X-RateLimit-Reset` (timestamp).
+pub fn server_delay(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(seconds) = header("Retry-After").and_then(|v| v.trim().parse::<u64>().ok()) {
+        return Some(Duration::from_secs(seconds));
+    }
+    let reset = header("X-RateLimit-Reset").and_then(parse_timestamp)?;
+    Some(Duration::from_secs(reset.saturating_sub(now_secs())))
+}
+
+fn jitter(max: Duration) -> Duration {
+    let max_nanos = max.as_nanos() as u64;
+    if max_nanos == 0 {
+        return Duration::ZERO;
+    }
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_nanos(random % (max_nanos + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn backoff_doubles_within_jitter_bounds_and_caps() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+
+        for _ in 0..20 {
+            let first = policy.backoff(0);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let second = policy.backoff(1);
+            assert!(second >= Duration::from_millis(100) && second <= Duration::from_millis(200));
+            let capped = policy.backoff(10);
+            assert!(capped >= Duration::from_millis(150) && capped <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn only_safe_requests_are_retried_on_server_errors() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(!is_idempotent(&Method::POST));
+
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS, false));
+        assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE, true));
+        assert!(!is_retryable(StatusCode::SERVICE_UNAVAILABLE, false));
+        assert!(!is_retryable(StatusCode::BAD_REQUEST, true));
+    }
+
+    #[test]
+    fn server_delay_prefers_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Retry-After", HeaderValue::from_static("7"));
+        headers.insert(
+            "X-RateLimit-Reset",
+            HeaderValue::from_static("2000-01-01T00:00Z"),
+        );
+
+        assert_eq!(server_delay(&headers), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn server_delay_reads_rate_limit_reset() {
+        let mut headers = HeaderMap::new();
+        let reset = (now_secs() + 20).to_string();
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_str(&reset).unwrap());
+
+        let delay = server_delay(&headers).unwrap();
+        assert!(delay <= Duration::from_secs(20) && delay >= Duration::from_secs(18));
+
+        // A reset time in the past means retry now
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-RateLimit-Reset",
+            HeaderValue::from_static("2000-01-01T00:00Z"),
+        );
+        assert_eq!(server_delay(&headers), Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,371 @@
+//! Jira time tracking durations and timestamps.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+/// Jira's default working day (8 hours)
+const DAY: u64 = 8 * HOUR;
+/// Jira's default working week (5 days)
+const WEEK: u64 = 5 * DAY;
+
+/// Parse a Jira duration such as `"1h 30m"`, `"2d"`, `"1.5h"` or `"1w 2d"` into
+/// seconds. A bare number is read as minutes, as in Jira. Days and weeks use
+/// Jira's default 8-hour day and 5-day week.
+pub fn parse_duration(input: &str) -> Result<u64> {
+    let mut total = 0.0;
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        anyhow::bail!("Duration is empty");
+    }
+
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let value: f64 = rest[..number_len]
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid duration '{}': expected e.g. '1h 30m'", input))?;
+        rest = rest[number_len..].trim_start();
+
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_len];
+        let seconds = match unit.to_ascii_lowercase().as_str() {
+            "w" | "wk" | "wks" | "week" | "weeks" => WEEK,
+            "d" | "day" | "days" => DAY,
+            "h" | "hr" | "hrs" | "hour" | "hours" => HOUR,
+            "" | "m" | "min" | "mins" | "minute" | "minutes" => MINUTE,
+            other => anyhow::bail!(
+                "Invalid duration '{}': unknown unit '{}' (use w, d, h or m)",
+                input,
+                other
+            ),
+        };
+        total += value * seconds as f64;
+        rest = rest[unit_len..].trim_start_matches([' ', ',']);
+    }
+
+    let total = total.round() as u64;
+    if total < MINUTE {
+        anyhow::bail!("Duration '{}' must be at least one minute", input);
+    }
+    Ok(total)
+}
+
+/// Format seconds the way Jira displays durations, e.g. `"1w 2d 3h 30m"`.
+pub fn format_duration(seconds: u64) -> String {
+    let mut remaining = seconds;
+    let mut parts = Vec::new();
+    for (unit, size) in [("w", WEEK), ("d", DAY), ("h", HOUR), ("m", MINUTE)] {
+        let count = remaining / size;
+        if count > 0 {
+            parts.push(format!("{}{}", count, unit));
+            remaining %= size;
+        }
+    }
+
+    if parts.is_empty() {
+        "0m".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Convert an ISO 8601 date or datetime to the format Jira expects for
+/// worklog `started` values (`2024-01-05T09:30:00.000+0000`). Values without
+/// a UTC offset are taken as UTC.
+pub fn to_jira_timestamp(input: &str) -> Result<String> {
+    Ok(format_jira_timestamp(parse_or_explain(input)?))
+}
+
+/// The current time in Jira's timestamp format.
+pub fn jira_timestamp_now() -> String {
+    format_jira_timestamp(now_secs())
+}
+
+/// Convert an ISO 8601 date or datetime to a UTC ISO 8601 timestamp
+/// (`2024-01-05T09:30:00.000Z`), as the Agile API expects for sprint dates.
+pub fn to_iso_timestamp(input: &str) -> Result<String> {
+    Ok(format_iso_timestamp(parse_or_explain(input)?))
+}
+
+/// The current time as a UTC ISO 8601 timestamp.
+pub fn iso_timestamp_now() -> String {
+    format_iso_timestamp(now_secs())
+}
+
+/// The ISO 8601 timestamp `days` calendar days after `input`.
+pub fn iso_timestamp_plus_days(input: &str, days: u64) -> Result<String> {
+    Ok(format_iso_timestamp(
+        parse_or_explain(input)? + days * 86_400,
+    ))
+}
+
+/// Convert an ISO 8601 date or datetime to a `YYYY-MM-DD` date (UTC), as
+/// versions expect for start and release dates.
+pub fn to_date(input: &str) -> Result<String> {
+    Ok(format_date(parse_or_explain(input)?))
+}
+
+/// Today's date (UTC) as `YYYY-MM-DD`.
+pub fn date_today() -> String {
+    format_date(now_secs())
+}
+
+fn parse_or_explain(input: &str) -> Result<u64> {
+    parse_timestamp(input).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid timestamp '{}': expected e.g. '2024-01-05T09:30:00+09:00'",
+            input
+        )
+    })
+}
+
+/// Format a Unix timestamp in milliseconds (as in ADF date nodes) as
+/// `YYYY-MM-DD` in UTC.
+pub fn date_from_millis(millis: i64) -> String {
+    let (year, month, day) = civil_from_days(millis.div_euclid(86_400_000));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn format_date(seconds: u64) -> String {
+    format_utc(seconds)[..10].to_string()
+}
+
+fn format_jira_timestamp(seconds: u64) -> String {
+    format!("{}.000+0000", format_utc(seconds))
+}
+
+fn format_iso_timestamp(seconds: u64) -> String {
+    format!("{}.000Z", format_utc(seconds))
+}
+
+/// `YYYY-MM-DDTHH:MM:SS` in UTC.
+fn format_utc(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let (year, month, day) = civil_from_days(days);
+    let time = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Parse an ISO 8601 date or datetime (`2025-01-07`, `2025-01-07T15:30:00.000Z`,
+/// `2025-01-07T15:30+09:00`, `2025-01-07T15:30:00.000+0900`) or Unix seconds
+/// into Unix seconds.
+pub fn parse_timestamp(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+
+    let (date, time) = value.split_once('T').unwrap_or((value, "00:00"));
+    let mut date_parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+
+    let (clock, offset_secs) = if let Some(clock) = time.strip_suffix('Z') {
+        (clock, 0)
+    } else if let Some(split) = time.rfind(['+', '-']) {
+        let (clock, offset) = time.split_at(split);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let digits = offset[1..].replace(':', "");
+        if digits.len() != 4 {
+            return None;
+        }
+        let hours = digits[..2].parse::<i64>().ok()?;
+        let minutes = digits[2..].parse::<i64>().ok()?;
+        (clock, sign * (hours * 3600 + minutes * 60))
+    } else {
+        (time, 0)
+    };
+
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour = clock_parts.next()?.parse::<i64>().ok()?;
+    let minute = clock_parts.next()?.parse::<i64>().ok()?;
+    let second = match clock_parts.next() {
+        Some(s) => s.split('.').next()?.parse::<i64>().ok()?,
+        None => 0,
+    };
+    // 60 allows for a leap second
+    if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..61).contains(&second) {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    u64::try_from(seconds).ok()
+}
+
+/// Number of days in `month` (1-12) of `year`.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date for days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_accepts_jira_notation() {
+        assert_eq!(parse_duration("1h 30m").unwrap(), 5400);
+        assert_eq!(parse_duration("1h30m").unwrap(), 5400);
+        assert_eq!(parse_duration("1.5h").unwrap(), 5400);
+        assert_eq!(parse_duration("90").unwrap(), 5400);
+        assert_eq!(parse_duration("2d").unwrap(), 2 * 8 * 3600);
+        assert_eq!(parse_duration("1w 1d").unwrap(), 6 * 8 * 3600);
+        assert_eq!(parse_duration("2 hours, 15 minutes").unwrap(), 8100);
+    }
+
+    #[test]
+    fn parse_duration_rejects_invalid_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("0m").is_err());
+    }
+
+    #[test]
+    fn format_duration_uses_largest_units() {
+        assert_eq!(format_duration(5400), "1h 30m");
+        assert_eq!(format_duration(6 * 8 * 3600), "1w 1d");
+        assert_eq!(format_duration(0), "0m");
+    }
+
+    #[test]
+    fn durations_round_trip() {
+        for input in ["1w 2d 3h 30m", "4h", "45m", "3d 1h"] {
+            assert_eq!(format_duration(parse_duration(input).unwrap()), input);
+        }
+    }
+
+    #[test]
+    fn parse_timestamp_handles_iso_8601_variants() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_timestamp("2024-02-29T12:30:15.250Z"),
+            Some(1_709_209_815)
+        );
+        assert_eq!(
+            parse_timestamp("2024-02-29T21:30:15+09:00"),
+            Some(1_709_209_815)
+        );
+        assert_eq!(
+            parse_timestamp("2024-02-29T21:30:15.000+0900"),
+            Some(1_709_209_815)
+        );
+        assert_eq!(parse_timestamp("2024-02-29T12:30Z"), Some(1_709_209_800));
+        assert_eq!(parse_timestamp("2024-02-29"), Some(1_709_164_800));
+        assert_eq!(parse_timestamp("1709209815"), Some(1_709_209_815));
+        assert_eq!(parse_timestamp("soon"), None);
+    }
+
+    #[test]
+    fn parse_timestamp_rejects_out_of_range_dates_and_times() {
+        assert_eq!(parse_timestamp("2025-02-30"), None);
+        assert_eq!(parse_timestamp("2025-02-29"), None);
+        assert_eq!(parse_timestamp("1900-02-29"), None);
+        assert_eq!(parse_timestamp("2025-04-31"), None);
+        assert_eq!(parse_timestamp("2025-01-07T25:00Z"), None);
+        assert_eq!(parse_timestamp("2025-01-07T12:99Z"), None);
+        assert_eq!(parse_timestamp("2025-01-07T12:30:61Z"), None);
+        assert!(parse_timestamp("2000-02-29T23:59:60Z").is_some());
+    }
+
+    #[test]
+    fn to_jira_timestamp_normalizes_to_utc() {
+        assert_eq!(
+            to_jira_timestamp("2024-02-29T21:30:15+09:00").unwrap(),
+            "2024-02-29T12:30:15.000+0000"
+        );
+        assert_eq!(
+            to_jira_timestamp("2024-12-31").unwrap(),
+            "2024-12-31T00:00:00.000+0000"
+        );
+        assert!(to_jira_timestamp("yesterday").is_err());
+    }
+
+    #[test]
+    fn iso_timestamps_use_utc_designator() {
+        assert_eq!(
+            to_iso_timestamp("2024-02-29T21:30:15+09:00").unwrap(),
+            "2024-02-29T12:30:15.000Z"
+        );
+        assert_eq!(
+            iso_timestamp_plus_days("2024-02-20", 14).unwrap(),
+            "2024-03-05T00:00:00.000Z"
+        );
+        assert!(to_iso_timestamp("next monday").is_err());
+    }
+
+    #[test]
+    fn dates_are_taken_in_utc() {
+        assert_eq!(to_date("2024-03-01").unwrap(), "2024-03-01");
+        assert_eq!(to_date("2024-03-01T08:00:00+09:00").unwrap(), "2024-02-29");
+        assert_eq!(date_today().len(), 10);
+        assert!(to_date("soon").is_err());
+    }
+
+    #[test]
+    fn dates_from_millis_handle_leap_days_and_pre_epoch_times() {
+        assert_eq!(date_from_millis(1_709_164_800_000), "2024-02-29");
+        assert_eq!(date_from_millis(-1), "1969-12-31");
+    }
+}
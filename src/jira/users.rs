@@ -0,0 +1,388 @@
+//! User lookup: searching users, users assignable to a project or issue, the
+//! current user, and resolving an email, display name or "me" to the
+//! identifier Jira expects (account ID on Cloud, username on Data Center).
+
+use reqwest::Method;
+
+use super::error::Result;
+use super::{Deployment, Field, JiraClient, User};
+
+/// Most users fetched when resolving a name or email
+const USER_LOOKUP_LIMIT: u32 = 20;
+
+impl JiraClient {
+    /// Users whose display name, email address or username matches `query`.
+    pub async fn search_users(&self, query: &str, max_results: u32) -> Result<Vec<User>> {
+        let url = self.api_url("user/search");
+        let request = self
+            .request(Method::GET, &url)
+            .query(&[(self.user_query_param(), query)])
+            .query(&[("maxResults", max_results)]);
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// Users who can be assigned issues in a project or a specific issue,
+    /// optionally narrowed down by `query`.
+    pub async fn find_assignable_users(
+        &self,
+        project_key: Option<&str>,
+        issue_key: Option<&str>,
+        query: Option<&str>,
+        max_results: u32,
+    ) -> Result<Vec<User>> {
+        let url = self.api_url("user/assignable/search");
+        let mut params = Vec::new();
+        if let Some(project_key) = project_key {
+            params.push(("project", project_key));
+        }
+        if let Some(issue_key) = issue_key {
+            params.push(("issueKey", issue_key));
+        }
+        if let Some(query) = query {
+            params.push((self.user_query_param(), query));
+        }
+
+        let request = self
+            .request(Method::GET, &url)
+            .query(&params)
+            .query(&[("maxResults", max_results)]);
+        let response = self.send(request).await?;
+        self.decode(response).await
+    }
+
+    /// The user the client is authenticated as.
+    pub async fn get_myself(&self) -> Result<User> {
+        let url = self.api_url("myself");
+
+        self.get_json(&url).await
+    }
+
+    /// Resolve an account ID, username, email address, display name or "me"
+    /// to the identifier used in user fields. Results are cached per input.
+    pub async fn resolve_user(&self, input: &str) -> anyhow::Result<String> {
+        let input = input.trim();
+        if self.deployment == Deployment::Cloud && looks_like_account_id(input) {
+            return Ok(input.to_string());
+        }
+
+        let cache_key = input.to_lowercase();
+        if let Some(id) = self.user_ids.read().await.get(&cache_key) {
+            return Ok(id.clone());
+        }
+
+        let user = if cache_key == "me" {
+            self.get_myself()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to look up the current user: {}", e))?
+        } else {
+            let users = self
+                .search_users(input, USER_LOOKUP_LIMIT)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to look up user '{}': {}", input, e))?;
+            pick_user(input, users)?
+        };
+        let id = user
+            .id()
+            .ok_or_else(|| anyhow::anyhow!("User '{}' has no account ID", user.display_name))?
+            .to_string();
+
+        self.user_ids.write().await.insert(cache_key, id.clone());
+        Ok(id)
+    }
+
+    /// Resolve the users named in a value for a user field (or array of users)
+    /// with [`resolve_user`](Self::resolve_user). Arrays may also be given as
+    /// comma-separated text; objects are passed through unchanged.
+    pub async fn resolve_user_values(
+        &self,
+        field: &Field,
+        value: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        use serde_json::Value;
+
+        let is_array = field
+            .schema
+            .as_ref()
+            .is_some_and(|s| s.field_type == "array");
+        let items = match value {
+            Value::String(text) if !is_array => {
+                return self
+                    .resolve_field_user(field, &text)
+                    .await
+                    .map(Value::String);
+            }
+            Value::String(text) => text
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+            Value::Array(items) => items,
+            other => return Ok(other),
+        };
+
+        let mut resolved = Vec::with_capacity(items.len());
+        for item in items {
+            resolved.push(match item {
+                Value::String(text) => Value::String(self.resolve_field_user(field, &text).await?),
+                other => other,
+            });
+        }
+        Ok(Value::Array(resolved))
+    }
+
+    async fn resolve_field_user(&self, field: &Field, input: &str) -> anyhow::Result<String> {
+        self.resolve_user(input)
+            .await
+            .map_err(|e| anyhow::anyhow!("Field '{}': {}", field.name, e))
+    }
+
+    /// Cloud searches by `query`, Data Center by `username` (which also
+    /// matches display names and email addresses).
+    fn user_query_param(&self) -> &'static str {
+        match self.deployment {
+            Deployment::Cloud => "query",
+            Deployment::DataCenter => "username",
+        }
+    }
+}
+
+/// Cloud account IDs are either 24 hex digits or `<number>:<uuid>`.
+fn looks_like_account_id(input: &str) -> bool {
+    let legacy = input.len() == 24 && input.chars().all(|c| c.is_ascii_hexdigit());
+    let prefixed = input.split_once(':').is_some_and(|(prefix, rest)| {
+        !prefix.is_empty()
+            && prefix.chars().all(|c| c.is_ascii_digit())
+            && !rest.is_empty()
+            && !rest.contains(char::is_whitespace)
+    });
+    legacy || prefixed
+}
+
+/// Pick the user meant by `input` from search results: an exact match on
+/// email, display name, username or account ID, or else the only result.
+fn pick_user(input: &str, users: Vec<User>) -> anyhow::Result<User> {
+    let is_exact = |user: &User| {
+        [
+            user.email_address.as_deref(),
+            Some(user.display_name.as_str()),
+            user.name.as_deref(),
+            user.account_id.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|value| value.eq_ignore_ascii_case(input))
+    };
+
+    let (exact, partial): (Vec<User>, Vec<User>) = users.into_iter().partition(is_exact);
+    let mut candidates = if exact.is_empty() { partial } else { exact };
+    match candidates.len() {
+        0 => anyhow::bail!(
+            "No user found matching '{}'. Use search_users to find the user.",
+            input
+        ),
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            let names: Vec<String> = candidates.iter().map(describe_user).collect();
+            anyhow::bail!(
+                "'{}' matches several users: {}. Use an email address or account ID.",
+                input,
+                names.join("; ")
+            )
+        }
+    }
+}
+
+/// "Name <email> (id)" for ambiguity errors.
+fn describe_user(user: &User) -> String {
+    let mut description = user.display_name.clone();
+    if let Some(email) = &user.email_address {
+        description.push_str(&format!(" <{}>", email));
+    }
+    if let Some(id) = user.id() {
+        description.push_str(&format!(" ({})", id));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn user(name: &str, email: &str, account_id: &str) -> User {
+        User {
+            display_name: name.to_string(),
+            email_address: Some(email.to_string()),
+            account_id: Some(account_id.to_string()),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn account_ids_are_recognised() {
+        assert!(looks_like_account_id("5b10ac8d82e05b22cc7d4ef5"));
+        assert!(looks_like_account_id(
+            "557058:f58131cb-b67d-43c7-b30d-6b58d40bd077"
+        ));
+        assert!(!looks_like_account_id("alice@example.com"));
+        assert!(!looks_like_account_id("Alice Smith"));
+        assert!(!looks_like_account_id("me"));
+    }
+
+    #[test]
+    fn pick_user_prefers_exact_matches() {
+        let users = vec![
+            user("Alex Kim", "alex.kim@example.com", "a1"),
+            user("Alex", "alex@example.com", "a2"),
+        ];
+
+        assert_eq!(
+            pick_user("ALEX@example.com", users)
+                .unwrap()
+                .account_id
+                .unwrap(),
+            "a2"
+        );
+    }
+
+    #[test]
+    fn pick_user_lists_candidates_when_ambiguous() {
+        let users = vec![
+            user("Alex Kim", "alex.kim@example.com", "a1"),
+            user("Alex Ray", "alex.ray@example.com", "a2"),
+        ];
+
+        let error = pick_user("alex", users).unwrap_err().to_string();
+
+        assert_eq!(
+            error,
+            "'alex' matches several users: Alex Kim <alex.kim@example.com> (a1); \
+             Alex Ray <alex.ray@example.com> (a2). Use an email address or account ID."
+        );
+        assert!(pick_user("nobody", Vec::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn resolve_user_looks_up_emails_once() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/user/search"))
+            .and(query_param("query", "alice@example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "accountId": "abc-123", "displayName": "Alice", "emailAddress": "alice@example.com" }
+            ])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        assert_eq!(
+            client.resolve_user("alice@example.com").await.unwrap(),
+            "abc-123"
+        );
+        assert_eq!(
+            client.resolve_user("Alice@Example.com").await.unwrap(),
+            "abc-123"
+        );
+    }
+
+    #[tokio::test]
+    async fn user_field_values_are_resolved_like_assignees() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/user/search"))
+            .and(query_param("query", "bob@example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "accountId": "b-1", "displayName": "Bob", "emailAddress": "bob@example.com" }
+            ])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/myself"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "accountId": "a-1", "displayName": "Alice"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+        let field = |schema: serde_json::Value| -> Field {
+            serde_json::from_value(serde_json::json!({
+                "id": "customfield_3", "name": "Reviewers", "custom": true, "schema": schema
+            }))
+            .unwrap()
+        };
+        let reviewer = field(serde_json::json!({ "type": "user" }));
+        let reviewers = field(serde_json::json!({ "type": "array", "items": "user" }));
+
+        assert_eq!(
+            client
+                .resolve_user_values(&reviewer, serde_json::json!("me"))
+                .await
+                .unwrap(),
+            serde_json::json!("a-1")
+        );
+        assert_eq!(
+            client
+                .resolve_user_values(&reviewers, serde_json::json!("me, bob@example.com"))
+                .await
+                .unwrap(),
+            serde_json::json!(["a-1", "b-1"])
+        );
+        assert_eq!(
+            client
+                .resolve_user_values(&reviewers, serde_json::json!([{ "accountId": "c-1" }]))
+                .await
+                .unwrap(),
+            serde_json::json!([{ "accountId": "c-1" }])
+        );
+    }
+
+    #[tokio::test]
+    async fn data_center_resolves_me_to_username() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/myself"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "jdoe", "key": "JIRAUSER10000", "displayName": "John Doe"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::with_personal_access_token(&mock_server.uri(), "pat");
+
+        assert_eq!(client.resolve_user("me").await.unwrap(), "jdoe");
+    }
+
+    #[tokio::test]
+    async fn find_assignable_users_filters_by_project() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/user/assignable/search"))
+            .and(query_param("project", "PROJ"))
+            .and(query_param("query", "bo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "accountId": "b-1", "displayName": "Bob" }
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let users = client
+            .find_assignable_users(Some("PROJ"), None, Some("bo"), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].display_name, "Bob");
+    }
+}
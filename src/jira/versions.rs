@@ -0,0 +1,318 @@
+//! Versions (releases): listing, creating, releasing and archiving them, and
+//! the issues they contain.
+
+use reqwest::Method;
+
+use super::error::Result;
+use super::{
+    FieldOperation, JiraClient, SearchResult, UpdateIssueRequest, Version, VersionProgress,
+    VersionRequest,
+};
+
+/// Most issues included in release notes
+pub const RELEASE_NOTES_LIMIT: u32 = 10_000;
+
+/// Issue fields release notes are written from
+const RELEASE_NOTES_FIELDS: &[&str] = &[
+    "summary",
+    "status",
+    "assignee",
+    "issuetype",
+    "components",
+    "labels",
+];
+
+impl JiraClient {
+    /// All versions of a project, including released and archived ones.
+    pub async fn get_project_versions(&self, project_key: &str) -> Result<Vec<Version>> {
+        let url = self.api_url(&format!("project/{}/versions", project_key));
+
+        self.get_json(&url).await
+    }
+
+    pub async fn get_version(&self, version_id: &str) -> Result<Version> {
+        let url = self.api_url(&format!("version/{}", version_id));
+
+        self.get_json(&url).await
+    }
+
+    pub async fn create_version(&self, request: VersionRequest) -> Result<Version> {
+        let url = self.api_url("version");
+
+        self.send_json(Method::POST, &url, &request).await
+    }
+
+    /// Change a version's name, dates or description, or release or archive
+    /// it. Only the fields set in `request` are changed.
+    pub async fn update_version(
+        &self,
+        version_id: &str,
+        request: VersionRequest,
+    ) -> Result<Version> {
+        let url = self.api_url(&format!("version/{}", version_id));
+
+        self.send_json(Method::PUT, &url, &request).await
+    }
+
+    /// Up to `limit` issues whose fix version is `version_id`.
+    pub async fn get_version_issues(&self, version_id: &str, limit: u32) -> Result<SearchResult> {
+        let jql = format!("fixVersion = {} ORDER BY key", version_id);
+
+        self.search_all_issues(&jql, limit, None).await
+    }
+
+    /// Progress over every issue whose fix version is `version_id`, paging
+    /// through them with only their status.
+    pub async fn get_version_progress(&self, version_id: &str) -> Result<VersionProgress> {
+        let jql = format!("fixVersion = {}", version_id);
+        let result = self
+            .search_all_issues_with_fields(&jql, u32::MAX, None, &["status"])
+            .await?;
+
+        Ok(VersionProgress::from_issues(&result.issues))
+    }
+
+    /// Issues matching `jql` with the fields release notes need, paging
+    /// through up to [`RELEASE_NOTES_LIMIT`] of them.
+    pub async fn get_release_notes_issues(&self, jql: &str) -> Result<SearchResult> {
+        self.search_all_issues_with_fields(jql, RELEASE_NOTES_LIMIT, None, RELEASE_NOTES_FIELDS)
+            .await
+    }
+
+    /// Move the unresolved issues of `from` to `to`, keeping their other fix
+    /// versions. The key of each moved issue is pushed to `moved` as soon as
+    /// it is updated, so callers can report what changed if a later update
+    /// fails.
+    pub async fn move_unresolved_issues(
+        &self,
+        from: &Version,
+        to: &Version,
+        moved: &mut Vec<String>,
+    ) -> Result<()> {
+        let jql = format!(
+            "fixVersion = {} AND resolution = Unresolved ORDER BY key",
+            from.id
+        );
+        // Collect every key before changing anything: moved issues drop out
+        // of the query, which would throw off paging.
+        let result = self.search_all_issues(&jql, u32::MAX, None).await?;
+
+        for issue in result.issues {
+            let update = UpdateIssueRequest::new()
+                .fix_versions(FieldOperation::Remove, &[&from.name])
+                .fix_versions(FieldOperation::Add, &[&to.name]);
+            self.update_issue(&issue.key, update).await?;
+            moved.push(issue.key);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn version(id: &str, name: &str) -> Version {
+        serde_json::from_value(serde_json::json!({ "id": id, "name": name })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn update_version_sends_only_changed_fields() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/version/10001"))
+            .and(body_json(
+                serde_json::json!({ "releaseDate": "2025-03-01", "released": true }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "10001", "name": "1.0", "released": true, "releaseDate": "2025-03-01"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let version = client
+            .update_version("10001", VersionRequest::release("2025-03-01"))
+            .await
+            .unwrap();
+
+        assert!(version.released);
+    }
+
+    #[tokio::test]
+    async fn move_unresolved_issues_swaps_fix_versions() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(body_partial_json(serde_json::json!({
+                "jql": "fixVersion = 1 AND resolution = Unresolved ORDER BY key"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [{
+                    "id": "1",
+                    "key": "PROJ-1",
+                    "self": "https://example.atlassian.net/rest/api/3/issue/1",
+                    "fields": {}
+                }],
+                "isLast": true
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .and(body_json(serde_json::json!({
+                "fields": {},
+                "update": {
+                    "fixVersions": [{ "remove": { "name": "1.0" } }, { "add": { "name": "1.1" } }]
+                }
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let mut moved = Vec::new();
+        client
+            .move_unresolved_issues(&version("1", "1.0"), &version("2", "1.1"), &mut moved)
+            .await
+            .unwrap();
+
+        assert_eq!(moved, vec!["PROJ-1"]);
+    }
+
+    #[tokio::test]
+    async fn move_unresolved_issues_keeps_keys_moved_before_a_failure() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [
+                    { "id": "1", "key": "PROJ-1", "self": "", "fields": {} },
+                    { "id": "2", "key": "PROJ-2", "self": "", "fields": {} },
+                    { "id": "3", "key": "PROJ-3", "self": "", "fields": {} }
+                ],
+                "isLast": true
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/issue/PROJ-1"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/rest/api/3/issue/PROJ-2"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "errors": { "fixVersions": "Version 1.1 is archived" }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let mut moved = Vec::new();
+        let result = client
+            .move_unresolved_issues(&version("1", "1.0"), &version("2", "1.1"), &mut moved)
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(moved, vec!["PROJ-1"]);
+    }
+
+    #[tokio::test]
+    async fn release_notes_issues_request_labels_and_components() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(body_partial_json(serde_json::json!({
+                "jql": "fixVersion = 10001",
+                "fields": ["summary", "status", "assignee", "issuetype", "components", "labels"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [{
+                    "id": "1", "key": "PROJ-1", "self": "",
+                    "fields": { "labels": ["internal"], "components": [{ "id": "5", "name": "API" }] }
+                }],
+                "isLast": true
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let result = client
+            .get_release_notes_issues("fixVersion = 10001")
+            .await
+            .unwrap();
+
+        let fields = &result.issues[0].fields;
+        assert_eq!(
+            fields.labels.as_deref(),
+            Some(&["internal".to_string()][..])
+        );
+        assert_eq!(fields.components.as_ref().unwrap()[0].name, "API");
+    }
+
+    #[tokio::test]
+    async fn version_progress_counts_every_page() {
+        let mock_server = MockServer::start().await;
+        let issue = |key: &str, category: &str| {
+            serde_json::json!({
+                "id": key, "key": key, "self": "",
+                "fields": { "status": { "name": category, "statusCategory": { "key": category, "name": category } } }
+            })
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(body_partial_json(
+                serde_json::json!({ "nextPageToken": "page-2" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [issue("PROJ-3", "new")],
+                "isLast": true
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(body_partial_json(serde_json::json!({
+                "jql": "fixVersion = 10001",
+                "fields": ["status"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [issue("PROJ-1", "done"), issue("PROJ-2", "indeterminate")],
+                "nextPageToken": "page-2",
+                "isLast": false
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = JiraClient::new(&mock_server.uri(), "test@example.com", "test-token");
+
+        let progress = client.get_version_progress("10001").await.unwrap();
+
+        assert_eq!(
+            progress,
+            VersionProgress {
+                done: 1,
+                in_progress: 1,
+                to_do: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,608 @@
+//! Conversion between Markdown and Jira wiki markup, the text format used by
+//! Jira Data Center / Server (REST API v2) for descriptions and comments.
+//!
+//! Reference: https://jira.atlassian.com/secure/WikiRendererHelpAction.jspa?section=all
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// Convert Markdown text to Jira wiki markup.
+///
+/// Task list checkboxes have no wiki equivalent and are written as ☑ / ☐.
+pub fn markdown_to_wiki(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut out = String::new();
+    let mut list_markers: Vec<char> = Vec::new();
+    let mut link_urls: Vec<String> = Vec::new();
+    let mut in_code_block = false;
+    let mut in_table = false;
+    let mut in_table_head = false;
+    let mut in_image = false;
+    // The first block inside a quote follows `{quote}` without a blank line
+    let mut quote_opened = false;
+
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::Paragraph) if list_markers.is_empty() && !in_table => {
+                block_break(&mut out, &mut quote_opened);
+            }
+            Event::End(TagEnd::Paragraph) if list_markers.is_empty() && !in_table => {
+                out.push('\n');
+            }
+            Event::Start(Tag::Heading { level, .. }) => {
+                block_break(&mut out, &mut quote_opened);
+                let level = match level {
+                    HeadingLevel::H1 => 1,
+                    HeadingLevel::H2 => 2,
+                    HeadingLevel::H3 => 3,
+                    HeadingLevel::H4 => 4,
+                    HeadingLevel::H5 => 5,
+                    HeadingLevel::H6 => 6,
+                };
+                out.push_str(&format!("h{}. ", level));
+            }
+            Event::End(TagEnd::Heading(_)) => out.push('\n'),
+            Event::Start(Tag::List(start)) => {
+                if list_markers.is_empty() {
+                    block_break(&mut out, &mut quote_opened);
+                }
+                list_markers.push(if start.is_some() { '#' } else { '*' });
+            }
+            Event::End(TagEnd::List(_)) => {
+                list_markers.pop();
+                line_break(&mut out);
+            }
+            Event::Start(Tag::Item) => {
+                line_break(&mut out);
+                out.extend(list_markers.iter());
+                out.push(' ');
+            }
+            Event::TaskListMarker(checked) => out.push_str(if checked { "☑ " } else { "☐ " }),
+            Event::Start(Tag::BlockQuote(_)) => {
+                block_break(&mut out, &mut quote_opened);
+                out.push_str("{quote}\n");
+                quote_opened = true;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                line_break(&mut out);
+                out.push_str("{quote}\n");
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                block_break(&mut out, &mut quote_opened);
+                let language = match &kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next(),
+                    CodeBlockKind::Indented => None,
+                };
+                match language {
+                    Some(language) => out.push_str(&format!("{{code:{}}}\n", language)),
+                    None => out.push_str("{code}\n"),
+                }
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                line_break(&mut out);
+                out.push_str("{code}\n");
+                in_code_block = false;
+            }
+            Event::Start(Tag::Table(_)) => {
+                block_break(&mut out, &mut quote_opened);
+                in_table = true;
+            }
+            Event::End(TagEnd::Table) => in_table = false,
+            Event::Start(Tag::TableHead) => in_table_head = true,
+            Event::End(TagEnd::TableHead) => {
+                out.push_str("||\n");
+                in_table_head = false;
+            }
+            Event::Start(Tag::TableCell) => out.push_str(if in_table_head { "||" } else { "|" }),
+            Event::End(TagEnd::TableRow) => out.push_str("|\n"),
+            Event::Rule => {
+                block_break(&mut out, &mut quote_opened);
+                out.push_str("----\n");
+            }
+            Event::Start(Tag::Strong) | Event::End(TagEnd::Strong) => out.push('*'),
+            Event::Start(Tag::Emphasis) | Event::End(TagEnd::Emphasis) => out.push('_'),
+            Event::Start(Tag::Strikethrough) | Event::End(TagEnd::Strikethrough) => out.push('-'),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                out.push('[');
+                link_urls.push(dest_url.to_string());
+            }
+            Event::End(TagEnd::Link) => {
+                let url = link_urls.pop().unwrap_or_default();
+                out.push_str(&format!("|{}]", url));
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                out.push_str(&format!("!{}", dest_url));
+                in_image = true;
+            }
+            Event::End(TagEnd::Image) => {
+                out.push('!');
+                in_image = false;
+            }
+            Event::Code(text) => out.push_str(&format!("{{{{{}}}}}", text)),
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                if in_code_block {
+                    out.push_str(&text);
+                } else if !in_image {
+                    let escaped = escape_wiki(&text, in_table, out.chars().next_back());
+                    out.push_str(&escaped);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if list_markers.is_empty() && !in_table {
+                    out.push('\n');
+                } else {
+                    out.push_str(" \\\\ ");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Start a new block, separated from the previous one by a blank line.
+fn block_break(out: &mut String, quote_opened: &mut bool) {
+    if std::mem::take(quote_opened) || out.is_empty() {
+        return;
+    }
+    line_break(out);
+    if !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn line_break(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Escape characters that wiki markup would read as markup: macro and link
+/// openers, table pipes, and text effect markers (`*bold*`, `_em_`, `-del-`,
+/// `+ins+`, `^sup^`, `~sub~`) unless they sit inside a word like `snake_case`.
+/// `previous` is the character written before `text`.
+fn escape_wiki(text: &str, in_table: bool, previous: Option<char>) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut escaped = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let before = if i == 0 { previous } else { Some(chars[i - 1]) };
+        let inside_word = before.is_some_and(char::is_alphanumeric)
+            && chars.get(i + 1).is_some_and(|n| n.is_alphanumeric());
+        let is_effect = matches!(c, '*' | '_' | '-' | '+' | '^' | '~') && !inside_word;
+        if matches!(c, '{' | '[') || (in_table && c == '|') || is_effect {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Convert Jira wiki markup to Markdown.
+///
+/// Handles headings, lists (with nesting), code/noformat blocks, quotes, tables,
+/// rules, text effects, links, mentions and images; anything else is passed
+/// through as text.
+pub fn wiki_to_markdown(wiki: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut code_block: Option<&str> = None;
+    let mut in_quote = false;
+    let mut list_counters: Vec<u32> = Vec::new();
+
+    for raw_line in wiki.lines() {
+        let line = raw_line.trim_end();
+
+        if let Some(end_tag) = code_block {
+            if let Some(rest) = line.strip_suffix(end_tag) {
+                if !rest.is_empty() {
+                    lines.push(rest.to_string());
+                }
+                lines.push("```".to_string());
+                code_block = None;
+            } else {
+                lines.push(raw_line.to_string());
+            }
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+
+        if let Some((language, end_tag)) = parse_code_start(trimmed) {
+            lines.push(format!("```{}", language));
+            code_block = Some(end_tag);
+            continue;
+        }
+        if trimmed == "{quote}" {
+            in_quote = !in_quote;
+            continue;
+        }
+
+        let converted = match convert_list_line(trimmed, &mut list_counters) {
+            Some(item) => item,
+            None => {
+                list_counters.clear();
+                convert_wiki_line(trimmed)
+            }
+        };
+        if in_quote {
+            lines.push(if converted.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {}", converted)
+            });
+        } else {
+            lines.push(converted);
+        }
+    }
+
+    // Tables need a delimiter row after the header
+    let mut output: Vec<String> = Vec::new();
+    for line in lines {
+        let header_cells = line
+            .strip_prefix("<<th>>")
+            .map(|cells| cells.matches(" | ").count() + 1);
+        match header_cells {
+            Some(count) => {
+                output.push(line.replacen("<<th>>", "", 1));
+                output.push(format!("|{}", " --- |".repeat(count)));
+            }
+            None => output.push(line),
+        }
+    }
+
+    output.join("\n").trim().to_string()
+}
+
+/// Detect `{code}`, `{code:lang}` and `{noformat}` openers, returning the
+/// Markdown fence language and the closing tag.
+fn parse_code_start(line: &str) -> Option<(&str, &'static str)> {
+    if line == "{noformat}" {
+        return Some(("", "{noformat}"));
+    }
+    let rest = line.strip_prefix("{code")?.strip_suffix('}')?;
+    if rest.is_empty() {
+        return Some(("", "{code}"));
+    }
+    let params = rest.strip_prefix(':')?;
+    // Parameters may be `java` or `title=Foo|language=java`
+    let language = params
+        .split('|')
+        .find_map(|p| p.strip_prefix("language="))
+        .or_else(|| params.split('|').find(|p| !p.contains('=')))
+        .unwrap_or("");
+    Some((language, "{code}"))
+}
+
+fn convert_wiki_line(line: &str) -> String {
+    if line.is_empty() {
+        return String::new();
+    }
+
+    if let Some((level, text)) = line
+        .strip_prefix('h')
+        .and_then(|rest| rest.split_once(". "))
+        .and_then(|(level, text)| Some((level.parse::<usize>().ok()?, text)))
+        .filter(|(level, _)| (1..=6).contains(level))
+    {
+        return format!("{} {}", "#".repeat(level), convert_inline(text));
+    }
+
+    if let Some(text) = line.strip_prefix("bq. ") {
+        return format!("> {}", convert_inline(text));
+    }
+
+    if line.len() >= 4 && line.chars().all(|c| c == '-') {
+        return "---".to_string();
+    }
+
+    if let Some(cells) = line.strip_prefix("||") {
+        let cells = cells.trim_end_matches('|');
+        let cells: Vec<String> = cells
+            .split("||")
+            .map(|c| convert_inline(c.trim()))
+            .collect();
+        return format!("<<th>>| {} |", cells.join(" | "));
+    }
+    if line.starts_with('|') {
+        let cells = split_table_cells(line);
+        let cells: Vec<String> = cells.iter().map(|c| convert_inline(c.trim())).collect();
+        return format!("| {} |", cells.join(" | "));
+    }
+
+    convert_inline(line)
+}
+
+/// Convert a `*`/`#` list line, numbering ordered items per nesting level.
+fn convert_list_line(line: &str, counters: &mut Vec<u32>) -> Option<String> {
+    let markers: String = line
+        .chars()
+        .take_while(|c| matches!(c, '*' | '#' | '-'))
+        .collect();
+    let is_list = !markers.is_empty()
+        && line[markers.len()..].starts_with(' ')
+        && (markers.len() == 1 || !markers.contains('-'));
+    if !is_list {
+        return None;
+    }
+
+    let depth = markers.chars().count();
+    counters.truncate(depth);
+    counters.resize(depth, 0);
+
+    let mut indent = String::new();
+    for parent in markers.chars().take(depth - 1) {
+        indent.push_str(if parent == '#' { "   " } else { "  " });
+    }
+    let marker = if markers.ends_with('#') {
+        counters[depth - 1] += 1;
+        format!("{}. ", counters[depth - 1])
+    } else {
+        "- ".to_string()
+    };
+
+    let text = line[markers.len()..].trim_start();
+    let text = if let Some(rest) = text.strip_prefix("☑ ") {
+        format!("[x] {}", convert_inline(rest))
+    } else if let Some(rest) = text.strip_prefix("☐ ") {
+        format!("[ ] {}", convert_inline(rest))
+    } else {
+        convert_inline(text)
+    };
+
+    Some(format!("{}{}{}", indent, marker, text))
+}
+
+/// Split a table row on `|`, ignoring pipes inside `[links|...]` and escaped pipes.
+fn split_table_cells(line: &str) -> Vec<String> {
+    let inner = line.trim_start_matches('|').trim_end_matches('|');
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '[' => {
+                depth += 1;
+                current.push(c);
+            }
+            ']' => {
+                depth -= 1;
+                current.push(c);
+            }
+            '|' if depth <= 0 => cells.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    cells.push(current);
+    cells
+}
+
+/// Convert inline wiki effects (`*bold*`, `_em_`, `-strike-`, `{{code}}`,
+/// `[text|url]`, `[~user]`, `!image!`) to Markdown.
+fn convert_inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '\\' && i + 1 < chars.len() {
+            if chars[i + 1] == '\\' {
+                out.push('\n');
+            } else {
+                // Markdown reads these as emphasis too, so keep them escaped
+                if matches!(chars[i + 1], '*' | '_' | '~') {
+                    out.push('\\');
+                }
+                out.push(chars[i + 1]);
+            }
+            i += 2;
+            continue;
+        }
+
+        if c == '{'
+            && chars.get(i + 1) == Some(&'{')
+            && let Some(end) = find_seq(&chars, i + 2, &['}', '}'])
+        {
+            let code: String = chars[i + 2..end].iter().collect();
+            out.push_str(&format!("`{}`", code));
+            i = end + 2;
+            continue;
+        }
+
+        if c == '['
+            && let Some(end) = find_char(&chars, i + 1, ']')
+        {
+            let inner: String = chars[i + 1..end].iter().collect();
+            out.push_str(&convert_link(&inner));
+            i = end + 1;
+            continue;
+        }
+
+        if c == '!'
+            && let Some(end) = find_char(&chars, i + 1, '!')
+        {
+            let inner: String = chars[i + 1..end].iter().collect();
+            if !inner.is_empty() && !inner.contains(' ') && inner.contains('.') {
+                let name = inner.split('|').next().unwrap_or(&inner);
+                out.push_str(&format!("[media: {}]", name));
+                i = end + 1;
+                continue;
+            }
+        }
+
+        if let Some(wrap) = match c {
+            '*' => Some("**"),
+            '_' => Some("*"),
+            '-' => Some("~~"),
+            _ => None,
+        } {
+            let opens = (i == 0 || !chars[i - 1].is_alphanumeric())
+                && chars
+                    .get(i + 1)
+                    .is_some_and(|n| !n.is_whitespace() && *n != c);
+            if opens && let Some(end) = find_closing(&chars, i + 1, c) {
+                let inner: String = chars[i + 1..end].iter().collect();
+                out.push_str(&format!("{}{}{}", wrap, convert_inline(&inner), wrap));
+                i = end + 1;
+                continue;
+            }
+        }
+
+        out.push(c);
+        i += 1;
+    }
+
+    out
+}
+
+fn convert_link(inner: &str) -> String {
+    if let Some(user) = inner.strip_prefix('~') {
+        return format!("@{}", user);
+    }
+    match inner.rsplit_once('|') {
+        Some((text, url)) => format!("[{}]({})", convert_inline(text), url),
+        None if inner.contains("://") || inner.starts_with("mailto:") => format!("<{}>", inner),
+        None => format!("[{}]", inner),
+    }
+}
+
+fn find_char(chars: &[char], from: usize, target: char) -> Option<usize> {
+    (from..chars.len()).find(|&j| chars[j] == target)
+}
+
+fn find_seq(chars: &[char], from: usize, seq: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&j| chars[j..].starts_with(seq))
+}
+
+/// Find a closing effect marker: preceded by non-whitespace and not followed
+/// by an alphanumeric character.
+fn find_closing(chars: &[char], from: usize, marker: char) -> Option<usize> {
+    (from + 1..chars.len()).find(|&j| {
+        chars[j] == marker
+            && !chars[j - 1].is_whitespace()
+            && chars.get(j + 1).is_none_or(|n| !n.is_alphanumeric())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_markdown_headings_and_marks() {
+        let wiki = markdown_to_wiki("## Title\n\n**bold** *em* ~~old~~ `code`");
+        assert_eq!(wiki, "h2. Title\n\n*bold* _em_ -old- {{code}}");
+    }
+
+    #[test]
+    fn converts_markdown_nested_lists() {
+        let wiki = markdown_to_wiki("- one\n  1. nested\n- two\n- [x] done");
+        assert_eq!(wiki, "* one\n*# nested\n* two\n* ☑ done");
+    }
+
+    #[test]
+    fn converts_markdown_links_code_blocks_and_quotes() {
+        let wiki = markdown_to_wiki(
+            "see [docs](https://example.com)\n\n```rust\nfn main() {}\n```\n\n> quoted",
+        );
+        assert_eq!(
+            wiki,
+            "see [docs|https://example.com]\n\n{code:rust}\nfn main() {}\n{code}\n\n{quote}\nquoted\n{quote}"
+        );
+    }
+
+    #[test]
+    fn converts_markdown_tables_and_rules() {
+        let wiki = markdown_to_wiki("| A | B |\n| --- | --- |\n| 1 | 2 |\n\n---");
+        assert_eq!(wiki, "||A||B||\n|1|2|\n\n----");
+    }
+
+    #[test]
+    fn escapes_wiki_special_characters_in_text() {
+        assert_eq!(
+            markdown_to_wiki("use {braces} and [brackets]"),
+            "use \\{braces} and \\[brackets]"
+        );
+    }
+
+    #[test]
+    fn escapes_literal_text_effect_markers() {
+        assert_eq!(markdown_to_wiki("2 * 3 * 4"), "2 \\* 3 \\* 4");
+        assert_eq!(
+            markdown_to_wiki("\\*not bold\\* and -1"),
+            "\\*not bold\\* and \\-1"
+        );
+        assert_eq!(
+            markdown_to_wiki("snake_case and 2025-01-31"),
+            "snake_case and 2025-01-31"
+        );
+    }
+
+    #[test]
+    fn converts_wiki_headings_and_effects() {
+        let md = wiki_to_markdown("h1. Title\n\n*bold* _em_ -old- {{code}} and a-b-c");
+        assert_eq!(md, "# Title\n\n**bold** *em* ~~old~~ `code` and a-b-c");
+    }
+
+    #[test]
+    fn converts_wiki_lists() {
+        let md = wiki_to_markdown("* one\n*# nested\n*# again\n* two\n\n# first\n# second");
+        assert_eq!(
+            md,
+            "- one\n  1. nested\n  2. again\n- two\n\n1. first\n2. second"
+        );
+    }
+
+    #[test]
+    fn converts_wiki_links_mentions_and_images() {
+        let md = wiki_to_markdown("[docs|https://example.com] [~jsmith] [https://a.io] !shot.png!");
+        assert_eq!(
+            md,
+            "[docs](https://example.com) @jsmith <https://a.io> [media: shot.png]"
+        );
+    }
+
+    #[test]
+    fn converts_wiki_code_quote_and_table() {
+        let md = wiki_to_markdown(
+            "{code:java}\nint x = 1;\n{code}\n{quote}\nquoted\n{quote}\n||A||B||\n|1|[x|http://x.io]|",
+        );
+        assert_eq!(
+            md,
+            "```java\nint x = 1;\n```\n> quoted\n| A | B |\n| --- | --- |\n| 1 | [x](http://x.io) |"
+        );
+    }
+
+    #[test]
+    fn round_trips_markdown_through_wiki() {
+        let cases = [
+            "## Overview\n\nsome text",
+            "**bold** and *italic* text",
+            "use `cargo build` to compile",
+            "- item one\n- item two",
+            "1. first\n2. second",
+            "- parent\n  - child\n- sibling",
+            "> quoted",
+            "see [the docs](https://example.com) and ~~old~~ text",
+            "```rust\nfn main() {}\n```",
+            "above\n\n---\n\nbelow",
+            "| Name | Value |\n| --- | --- |\n| a | 1 |",
+            "- [x] done\n- [ ] todo",
+            "\\*not bold\\* and \\_not em\\_ in snake_case",
+        ];
+
+        for markdown in cases {
+            assert_eq!(wiki_to_markdown(&markdown_to_wiki(markdown)), markdown);
+        }
+    }
+}
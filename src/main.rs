@@ -2,10 +2,13 @@ mod jira;
 mod server;
 mod tools;
 
-use anyhow::Result;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
 use rmcp::{transport::stdio, ServiceExt};
 
-use jira::JiraClient;
+use jira::oauth::{self, OAuthConfig, OAuthSession};
+use jira::{Deployment, JiraClient, RetryPolicy};
 use server::JiraServer;
 
 #[tokio::main]
@@ -18,14 +21,17 @@ async fn main() -> Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
-    let base_url =
-        std::env::var("JIRA_BASE_URL").expect("JIRA_BASE_URL environment variable is required");
-    let email = std::env::var("JIRA_EMAIL").expect("JIRA_EMAIL environment variable is required");
-    let api_token =
-        std::env::var("JIRA_API_TOKEN").expect("JIRA_API_TOKEN environment variable is required");
+    if std::env::args().nth(1).as_deref() == Some("login") {
+        oauth::login(&OAuthConfig::from_env()?).await?;
+        return Ok(());
+    }
 
-    let jira = JiraClient::new(&base_url, &email, &api_token);
-    let server = JiraServer::new(jira);
+    let jira = if std::env::var("JIRA_AUTH").is_ok_and(|auth| auth.eq_ignore_ascii_case("oauth")) {
+        JiraClient::with_oauth(OAuthSession::load(OAuthConfig::from_env()?)?)
+    } else {
+        client_from_env()?
+    };
+    let server = JiraServer::new(configure_from_env(jira)?);
 
     tracing::info!("Starting Jira MCP server...");
 
@@ -34,3 +40,80 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Build a client from JIRA_BASE_URL and API token / PAT credentials.
+fn client_from_env() -> Result<JiraClient> {
+    let base_url =
+        std::env::var("JIRA_BASE_URL").context("JIRA_BASE_URL environment variable is required")?;
+    let deployment = match std::env::var("JIRA_DEPLOYMENT") {
+        Ok(name) => Some(
+            Deployment::from_name(&name)
+                .context("JIRA_DEPLOYMENT must be 'cloud' or 'datacenter' (alias: 'server')")?,
+        ),
+        Err(_) => None,
+    };
+
+    // A Personal Access Token only exists on Data Center, so it implies that
+    // deployment unless Cloud was asked for explicitly
+    if let Ok(token) = std::env::var("JIRA_PERSONAL_ACCESS_TOKEN") {
+        if deployment == Some(Deployment::Cloud) {
+            anyhow::bail!(
+                "JIRA_PERSONAL_ACCESS_TOKEN is for Jira Data Center / Server, but \
+                 JIRA_DEPLOYMENT is 'cloud': unset one of them"
+            );
+        }
+        return Ok(JiraClient::with_personal_access_token(&base_url, &token));
+    }
+
+    let email =
+        std::env::var("JIRA_EMAIL").context("JIRA_EMAIL environment variable is required")?;
+    let api_token = std::env::var("JIRA_API_TOKEN")
+        .context("JIRA_API_TOKEN environment variable is required")?;
+    Ok(JiraClient::new(&base_url, &email, &api_token)
+        .with_deployment(deployment.unwrap_or(Deployment::Cloud)))
+}
+
+/// Apply optional request settings: retry limits, timeout, User-Agent and
+/// response and upload size limits.
+fn configure_from_env(mut jira: JiraClient) -> Result<JiraClient> {
+    let mut policy = RetryPolicy::default();
+    if let Ok(retries) = std::env::var("JIRA_MAX_RETRIES") {
+        policy.max_retries = retries
+            .parse()
+            .context("JIRA_MAX_RETRIES must be a non-negative integer")?;
+    }
+    if let Ok(seconds) = std::env::var("JIRA_RETRY_MAX_DELAY_SECS") {
+        policy.max_delay = Duration::from_secs(
+            seconds
+                .parse()
+                .context("JIRA_RETRY_MAX_DELAY_SECS must be a number of seconds")?,
+        );
+    }
+    jira = jira.with_retry_policy(policy);
+
+    if let Ok(seconds) = std::env::var("JIRA_TIMEOUT_SECS") {
+        jira = jira.with_timeout(Duration::from_secs(
+            seconds
+                .parse()
+                .context("JIRA_TIMEOUT_SECS must be a number of seconds")?,
+        ));
+    }
+    if let Ok(user_agent) = std::env::var("JIRA_USER_AGENT") {
+        jira = jira.with_user_agent(&user_agent);
+    }
+    if let Ok(bytes) = std::env::var("JIRA_MAX_RESPONSE_BYTES") {
+        jira = jira.with_max_response_bytes(
+            bytes
+                .parse()
+                .context("JIRA_MAX_RESPONSE_BYTES must be a number of bytes")?,
+        );
+    }
+    if let Ok(bytes) = std::env::var("JIRA_MAX_UPLOAD_BYTES") {
+        jira = jira.with_max_upload_bytes(
+            bytes
+                .parse()
+                .context("JIRA_MAX_UPLOAD_BYTES must be a number of bytes")?,
+        );
+    }
+    Ok(jira)
+}
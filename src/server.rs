@@ -1,5 +1,8 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 
+use base64::{Engine, engine::general_purpose::STANDARD};
 use rmcp::{
     handler::server::router::tool::ToolRouter,
     handler::server::tool::Parameters,
@@ -7,17 +10,46 @@ use rmcp::{
     tool, tool_handler, tool_router,
     ErrorData as McpError,
 };
+use tokio::io::AsyncReadExt;
 
-use crate::jira::{CreateIssueRequest, JiraClient, UpdateIssueRequest};
+use crate::jira::{
+    AddCommentRequest, ChangelogFilter, CreateIssueRequest, EstimateAdjustment, Field,
+    FieldOperation, JiraClient, JiraError, LinkDirection, LinkIssuesRequest, Project,
+    RELEASE_NOTES_LIMIT, SprintRequest, TransitionIssueRequest, UpdateIssueRequest, Version,
+    VersionRequest, WorklogRequest, date_today, iso_timestamp_now, iso_timestamp_plus_days,
+    jira_timestamp_now, parse_duration, to_date, to_iso_timestamp, to_jira_timestamp,
+};
 use crate::tools::{
-    format_children, format_comment, format_comments, format_create_result, format_issue,
-    format_search_result, format_update_result, AddCommentParams, CreateIssueParams,
-    GetChildrenParams, GetCommentsParams, GetIssueParams, SearchIssuesParams, UpdateIssueParams,
+    AddAttachmentParams, AddCommentParams, AddWorklogParams, ArchiveVersionParams,
+    CloseSprintParams, Confirmations, CreateIssueParams, CreateSprintParams, CreateVersionParams,
+    DeleteCommentParams, DeleteIssueParams, DeleteWorklogParams, DownloadAttachmentParams,
+    FindAssignableUsersParams, GenerateReleaseNotesParams, GetBacklogParams, GetChildrenParams,
+    GetCommentParams, GetCommentsParams, GetCreateMetadataParams, GetIssueHistoryParams,
+    GetIssueParams, GetProjectParams, GetSprintParams, GetVersionParams, GroupBy, LinkIssuesParams,
+    ListAttachmentsParams, ListBoardsParams, ListFieldsParams, ListProjectsParams,
+    ListSprintsParams, ListTransitionsParams, ListVersionsParams, ListWorklogsParams,
+    MoveIssuesToBacklogParams, MoveIssuesToSprintParams, MoveVersionIssuesParams,
+    ReleaseNotesOptions, ReleaseVersionParams, RemoveIssueLinkParams, SearchIssuesParams,
+    SearchUsersParams, StartSprintParams, TransitionIssueParams, UpdateCommentParams,
+    UpdateIssueParams, UpdateWorklogParams, format_attachment_result, format_attachments,
+    format_backlog, format_boards, format_children, format_comment,
+    format_comment_deletion_preview, format_comment_details, format_comment_update_result,
+    format_comments, format_create_fields, format_create_issue_types, format_create_result,
+    format_error, format_fields, format_history, format_issue, format_issue_deletion_preview,
+    format_link_types, format_project, format_projects, format_search_result, format_size,
+    format_sprint, format_sprint_result, format_sprints, format_transition_result,
+    format_transitions, format_update_result, format_user, format_users, format_version,
+    format_version_result, format_versions, format_worklog_result, format_worklogs,
+    render_release_notes,
 };
 
+/// Length of a sprint started without an end date, as in Jira's UI
+const DEFAULT_SPRINT_DAYS: u64 = 14;
+
 #[derive(Clone)]
 pub struct JiraServer {
     jira: Arc<JiraClient>,
+    confirmations: Arc<Confirmations>,
     tool_router: ToolRouter<Self>,
 }
 
@@ -26,11 +58,14 @@ impl JiraServer {
     pub fn new(jira: JiraClient) -> Self {
         Self {
             jira: Arc::new(jira),
+            confirmations: Arc::default(),
             tool_router: Self::tool_router(),
         }
     }
 
-    #[tool(description = "Create a new Jira issue. Requires project key, summary, and issue type. Optionally supports description, priority, assignee, parent, labels, and due date.")]
+    #[tool(
+        description = "Create a new Jira issue. Requires project key, summary, and issue type. Optionally supports description, priority, assignee (account ID, email, name, or 'me'), parent, labels, due date, and custom fields by name (e.g., Story Points). Set validate to check required fields and allowed values first (see get_create_metadata)."
+    )]
     async fn create_issue(
         &self,
         Parameters(params): Parameters<CreateIssueParams>,
@@ -44,8 +79,12 @@ impl JiraServer {
         if let Some(priority) = &params.priority {
             request = request.priority(priority);
         }
-        if let Some(assignee_id) = &params.assignee_account_id {
-            request = request.assignee(assignee_id);
+        if let Some(assignee) = &params.assignee_account_id {
+            let assignee_id = match self.jira.resolve_user(assignee).await {
+                Ok(assignee_id) => assignee_id,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            };
+            request = request.assignee(&assignee_id);
         }
         if let Some(parent_key) = &params.parent_key {
             request = request.parent(parent_key);
@@ -57,34 +96,101 @@ impl JiraServer {
         if let Some(due_date) = &params.due_date {
             request = request.due_date(due_date);
         }
+        if let Some(components) = &params.components {
+            request = request.components(&str_refs(components));
+        }
+        if let Some(fix_versions) = &params.fix_versions {
+            request = request.fix_versions(&str_refs(fix_versions));
+        }
+        if let Some(affects_versions) = &params.affects_versions {
+            request = request.affects_versions(&str_refs(affects_versions));
+        }
+        let custom_fields = match self
+            .resolve_custom_fields(params.custom_fields.as_ref())
+            .await
+        {
+            Ok(custom_fields) => custom_fields,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        for (field, value) in &custom_fields {
+            request = match request.custom_field(field, value) {
+                Ok(request) => request,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            };
+        }
+
+        if params.validate.unwrap_or(false) {
+            let issue_types = match self.jira.get_create_issue_types(&params.project_key).await {
+                Ok(issue_types) => issue_types,
+                Err(e) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format_error(
+                        "Failed to get create metadata",
+                        &e,
+                    ))]));
+                }
+            };
+            let issue_type = match issue_types.find(&params.issue_type) {
+                Ok(issue_type) => issue_type,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            };
+            let fields = match self
+                .jira
+                .get_create_fields(&params.project_key, &issue_type.id)
+                .await
+            {
+                Ok(fields) => fields,
+                Err(e) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format_error(
+                        "Failed to get create metadata",
+                        &e,
+                    ))]));
+                }
+            };
+
+            let problems = request.validate(&fields);
+            if !problems.is_empty() {
+                let output = format!(
+                    "Issue not created, {} problem(s) found:\n- {}",
+                    problems.len(),
+                    problems.join("\n- ")
+                );
+                return Ok(CallToolResult::error(vec![Content::text(output)]));
+            }
+        }
 
         match self.jira.create_issue(request).await {
             Ok(created) => {
                 let output = format_create_result(&created);
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to create issue: {}",
-                e
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to create issue",
+                &e,
             ))])),
         }
     }
 
-    #[tool(description = "Search for Jira issues using JQL (Jira Query Language). Returns a list of issues matching the query.")]
+    #[tool(
+        description = "Search for Jira issues using JQL (Jira Query Language). Returns a list of issues matching the query and a page token when more results are available."
+    )]
     async fn search_issues(
         &self,
         Parameters(params): Parameters<SearchIssuesParams>,
     ) -> Result<CallToolResult, McpError> {
-        let max_results = params.max_results.unwrap_or(50).min(100);
+        let max_results = params.max_results.unwrap_or(50).min(500);
 
-        match self.jira.search_issues(&params.jql, max_results).await {
+        match self
+            .jira
+            .search_all_issues(&params.jql, max_results, params.page_token.as_deref())
+            .await
+        {
             Ok(result) => {
                 let output = format_search_result(&result);
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to search issues: {}",
-                e
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to search issues",
+                &e,
             ))])),
         }
     }
@@ -99,30 +205,91 @@ impl JiraServer {
                 let output = format_issue(&issue);
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to get issue: {}",
-                e
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get issue",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Get the change history of a Jira issue as a timeline: who changed which field, from what to what, and when. Optionally filter by field (e.g., status, assignee, Sprint) and date range."
+    )]
+    async fn get_issue_history(
+        &self,
+        Parameters(params): Parameters<GetIssueHistoryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let filter = match ChangelogFilter::new(
+            params.fields.as_deref().unwrap_or_default(),
+            params.since.as_deref(),
+            params.until.as_deref(),
+        ) {
+            Ok(filter) => filter,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self.jira.get_changelog(&params.issue_key).await {
+            Ok(entries) => {
+                let output = format_history(&params.issue_key, &filter.apply(entries));
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get issue history",
+                &e,
             ))])),
         }
     }
 
-    #[tool(description = "Add a comment to a Jira issue. Use this to leave notes, updates, or feedback on an issue.")]
+    #[tool(
+        description = "Add a comment to a Jira issue. Use this to leave notes, updates, or feedback on an issue. Supports Markdown unless plain_text is set."
+    )]
     async fn add_comment(
         &self,
         Parameters(params): Parameters<AddCommentParams>,
     ) -> Result<CallToolResult, McpError> {
+        let request = if params.plain_text.unwrap_or(false) {
+            AddCommentRequest::plain(&params.comment)
+        } else {
+            AddCommentRequest::new(&params.comment)
+        };
+
+        match self.jira.add_comment(&params.issue_key, request).await {
+            Ok(comment) => {
+                let output = format_comment(&params.issue_key, &comment);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to add comment",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Replace the text of an existing comment on a Jira issue (e.g., to correct a status update instead of posting a duplicate). Supports Markdown unless plain_text is set."
+    )]
+    async fn update_comment(
+        &self,
+        Parameters(params): Parameters<UpdateCommentParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let request = if params.plain_text.unwrap_or(false) {
+            AddCommentRequest::plain(&params.comment)
+        } else {
+            AddCommentRequest::new(&params.comment)
+        };
+
         match self
             .jira
-            .add_comment(&params.issue_key, &params.comment)
+            .update_comment(&params.issue_key, &params.comment_id, request)
             .await
         {
             Ok(comment) => {
-                let output = format_comment(&params.issue_key, &comment);
+                let output = format_comment_update_result(&params.issue_key, &comment);
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to add comment: {}",
-                e
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to update comment",
+                &e,
             ))])),
         }
     }
@@ -139,14 +306,16 @@ impl JiraServer {
                 let output = format_children(&params.parent_key, &result);
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to get children: {}",
-                e
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get children",
+                &e,
             ))])),
         }
     }
 
-    #[tool(description = "Get comments on a Jira issue with pagination support. Returns comments with author, date, and content.")]
+    #[tool(
+        description = "Get comments on a Jira issue with pagination support. Returns comments with their IDs, author, date, and content."
+    )]
     async fn get_comments(
         &self,
         Parameters(params): Parameters<GetCommentsParams>,
@@ -163,14 +332,39 @@ impl JiraServer {
                 let output = format_comments(&params.issue_key, &response);
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to get comments: {}",
-                e
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get comments",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Get a single comment on a Jira issue by its ID (as shown by get_comments)."
+    )]
+    async fn get_comment(
+        &self,
+        Parameters(params): Parameters<GetCommentParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self
+            .jira
+            .get_comment(&params.issue_key, &params.comment_id)
+            .await
+        {
+            Ok(comment) => {
+                let output = format_comment_details(&params.issue_key, &comment);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get comment",
+                &e,
             ))])),
         }
     }
 
-    #[tool(description = "Update a Jira issue's fields. Can update summary, description, due date, priority, assignee, parent (epic), and labels.")]
+    #[tool(
+        description = "Update a Jira issue's fields. Can update summary, description, due date, priority, assignee, parent (epic), labels, components and fix/affects versions (set, add or remove), values of multi-value custom fields (add or remove), and custom fields by name (e.g., Story Points)."
+    )]
     async fn update_issue(
         &self,
         Parameters(params): Parameters<UpdateIssueParams>,
@@ -194,8 +388,12 @@ impl JiraServer {
             update = update.priority(priority);
             updated_fields.push("priority");
         }
-        if let Some(assignee_id) = &params.assignee_account_id {
-            update = update.assignee(assignee_id);
+        if let Some(assignee) = &params.assignee_account_id {
+            let assignee_id = match self.jira.resolve_user(assignee).await {
+                Ok(assignee_id) => assignee_id,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            };
+            update = update.assignee(&assignee_id);
             updated_fields.push("assignee");
         }
         if let Some(parent_key) = &params.parent_key {
@@ -207,7 +405,78 @@ impl JiraServer {
             update = update.labels(label_refs);
             updated_fields.push("labels");
         }
+        if let Some(labels) = &params.add_labels {
+            update = update.add_labels(&str_refs(labels));
+            updated_fields.push("labels");
+        }
+        if let Some(labels) = &params.remove_labels {
+            update = update.remove_labels(&str_refs(labels));
+            updated_fields.push("labels");
+        }
+        for (operation, names) in [
+            (FieldOperation::Set, &params.components),
+            (FieldOperation::Add, &params.add_components),
+            (FieldOperation::Remove, &params.remove_components),
+        ] {
+            if let Some(names) = names {
+                update = update.components(operation, &str_refs(names));
+                updated_fields.push("components");
+            }
+        }
+        for (operation, names) in [
+            (FieldOperation::Set, &params.fix_versions),
+            (FieldOperation::Add, &params.add_fix_versions),
+            (FieldOperation::Remove, &params.remove_fix_versions),
+        ] {
+            if let Some(names) = names {
+                update = update.fix_versions(operation, &str_refs(names));
+                updated_fields.push("fix_versions");
+            }
+        }
+        for (operation, names) in [
+            (FieldOperation::Set, &params.affects_versions),
+            (FieldOperation::Add, &params.add_affects_versions),
+            (FieldOperation::Remove, &params.remove_affects_versions),
+        ] {
+            if let Some(names) = names {
+                update = update.affects_versions(operation, &str_refs(names));
+                updated_fields.push("affects_versions");
+            }
+        }
+        let custom_fields = match self
+            .resolve_custom_fields(params.custom_fields.as_ref())
+            .await
+        {
+            Ok(custom_fields) => custom_fields,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        for (field, value) in &custom_fields {
+            update = match update.custom_field(field, value) {
+                Ok(update) => update,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            };
+            updated_fields.push(field.name.as_str());
+        }
+        let mut field_operations = Vec::new();
+        for (operation, input) in [
+            (FieldOperation::Add, &params.add_custom_fields),
+            (FieldOperation::Remove, &params.remove_custom_fields),
+        ] {
+            match self.resolve_custom_fields(input.as_ref()).await {
+                Ok(fields) => field_operations.extend(fields.into_iter().map(|f| (operation, f))),
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            }
+        }
+        for (operation, (field, value)) in &field_operations {
+            update = match update.custom_field_operation(field, *operation, value) {
+                Ok(update) => update,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            };
+            updated_fields.push(field.name.as_str());
+        }
 
+        let mut seen = HashSet::new();
+        updated_fields.retain(|field| seen.insert(*field));
         if updated_fields.is_empty() {
             return Ok(CallToolResult::error(vec![Content::text(
                 "No fields provided to update. Please specify at least one field to update.",
@@ -219,23 +488,1465 @@ impl JiraServer {
                 let output = format_update_result(&params.issue_key, &updated_fields);
                 Ok(CallToolResult::success(vec![Content::text(output)]))
             }
-            Err(e) => Ok(CallToolResult::error(vec![Content::text(format!(
-                "Failed to update issue: {}",
-                e
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to update issue",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Delete a Jira issue, optionally with its sub-tasks. The first call only previews the issue and returns a confirmation token; call again with the same arguments and confirmation_token to delete it. This cannot be undone."
+    )]
+    async fn delete_issue(
+        &self,
+        Parameters(params): Parameters<DeleteIssueParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let issue_key = params.issue_key.trim().to_uppercase();
+        let delete_subtasks = params.delete_subtasks.unwrap_or(false);
+        let action = if delete_subtasks {
+            format!("delete issue {} and its sub-tasks", issue_key)
+        } else {
+            format!("delete issue {}", issue_key)
+        };
+
+        let Some(token) = &params.confirmation_token else {
+            return match self.jira.get_issue(&issue_key).await {
+                Ok(issue) => {
+                    let token = self.confirmations.issue(&action);
+                    let output = format_issue_deletion_preview(&issue, delete_subtasks, &token);
+                    Ok(CallToolResult::success(vec![Content::text(output)]))
+                }
+                Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to get issue",
+                    &e,
+                ))])),
+            };
+        };
+        if let Err(e) = self.confirmations.redeem(token, &action) {
+            return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+        }
+
+        match self.jira.delete_issue(&issue_key, delete_subtasks).await {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Issue {} deleted",
+                issue_key
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to delete issue",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Delete a comment from a Jira issue. The first call only previews the comment and returns a confirmation token; call again with the same arguments and confirmation_token to delete it. This cannot be undone."
+    )]
+    async fn delete_comment(
+        &self,
+        Parameters(params): Parameters<DeleteCommentParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let issue_key = params.issue_key.trim().to_uppercase();
+        let comment_id = params.comment_id.trim();
+        let action = format!("delete comment {} on {}", comment_id, issue_key);
+
+        let Some(token) = &params.confirmation_token else {
+            return match self.jira.get_comment(&issue_key, comment_id).await {
+                Ok(comment) => {
+                    let token = self.confirmations.issue(&action);
+                    let output = format_comment_deletion_preview(&issue_key, &comment, &token);
+                    Ok(CallToolResult::success(vec![Content::text(output)]))
+                }
+                Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to get comment",
+                    &e,
+                ))])),
+            };
+        };
+        if let Err(e) = self.confirmations.redeem(token, &action) {
+            return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+        }
+
+        match self.jira.delete_comment(&issue_key, comment_id).await {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Comment {} deleted from {}",
+                comment_id, issue_key
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to delete comment",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "List the workflow transitions currently available for a Jira issue, with the status each one leads to and any required fields."
+    )]
+    async fn list_transitions(
+        &self,
+        Parameters(params): Parameters<ListTransitionsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self.jira.get_transitions(&params.issue_key).await {
+            Ok(response) => {
+                let output = format_transitions(&params.issue_key, &response);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list transitions",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Move a Jira issue through its workflow (e.g., To Do → In Progress → Done). Accepts a transition ID or a target status name. Optionally sets resolution, fields required by the transition screen, and a comment."
+    )]
+    async fn transition_issue(
+        &self,
+        Parameters(params): Parameters<TransitionIssueParams>,
+    ) -> Result<CallToolResult, McpError> {
+        if params.transition_id.is_none() && params.status.is_none() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Either transition_id or status must be provided.",
+            )]));
+        }
+
+        let available = match self.jira.get_transitions(&params.issue_key).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to get transitions",
+                    &e,
+                ))]));
+            }
+        };
+
+        let transition = match (&params.transition_id, &params.status) {
+            (Some(id), _) => available.transitions.iter().find(|t| &t.id == id),
+            (None, Some(status)) => available.find_by_status(status),
+            (None, None) => None,
+        };
+        let Some(transition) = transition else {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "No matching transition found.\n\n{}",
+                format_transitions(&params.issue_key, &available)
+            ))]));
+        };
+
+        let mut request = TransitionIssueRequest::new(&transition.id);
+
+        if let Some(resolution) = &params.resolution {
+            request = request.resolution(resolution);
+        }
+        if let Some(fields) = &params.fields {
+            for (field_id, value) in fields {
+                request = request.field(field_id, value.clone());
+            }
+        }
+        if let Some(comment) = &params.comment {
+            request = request.comment(comment);
+        }
+
+        match self.jira.transition_issue(&params.issue_key, request).await {
+            Ok(()) => {
+                let output = format_transition_result(&params.issue_key, transition);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to transition issue",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "List time logged on a Jira issue (worklogs) with pagination. Returns worklog IDs, durations, authors, start times and comments."
+    )]
+    async fn list_worklogs(
+        &self,
+        Parameters(params): Parameters<ListWorklogsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let start_at = params.start_at.unwrap_or(0);
+        let max_results = params.max_results.unwrap_or(50).min(1000);
+
+        match self
+            .jira
+            .get_worklogs(&params.issue_key, start_at, max_results)
+            .await
+        {
+            Ok(response) => {
+                let output = format_worklogs(&params.issue_key, &response);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list worklogs",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Log time on a Jira issue. Accepts durations like '1h 30m', an optional start time and Markdown comment, and how to adjust the remaining estimate."
+    )]
+    async fn add_worklog(
+        &self,
+        Parameters(params): Parameters<AddWorklogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let started = params.started.clone().unwrap_or_else(jira_timestamp_now);
+        let prepared = worklog_request(
+            Some(&params.time_spent),
+            Some(&started),
+            params.comment.as_deref(),
+        )
+        .and_then(|request| {
+            let adjustment = EstimateAdjustment::from_mode(
+                params.adjust_estimate.as_deref(),
+                params.estimate.as_deref(),
+            )?;
+            Ok((request, adjustment))
+        });
+        let (request, adjustment) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self
+            .jira
+            .add_worklog(&params.issue_key, request, &adjustment)
+            .await
+        {
+            Ok(worklog) => {
+                let output = format_worklog_result(&params.issue_key, &worklog, "added");
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to add worklog",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Update an existing worklog on a Jira issue: time spent, start time or comment."
+    )]
+    async fn update_worklog(
+        &self,
+        Parameters(params): Parameters<UpdateWorklogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let prepared = worklog_request(
+            params.time_spent.as_deref(),
+            params.started.as_deref(),
+            params.comment.as_deref(),
+        )
+        .and_then(|request| {
+            let adjustment = EstimateAdjustment::from_mode(
+                params.adjust_estimate.as_deref(),
+                params.estimate.as_deref(),
+            )?;
+            Ok((request, adjustment))
+        });
+        let (request, adjustment) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self
+            .jira
+            .update_worklog(&params.issue_key, &params.worklog_id, request, &adjustment)
+            .await
+        {
+            Ok(worklog) => {
+                let output = format_worklog_result(&params.issue_key, &worklog, "updated");
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to update worklog",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Delete a worklog from a Jira issue, optionally adjusting the remaining estimate."
+    )]
+    async fn delete_worklog(
+        &self,
+        Parameters(params): Parameters<DeleteWorklogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let adjustment = match EstimateAdjustment::from_mode(
+            params.adjust_estimate.as_deref(),
+            params.estimate.as_deref(),
+        ) {
+            Ok(adjustment) => adjustment,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self
+            .jira
+            .delete_worklog(&params.issue_key, &params.worklog_id, &adjustment)
+            .await
+        {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Worklog {} deleted from {}",
+                params.worklog_id, params.issue_key
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to delete worklog",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Attach a file to a Jira issue. Provide either a local file path or base64-encoded content with a filename."
+    )]
+    async fn add_attachment(
+        &self,
+        Parameters(params): Parameters<AddAttachmentParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let max_bytes = self.jira.max_upload_bytes();
+        let (filename, data) = match attachment_upload(&params, max_bytes).await {
+            Ok(upload) => upload,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self
+            .jira
+            .add_attachment(&params.issue_key, &filename, data)
+            .await
+        {
+            Ok(attachments) => {
+                let output = format_attachment_result(&params.issue_key, &attachments);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to add attachment",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "List the attachments on a Jira issue with their IDs, sizes, MIME types and authors."
+    )]
+    async fn list_attachments(
+        &self,
+        Parameters(params): Parameters<ListAttachmentsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self.jira.get_attachments(&params.issue_key).await {
+            Ok(attachments) => {
+                let output = format_attachments(&params.issue_key, &attachments);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list attachments",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Download a Jira attachment. Text files are returned inline; binary files (images, PDFs, archives) as embedded resources."
+    )]
+    async fn download_attachment(
+        &self,
+        Parameters(params): Parameters<DownloadAttachmentParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let (attachment, data) = match self.jira.download_attachment(&params.attachment_id).await {
+            Ok(download) => download,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to download attachment",
+                    &e,
+                ))]));
+            }
+        };
+
+        let mime_type = attachment
+            .mime_type
+            .clone()
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+        let header = format!(
+            "Attachment {} ({}, {})",
+            attachment.filename,
+            mime_type,
+            format_size(data.len() as u64)
+        );
+
+        if is_text_mime_type(&mime_type)
+            && let Ok(text) = std::str::from_utf8(&data)
+        {
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "{}:\n\n{}",
+                header, text
+            ))]));
+        }
+
+        let uri = attachment
+            .content
+            .clone()
+            .unwrap_or_else(|| format!("jira-attachment://{}", attachment.id));
+        Ok(CallToolResult::success(vec![
+            Content::text(header),
+            Content::resource(ResourceContents::BlobResourceContents {
+                uri,
+                mime_type: Some(mime_type),
+                blob: STANDARD.encode(&data),
+            }),
+        ]))
+    }
+
+    #[tool(
+        description = "Get what create_issue accepts in a project: without an issue type, the issue types that can be created; with one, its fields, which are required, and their allowed values."
+    )]
+    async fn get_create_metadata(
+        &self,
+        Parameters(params): Parameters<GetCreateMetadataParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let issue_types = match self.jira.get_create_issue_types(&params.project_key).await {
+            Ok(issue_types) => issue_types,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to get create metadata",
+                    &e,
+                ))]));
+            }
+        };
+        let Some(issue_type_name) = &params.issue_type else {
+            return Ok(CallToolResult::success(vec![Content::text(
+                format_create_issue_types(&params.project_key, &issue_types),
+            )]));
+        };
+
+        let issue_type = match issue_types.find(issue_type_name) {
+            Ok(issue_type) => issue_type,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        match self
+            .jira
+            .get_create_fields(&params.project_key, &issue_type.id)
+            .await
+        {
+            Ok(fields) => {
+                let output = format_create_fields(&params.project_key, issue_type, &fields);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get create metadata",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "List the Jira projects you can see, with their keys. Optionally filter by key or name."
+    )]
+    async fn list_projects(
+        &self,
+        Parameters(params): Parameters<ListProjectsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let start_at = params.start_at.unwrap_or(0);
+        let max_results = params.max_results.unwrap_or(50).min(100);
+
+        match self
+            .jira
+            .list_projects(params.query.as_deref(), start_at, max_results)
+            .await
+        {
+            Ok(page) => Ok(CallToolResult::success(vec![Content::text(
+                format_projects(&page),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list projects",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Get a Jira project's details: lead, issue types, components and versions."
+    )]
+    async fn get_project(
+        &self,
+        Parameters(params): Parameters<GetProjectParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = tokio::try_join!(
+            self.jira.get_project(&params.project_key),
+            self.jira.get_project_components(&params.project_key),
+            self.jira.get_project_versions(&params.project_key),
+        );
+
+        match result {
+            Ok((project, components, versions)) => {
+                let output = format_project(&project, &components, &versions);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get project",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Search Jira users by name, email address or username. Returns their account IDs (Cloud) or usernames (Data Center)."
+    )]
+    async fn search_users(
+        &self,
+        Parameters(params): Parameters<SearchUsersParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let max_results = params.max_results.unwrap_or(20).min(100);
+
+        match self.jira.search_users(&params.query, max_results).await {
+            Ok(users) => Ok(CallToolResult::success(vec![Content::text(format_users(
+                &users,
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to search users",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Find users who can be assigned issues in a project or a specific issue, optionally filtered by name or email."
+    )]
+    async fn find_assignable_users(
+        &self,
+        Parameters(params): Parameters<FindAssignableUsersParams>,
+    ) -> Result<CallToolResult, McpError> {
+        if params.project_key.is_none() && params.issue_key.is_none() {
+            return Ok(CallToolResult::error(vec![Content::text(
+                "Either 'project_key' or 'issue_key' is required",
+            )]));
+        }
+        let max_results = params.max_results.unwrap_or(20).min(100);
+
+        match self
+            .jira
+            .find_assignable_users(
+                params.project_key.as_deref(),
+                params.issue_key.as_deref(),
+                params.query.as_deref(),
+                max_results,
+            )
+            .await
+        {
+            Ok(users) => Ok(CallToolResult::success(vec![Content::text(format_users(
+                &users,
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to find assignable users",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(description = "Get the Jira user the server is authenticated as.")]
+    async fn get_myself(&self) -> Result<CallToolResult, McpError> {
+        match self.jira.get_myself().await {
+            Ok(user) => Ok(CallToolResult::success(vec![Content::text(format_user(
+                &user,
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get current user",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "List Jira fields (system and custom) with their IDs and value types. Use it to find custom fields such as Story Points for create_issue and update_issue."
+    )]
+    async fn list_fields(
+        &self,
+        Parameters(params): Parameters<ListFieldsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let catalogue = match self.jira.get_fields(true).await {
+            Ok(catalogue) => catalogue,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to list fields",
+                    &e,
+                ))]));
+            }
+        };
+
+        let query = params.query.as_deref().map(str::to_lowercase);
+        let mut fields: Vec<&Field> = catalogue
+            .fields()
+            .iter()
+            .filter(|f| f.custom || !params.custom_only.unwrap_or(false))
+            .filter(|f| {
+                query.as_ref().is_none_or(|q| {
+                    f.name.to_lowercase().contains(q) || f.id.to_lowercase().contains(q)
+                })
+            })
+            .collect();
+        fields.sort_by_key(|f| f.name.to_lowercase());
+
+        Ok(CallToolResult::success(vec![Content::text(format_fields(
+            &fields,
+        ))]))
+    }
+
+    #[tool(
+        description = "List the kinds of links that can be created between Jira issues (e.g., Blocks: 'blocks' / 'is blocked by')."
+    )]
+    async fn list_link_types(&self) -> Result<CallToolResult, McpError> {
+        match self.jira.get_issue_link_types().await {
+            Ok(response) => Ok(CallToolResult::success(vec![Content::text(
+                format_link_types(&response),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list link types",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Link two Jira issues, e.g. 'PROJ-1 blocks PROJ-2'. Accepts a link type name with a direction, or a relationship such as 'is blocked by'."
+    )]
+    async fn link_issues(
+        &self,
+        Parameters(params): Parameters<LinkIssuesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let link_types = match self.jira.get_issue_link_types().await {
+            Ok(response) => response,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to get link types",
+                    &e,
+                ))]));
+            }
+        };
+        let Some((link_type, implied)) = link_types.find(&params.link_type) else {
+            return Ok(CallToolResult::error(vec![Content::text(format!(
+                "Unknown link type '{}'.\n\n{}",
+                params.link_type,
+                format_link_types(&link_types)
+            ))]));
+        };
+
+        let direction = match params.direction.as_deref().map(LinkDirection::from_name) {
+            Some(Ok(direction)) if implied.is_some_and(|implied| implied != direction) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "'{}' contradicts direction '{}'",
+                    params.link_type,
+                    params.direction.as_deref().unwrap_or_default()
+                ))]));
+            }
+            Some(Ok(direction)) => direction,
+            Some(Err(e)) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            None => implied.unwrap_or_default(),
+        };
+        let relationship = match direction {
+            LinkDirection::Outward => &link_type.outward,
+            LinkDirection::Inward => &link_type.inward,
+        };
+
+        let request = LinkIssuesRequest::new(
+            &link_type.name,
+            &params.issue_key,
+            &params.other_issue_key,
+            direction,
+        );
+        match self.jira.link_issues(request).await {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Linked issues: {} {} {}",
+                params.issue_key, relationship, params.other_issue_key
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to link issues",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Remove a link between two Jira issues by link ID (shown under Links by get_issue)."
+    )]
+    async fn remove_issue_link(
+        &self,
+        Parameters(params): Parameters<RemoveIssueLinkParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self.jira.delete_issue_link(&params.link_id).await {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Issue link {} removed",
+                params.link_id
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to remove issue link",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "List Jira Software boards, optionally filtered by project, type (scrum/kanban) or name. Returns board IDs for the sprint and backlog tools."
+    )]
+    async fn list_boards(
+        &self,
+        Parameters(params): Parameters<ListBoardsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self
+            .jira
+            .get_boards(
+                params.project_key.as_deref(),
+                params.board_type.as_deref(),
+                params.name.as_deref(),
+            )
+            .await
+        {
+            Ok(boards) => Ok(CallToolResult::success(vec![Content::text(format_boards(
+                &boards,
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list boards",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "List the sprints of a board with their state, dates and goal. Optionally filter by state (future, active, closed)."
+    )]
+    async fn list_sprints(
+        &self,
+        Parameters(params): Parameters<ListSprintsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self
+            .jira
+            .get_sprints(params.board_id, params.state.as_deref())
+            .await
+        {
+            Ok(sprints) => Ok(CallToolResult::success(vec![Content::text(
+                format_sprints(params.board_id, &sprints),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list sprints",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Get a sprint's goal, dates, status breakdown and issues. Pass a sprint ID, or a board ID to get the board's current (active) sprint."
+    )]
+    async fn get_sprint(
+        &self,
+        Parameters(params): Parameters<GetSprintParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let sprint = match (params.sprint_id, params.board_id) {
+            (Some(sprint_id), _) => self.jira.get_sprint(sprint_id).await,
+            (None, Some(board_id)) => match self.jira.get_sprints(board_id, Some("active")).await {
+                Ok(sprints) => match sprints.into_iter().next() {
+                    Some(sprint) => Ok(sprint),
+                    None => {
+                        return Ok(CallToolResult::error(vec![Content::text(format!(
+                            "Board {} has no active sprint",
+                            board_id
+                        ))]));
+                    }
+                },
+                Err(e) => Err(e),
+            },
+            (None, None) => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    "Either sprint_id or board_id must be provided.",
+                )]));
+            }
+        };
+        let sprint = match sprint {
+            Ok(sprint) => sprint,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to get sprint",
+                    &e,
+                ))]));
+            }
+        };
+
+        let max_results = params.max_results.unwrap_or(100).min(1000);
+        match self.jira.get_sprint_issues(sprint.id, max_results).await {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(format_sprint(
+                &sprint, &result,
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get sprint issues",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Get the backlog of a board (issues not in any sprint), in rank order, with a status breakdown."
+    )]
+    async fn get_backlog(
+        &self,
+        Parameters(params): Parameters<GetBacklogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let max_results = params.max_results.unwrap_or(100).min(1000);
+
+        match self.jira.get_backlog(params.board_id, max_results).await {
+            Ok(result) => Ok(CallToolResult::success(vec![Content::text(
+                format_backlog(params.board_id, &result),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get backlog",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(description = "Move issues into a sprint.")]
+    async fn move_issues_to_sprint(
+        &self,
+        Parameters(params): Parameters<MoveIssuesToSprintParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self
+            .jira
+            .move_issues_to_sprint(params.sprint_id, &params.issue_keys)
+            .await
+        {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Moved {} issue(s) to sprint {}: {}",
+                params.issue_keys.len(),
+                params.sprint_id,
+                params.issue_keys.join(", ")
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to move issues to sprint",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(description = "Move issues out of their sprint into the backlog.")]
+    async fn move_issues_to_backlog(
+        &self,
+        Parameters(params): Parameters<MoveIssuesToBacklogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self.jira.move_issues_to_backlog(&params.issue_keys).await {
+            Ok(()) => Ok(CallToolResult::success(vec![Content::text(format!(
+                "Moved {} issue(s) to the backlog: {}",
+                params.issue_keys.len(),
+                params.issue_keys.join(", ")
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to move issues to backlog",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Create a future sprint on a board, optionally with a goal and planned dates."
+    )]
+    async fn create_sprint(
+        &self,
+        Parameters(params): Parameters<CreateSprintParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let mut request = SprintRequest::create(params.board_id, &params.name);
+        if let Some(goal) = &params.goal {
+            request = request.goal(goal);
+        }
+        let request = match sprint_dates(
+            request,
+            params.start_date.as_deref(),
+            params.end_date.as_deref(),
+        ) {
+            Ok(request) => request,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self.jira.create_sprint(request).await {
+            Ok(sprint) => Ok(CallToolResult::success(vec![Content::text(
+                format_sprint_result(&sprint, "created"),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to create sprint",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Start a future sprint. Starts now and ends at the sprint's planned end (or after two weeks) unless dates are given."
+    )]
+    async fn start_sprint(
+        &self,
+        Parameters(params): Parameters<StartSprintParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let sprint = match self.jira.get_sprint(params.sprint_id).await {
+            Ok(sprint) => sprint,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to get sprint",
+                    &e,
+                ))]));
+            }
+        };
+
+        // Start now unless told otherwise; keep the planned end if there is one
+        let start = params.start_date.clone().unwrap_or_else(iso_timestamp_now);
+        let end = params.end_date.as_deref().or(sprint.end_date.as_deref());
+        let mut request = SprintRequest::start();
+        if let Some(goal) = &params.goal {
+            request = request.goal(goal);
+        }
+        let request = match sprint_dates(request, Some(&start), end) {
+            Ok(request) => request,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self.jira.update_sprint(params.sprint_id, request).await {
+            Ok(sprint) => Ok(CallToolResult::success(vec![Content::text(
+                format_sprint_result(&sprint, "started"),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to start sprint",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Close an active sprint. Jira moves its unfinished issues to the backlog."
+    )]
+    async fn close_sprint(
+        &self,
+        Parameters(params): Parameters<CloseSprintParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self
+            .jira
+            .update_sprint(params.sprint_id, SprintRequest::close())
+            .await
+        {
+            Ok(sprint) => Ok(CallToolResult::success(vec![Content::text(
+                format_sprint_result(&sprint, "closed"),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to close sprint",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(description = "List a project's versions (releases) with their IDs, status and dates.")]
+    async fn list_versions(
+        &self,
+        Parameters(params): Parameters<ListVersionsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self.jira.get_project_versions(&params.project_key).await {
+            Ok(versions) => {
+                let include_archived = params.include_archived.unwrap_or(false);
+                let versions: Vec<&Version> = versions
+                    .iter()
+                    .filter(|v| include_archived || !v.archived)
+                    .collect();
+                let output = format_versions(&params.project_key, &versions);
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to list versions",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Get a version (release) with its progress (done / in progress / to do counts) and the issues whose fix version it is."
+    )]
+    async fn get_version(
+        &self,
+        Parameters(params): Parameters<GetVersionParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let max_results = params.max_results.unwrap_or(100).min(1000);
+        let result = tokio::try_join!(
+            self.jira.get_version(&params.version_id),
+            self.jira.get_version_progress(&params.version_id),
+            self.jira
+                .get_version_issues(&params.version_id, max_results),
+        );
+
+        match result {
+            Ok((version, progress, issues)) => Ok(CallToolResult::success(vec![Content::text(
+                format_version(&version, &progress, &issues),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to get version",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Create a version (release) in a project, optionally with a description, start date and planned release date."
+    )]
+    async fn create_version(
+        &self,
+        Parameters(params): Parameters<CreateVersionParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let project = match self.jira.get_project(&params.project_key).await {
+            Ok(project) => project,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format_error(
+                    "Failed to create version",
+                    &e,
+                ))]));
+            }
+        };
+        let request = match version_request(&project, &params) {
+            Ok(request) => request,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        match self.jira.create_version(request).await {
+            Ok(version) => Ok(CallToolResult::success(vec![Content::text(
+                format_version_result(&version, "created"),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to create version",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Mark a version as released (today by default). Optionally move its unresolved issues to another version first."
+    )]
+    async fn release_version(
+        &self,
+        Parameters(params): Parameters<ReleaseVersionParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let release_date = match params.release_date.as_deref().map(to_date) {
+            Some(Ok(date)) => date,
+            Some(Err(e)) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            None => date_today(),
+        };
+
+        let mut moved = None;
+        if let Some(target_id) = &params.move_unresolved_to {
+            match self
+                .move_unresolved_issues(&params.version_id, target_id)
+                .await
+            {
+                Ok((_, target, keys)) => moved = Some((target.name, keys)),
+                Err(message) => return Ok(CallToolResult::error(vec![Content::text(message)])),
+            }
+        }
+
+        match self
+            .jira
+            .update_version(&params.version_id, VersionRequest::release(&release_date))
+            .await
+        {
+            Ok(version) => {
+                let mut output = format_version_result(&version, "released");
+                if let Some((target, keys)) = &moved {
+                    output.push_str(&format!(
+                        "\n\nMoved {} unresolved issue(s) to {}: {}",
+                        keys.len(),
+                        target,
+                        keys.join(", ")
+                    ));
+                }
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to release version",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(description = "Archive a version so it no longer appears in version pickers.")]
+    async fn archive_version(
+        &self,
+        Parameters(params): Parameters<ArchiveVersionParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self
+            .jira
+            .update_version(&params.version_id, VersionRequest::archive())
+            .await
+        {
+            Ok(version) => Ok(CallToolResult::success(vec![Content::text(
+                format_version_result(&version, "archived"),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to archive version",
+                &e,
+            ))])),
+        }
+    }
+
+    #[tool(
+        description = "Move the unresolved issues of a version to another version (e.g., the next release), keeping their other fix versions."
+    )]
+    async fn move_version_issues(
+        &self,
+        Parameters(params): Parameters<MoveVersionIssuesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        match self
+            .move_unresolved_issues(&params.version_id, &params.target_version_id)
+            .await
+        {
+            Ok((source, target, keys)) if keys.is_empty() => {
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "{} has no unresolved issues; nothing moved to {}",
+                    source.name, target.name
+                ))]))
+            }
+            Ok((source, target, keys)) => {
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Moved {} unresolved issue(s) from {} to {}: {}",
+                    keys.len(),
+                    source.name,
+                    target.name,
+                    keys.join(", ")
+                ))]))
+            }
+            Err(message) => Ok(CallToolResult::error(vec![Content::text(message)])),
+        }
+    }
+
+    #[tool(
+        description = "Generate Markdown release notes for a fix version (or any JQL), grouped by issue type and/or component. Headings per type, issue keys, links and the line format are configurable, and issues with given labels (e.g., 'internal') can be left out."
+    )]
+    async fn generate_release_notes(
+        &self,
+        Parameters(params): Parameters<GenerateReleaseNotesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let (jql, options) = match (release_notes_jql(&params), release_notes_options(&params)) {
+            (Ok(jql), Ok(options)) => (jql, options),
+            (Err(e), _) | (_, Err(e)) => {
+                return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+            }
+        };
+
+        match self.jira.get_release_notes_issues(&jql).await {
+            Ok(result) => {
+                let mut output =
+                    render_release_notes(&result.issues, &options, |key| self.jira.browse_url(key));
+                if result.next_page_token.is_some() {
+                    output.push_str(&format!(
+                        "\n_Only the first {} matching issues are included._\n",
+                        RELEASE_NOTES_LIMIT
+                    ));
+                }
+                Ok(CallToolResult::success(vec![Content::text(output)]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(format_error(
+                "Failed to generate release notes",
+                &e,
             ))])),
         }
     }
 }
 
+impl JiraServer {
+    /// Move the unresolved issues of one version to another, returning both
+    /// versions and the moved issue keys. The error message lists any issues
+    /// moved before the failure.
+    async fn move_unresolved_issues(
+        &self,
+        version_id: &str,
+        target_version_id: &str,
+    ) -> Result<(Version, Version, Vec<String>), String> {
+        let failed = |e: &JiraError| format_error("Failed to move unresolved issues", e);
+        let (source, target) = tokio::try_join!(
+            self.jira.get_version(version_id),
+            self.jira.get_version(target_version_id),
+        )
+        .map_err(|e| failed(&e))?;
+
+        let mut moved = Vec::new();
+        if let Err(e) = self
+            .jira
+            .move_unresolved_issues(&source, &target, &mut moved)
+            .await
+        {
+            let mut message = failed(&e);
+            if !moved.is_empty() {
+                message.push_str(&format!(
+                    "\n\nAlready moved {} issue(s) to {} before the failure: {}",
+                    moved.len(),
+                    target.name,
+                    moved.join(", ")
+                ));
+            }
+            return Err(message);
+        }
+        Ok((source, target, moved))
+    }
+
+    /// Look up `custom_fields` input (keyed by field name or ID) in the field
+    /// catalogue, refreshing the catalogue once if a field is not found. Users
+    /// in user fields may be given like assignees (email, name or "me").
+    async fn resolve_custom_fields(
+        &self,
+        input: Option<&HashMap<String, serde_json::Value>>,
+    ) -> anyhow::Result<Vec<(Field, serde_json::Value)>> {
+        let Some(input) = input.filter(|input| !input.is_empty()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut refresh = false;
+        loop {
+            let catalogue = self
+                .jira
+                .get_fields(refresh)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to load fields: {}", e))?;
+            let resolved: anyhow::Result<Vec<_>> = input
+                .iter()
+                .map(|(key, value)| Ok((catalogue.resolve(key)?.clone(), value.clone())))
+                .collect();
+
+            match resolved {
+                Ok(mut resolved) => {
+                    for (field, value) in &mut resolved {
+                        if field.holds_users() {
+                            *value = self.jira.resolve_user_values(field, value.take()).await?;
+                        }
+                    }
+                    resolved.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+                    return Ok(resolved);
+                }
+                Err(e) if refresh => return Err(e),
+                Err(_) => refresh = true,
+            }
+        }
+    }
+}
+
 #[tool_handler]
 impl rmcp::ServerHandler for JiraServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             instructions: Some(
-                "Jira MCP Server - Search, retrieve, and comment on Jira issues".into(),
+                "Jira MCP Server - Search, create, update and delete Jira issues and comments; \
+                 move issues through workflow transitions; log work; manage attachments and \
+                 issue links; plan sprints on boards; manage versions and generate release \
+                 notes; and look up projects, users and fields. Deleting an issue or comment \
+                 takes a confirmation token returned by a first, previewing call."
+                    .into(),
             ),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
             ..Default::default()
         }
     }
 }
+
+/// Build a worklog request from tool input, parsing the duration and start time.
+fn worklog_request(
+    time_spent: Option<&str>,
+    started: Option<&str>,
+    comment: Option<&str>,
+) -> anyhow::Result<WorklogRequest> {
+    let mut request = WorklogRequest::new();
+    if let Some(time_spent) = time_spent {
+        request = request.time_spent_seconds(parse_duration(time_spent)?);
+    }
+    if let Some(started) = started {
+        request = request.started(&to_jira_timestamp(started)?);
+    }
+    if let Some(comment) = comment {
+        request = request.comment(comment);
+    }
+    Ok(request)
+}
+
+/// Set a sprint's dates from ISO 8601 input. Without an end, a sprint with a
+/// start lasts [`DEFAULT_SPRINT_DAYS`].
+fn sprint_dates(
+    request: SprintRequest,
+    start: Option<&str>,
+    end: Option<&str>,
+) -> anyhow::Result<SprintRequest> {
+    let Some(start) = start.map(to_iso_timestamp).transpose()? else {
+        return match end {
+            Some(end) => Ok(request.end_date(&to_iso_timestamp(end)?)),
+            None => Ok(request),
+        };
+    };
+    let end = match end {
+        Some(end) => to_iso_timestamp(end)?,
+        None => iso_timestamp_plus_days(&start, DEFAULT_SPRINT_DAYS)?,
+    };
+    Ok(request.start_date(&start).end_date(&end))
+}
+
+/// Read the file name and content of an attachment upload from a local path
+/// or base64 input, refusing anything larger than `max_bytes`.
+async fn attachment_upload(
+    params: &AddAttachmentParams,
+    max_bytes: usize,
+) -> anyhow::Result<(String, Vec<u8>)> {
+    let too_large = |size: u64| {
+        anyhow::anyhow!(
+            "Attachment is {}, larger than the {} upload limit (JIRA_MAX_UPLOAD_BYTES)",
+            format_size(size),
+            format_size(max_bytes as u64)
+        )
+    };
+
+    match (&params.file_path, &params.content_base64) {
+        (Some(path), None) => {
+            let path = Path::new(path);
+            let cannot_read =
+                |e: std::io::Error| anyhow::anyhow!("Cannot read {}: {}", path.display(), e);
+            let file = tokio::fs::File::open(path).await.map_err(cannot_read)?;
+            let metadata = file.metadata().await.map_err(cannot_read)?;
+            if !metadata.is_file() {
+                anyhow::bail!("{} is not a regular file", path.display());
+            }
+            if metadata.len() > max_bytes as u64 {
+                return Err(too_large(metadata.len()));
+            }
+
+            // The file may grow after the size check; never read past the limit.
+            let mut data = Vec::with_capacity(metadata.len() as usize);
+            file.take(max_bytes as u64 + 1)
+                .read_to_end(&mut data)
+                .await
+                .map_err(cannot_read)?;
+            if data.len() > max_bytes {
+                return Err(too_large(data.len() as u64));
+            }
+
+            let filename = match &params.filename {
+                Some(filename) => filename.clone(),
+                None => path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .ok_or_else(|| anyhow::anyhow!("{} has no file name", path.display()))?,
+            };
+            Ok((filename, data))
+        }
+        (None, Some(content)) => {
+            let filename = params
+                .filename
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("'filename' is required with 'content_base64'"))?;
+            let content = content.trim();
+            // Size of well-formed padded input, checked before decoding it
+            let padding = content.bytes().rev().take_while(|&b| b == b'=').count();
+            let decoded_size = (content.len() / 4 * 3).saturating_sub(padding);
+            if decoded_size > max_bytes {
+                return Err(too_large(decoded_size as u64));
+            }
+            let data = STANDARD
+                .decode(content)
+                .map_err(|e| anyhow::anyhow!("Invalid base64 content: {}", e))?;
+            if data.len() > max_bytes {
+                return Err(too_large(data.len() as u64));
+            }
+            Ok((filename, data))
+        }
+        _ => anyhow::bail!("Provide exactly one of 'file_path' or 'content_base64'"),
+    }
+}
+
+/// Whether an attachment can be returned inline as text.
+fn is_text_mime_type(mime_type: &str) -> bool {
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || matches!(
+            essence,
+            "application/json"
+                | "application/xml"
+                | "application/x-yaml"
+                | "application/yaml"
+                | "application/javascript"
+                | "application/x-sh"
+                | "application/sql"
+        )
+}
+
+/// Build a version creation request, checking the dates.
+fn version_request(
+    project: &Project,
+    params: &CreateVersionParams,
+) -> anyhow::Result<VersionRequest> {
+    let project_id = project
+        .id
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Unexpected project ID '{}'", project.id))?;
+    let mut request = VersionRequest::create(project_id, &params.name);
+    if let Some(description) = &params.description {
+        request = request.description(description);
+    }
+    if let Some(start_date) = &params.start_date {
+        request = request.start_date(&to_date(start_date)?);
+    }
+    if let Some(release_date) = &params.release_date {
+        request = request.release_date(&to_date(release_date)?);
+    }
+    Ok(request)
+}
+
+/// JQL for the issues in the release notes: the given JQL, or the issues of
+/// a fix version (by ID, or by name within an optional project).
+fn release_notes_jql(params: &GenerateReleaseNotesParams) -> anyhow::Result<String> {
+    match (&params.jql, &params.fix_version) {
+        (Some(_), Some(_)) => anyhow::bail!("Pass either 'fix_version' or 'jql', not both"),
+        (Some(jql), None) => Ok(jql.clone()),
+        (None, Some(version)) => {
+            let version = version.trim();
+            let is_id = !version.is_empty() && version.chars().all(|c| c.is_ascii_digit());
+            let version_clause = if is_id {
+                format!("fixVersion = {}", version)
+            } else {
+                format!("fixVersion = {}", jql_string(version))
+            };
+            Ok(match &params.project_key {
+                Some(project_key) => format!(
+                    "project = {} AND {} ORDER BY key",
+                    jql_string(project_key.trim()),
+                    version_clause
+                ),
+                None => format!("{} ORDER BY key", version_clause),
+            })
+        }
+        (None, None) => anyhow::bail!("Pass 'fix_version' or 'jql' to select the issues"),
+    }
+}
+
+/// Quote `value` as a JQL string literal, escaping backslashes and quotes.
+fn jql_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn release_notes_options(
+    params: &GenerateReleaseNotesParams,
+) -> anyhow::Result<ReleaseNotesOptions> {
+    let defaults = ReleaseNotesOptions::default();
+    let title = match (&params.title, &params.fix_version) {
+        (Some(title), _) => title.clone(),
+        (None, Some(version)) => format!("{} — {}", defaults.title, version.trim()),
+        (None, None) => defaults.title.clone(),
+    };
+    Ok(ReleaseNotesOptions {
+        title,
+        group_by: match params.group_by.as_deref() {
+            Some(name) => GroupBy::from_name(name)?,
+            None => GroupBy::default(),
+        },
+        type_headings: params.type_headings.clone().unwrap_or_default(),
+        include_keys: params.include_keys.unwrap_or(defaults.include_keys),
+        include_links: params.include_links.unwrap_or(defaults.include_links),
+        line_template: params.line_template.clone(),
+        exclude_labels: params.exclude_labels.clone().unwrap_or_default(),
+        include_subtasks: params.include_subtasks.unwrap_or(defaults.include_subtasks),
+    })
+}
+
+/// Borrow a list of tool input strings for the request builders.
+fn str_refs(values: &[String]) -> Vec<&str> {
+    values.iter().map(String::as_str).collect()
+}
@@ -0,0 +1,546 @@
+//! Rendering of Atlassian Document Format (ADF) back to Markdown.
+//!
+//! Reference: https://developer.atlassian.com/cloud/jira/platform/apis/document/structure/
+
+use serde_json::Value;
+
+use crate::jira::date_from_millis;
+
+/// Convert an ADF document (or any ADF node) to Markdown.
+///
+/// Block nodes are separated by blank lines, lists are kept tight and nested
+/// lists are indented under their parent item. Nodes without a Markdown
+/// equivalent (media, panels, status lozenges) are rendered as readable
+/// placeholders instead of being dropped.
+pub fn adf_to_markdown(doc: &Value) -> String {
+    let nodes = match node_type(doc) {
+        "doc" => children(doc),
+        _ => std::slice::from_ref(doc),
+    };
+    render_blocks(nodes).trim_end().to_string()
+}
+
+fn node_type(node: &Value) -> &str {
+    node.get("type").and_then(Value::as_str).unwrap_or("")
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+fn attr<'a>(node: &'a Value, name: &str) -> Option<&'a Value> {
+    node.get("attrs").and_then(|a| a.get(name))
+}
+
+fn attr_str<'a>(node: &'a Value, name: &str) -> Option<&'a str> {
+    attr(node, name)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+}
+
+fn is_inline(node: &Value) -> bool {
+    matches!(
+        node_type(node),
+        "text"
+            | "hardBreak"
+            | "mention"
+            | "emoji"
+            | "date"
+            | "status"
+            | "inlineCard"
+            | "mediaInline"
+    )
+}
+
+/// Render a sequence of block nodes separated by blank lines.
+///
+/// Stray inline nodes (e.g. the content of a `taskItem`) are grouped into a
+/// single paragraph.
+fn render_blocks(nodes: &[Value]) -> String {
+    render_block_list(nodes).join("\n\n")
+}
+
+fn render_block_list(nodes: &[Value]) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut inline = String::new();
+
+    for node in nodes {
+        if is_inline(node) {
+            inline.push_str(&render_inline(node));
+            continue;
+        }
+        if !inline.is_empty() {
+            blocks.push(std::mem::take(&mut inline));
+        }
+        let block = render_block(node);
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+    }
+    if !inline.is_empty() {
+        blocks.push(inline);
+    }
+
+    blocks
+}
+
+fn render_block(node: &Value) -> String {
+    match node_type(node) {
+        "paragraph" => render_inlines(children(node)),
+        "heading" => {
+            let level = attr(node, "level")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6) as usize;
+            format!("{} {}", "#".repeat(level), render_inlines(children(node)))
+        }
+        "bulletList" => render_list(children(node), |_| "- ".to_string()),
+        "orderedList" => {
+            let start = attr(node, "order").and_then(Value::as_u64).unwrap_or(1);
+            render_list(children(node), |i| format!("{}. ", start + i as u64))
+        }
+        "taskList" => render_list(children(node), |_| String::new()),
+        "decisionList" => render_list(children(node), |_| "- ".to_string()),
+        "codeBlock" => {
+            let language = attr_str(node, "language").unwrap_or("");
+            let code: String = children(node)
+                .iter()
+                .filter_map(|n| n.get("text").and_then(Value::as_str))
+                .collect();
+            format!("```{}\n{}\n```", language, code.trim_end_matches('\n'))
+        }
+        "blockquote" => prefix_lines(&render_blocks(children(node)), "> "),
+        "panel" => {
+            let label = attr_str(node, "panelType").map(capitalize);
+            let body = render_blocks(children(node));
+            let quoted = match label {
+                Some(label) => format!("**{}:** {}", label, body),
+                None => body,
+            };
+            prefix_lines(&quoted, "> ")
+        }
+        "expand" | "nestedExpand" => {
+            let body = render_blocks(children(node));
+            match attr_str(node, "title") {
+                Some(title) => format!("**{}**\n\n{}", title, body),
+                None => body,
+            }
+        }
+        "rule" => "---".to_string(),
+        "table" => render_table(node),
+        "mediaSingle" | "mediaGroup" => children(node)
+            .iter()
+            .map(render_media)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "media" => render_media(node),
+        _ => render_blocks(children(node)),
+    }
+}
+
+/// Render list items with the given marker, indenting continuation lines and
+/// nested lists under the item's text.
+fn render_list(items: &[Value], marker: impl Fn(usize) -> String) -> String {
+    let mut lines = Vec::new();
+    let mut index = 0;
+
+    for item in items {
+        match node_type(item) {
+            // ADF nests task lists as siblings of the items they belong to
+            "taskList" => lines.push(prefix_lines(&render_block(item), "  ")),
+            item_type => {
+                let marker = match item_type {
+                    "taskItem" if attr_str(item, "state") == Some("DONE") => "- [x] ".to_string(),
+                    "taskItem" => "- [ ] ".to_string(),
+                    _ => marker(index),
+                };
+                let body = render_block_list(children(item)).join("\n");
+                let padding = " ".repeat(marker.chars().count());
+                lines.push(format!("{}{}", marker, indent(&body, &padding)));
+                index += 1;
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn render_table(table: &Value) -> String {
+    let rows: Vec<Vec<String>> = children(table)
+        .iter()
+        .map(|row| {
+            children(row)
+                .iter()
+                .map(|cell| {
+                    render_blocks(children(cell))
+                        .replace('|', "\\|")
+                        .replace("\n\n", "<br>")
+                        .replace('\n', "<br>")
+                })
+                .collect()
+        })
+        .collect();
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let format_row = |cells: &[String]| {
+        let padded: Vec<&str> = (0..columns)
+            .map(|i| cells.get(i).map(String::as_str).unwrap_or(""))
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+
+    let mut lines = vec![format_row(&rows[0])];
+    lines.push(format!("|{}", " --- |".repeat(columns)));
+    for row in &rows[1..] {
+        lines.push(format_row(row));
+    }
+
+    lines.join("\n")
+}
+
+fn render_media(node: &Value) -> String {
+    let name = attr_str(node, "alt")
+        .or_else(|| attr_str(node, "url"))
+        .or_else(|| attr_str(node, "id"))
+        .unwrap_or("attachment");
+    format!("[media: {}]", name)
+}
+
+fn render_inlines(nodes: &[Value]) -> String {
+    nodes.iter().map(render_inline).collect()
+}
+
+fn render_inline(node: &Value) -> String {
+    match node_type(node) {
+        "text" => {
+            let text = node.get("text").and_then(Value::as_str).unwrap_or("");
+            let marks = node
+                .get("marks")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            apply_marks(text, marks)
+        }
+        "hardBreak" => "\n".to_string(),
+        "mention" => {
+            let text = attr_str(node, "text")
+                .or_else(|| attr_str(node, "id"))
+                .unwrap_or("unknown");
+            if text.starts_with('@') {
+                text.to_string()
+            } else {
+                format!("@{}", text)
+            }
+        }
+        "emoji" => attr_str(node, "text")
+            .or_else(|| attr_str(node, "shortName"))
+            .unwrap_or("")
+            .to_string(),
+        "date" => attr_str(node, "timestamp")
+            .and_then(|ts| ts.parse::<i64>().ok())
+            .map(date_from_millis)
+            .unwrap_or_default(),
+        "status" => format!("[{}]", attr_str(node, "text").unwrap_or("")),
+        "inlineCard" => attr_str(node, "url")
+            .map(|url| format!("<{}>", url))
+            .unwrap_or_default(),
+        "mediaInline" => render_media(node),
+        _ => render_inlines(children(node)),
+    }
+}
+
+/// Wrap text in Markdown syntax for its marks, innermost first.
+fn apply_marks(text: &str, marks: &[Value]) -> String {
+    let has = |name: &str| marks.iter().any(|m| node_type(m) == name);
+    let mut output = text.to_string();
+
+    if has("code") {
+        let fence = if output.contains('`') { "``" } else { "`" };
+        output = format!("{}{}{}", fence, output, fence);
+    }
+    if has("em") {
+        output = format!("*{}*", output);
+    }
+    if has("strong") {
+        output = format!("**{}**", output);
+    }
+    if has("strike") {
+        output = format!("~~{}~~", output);
+    }
+    if let Some(href) = marks
+        .iter()
+        .find(|m| node_type(m) == "link")
+        .and_then(|m| attr_str(m, "href"))
+    {
+        output = format!("[{}]({})", output, href);
+    }
+
+    output
+}
+
+fn prefix_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Indent every line except the first (which follows a list marker).
+fn indent(text: &str, padding: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{}{}", padding, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::markdown_to_adf;
+    use serde_json::json;
+
+    fn doc(content: Value) -> Value {
+        json!({ "type": "doc", "version": 1, "content": content })
+    }
+
+    fn text(value: &str) -> Value {
+        json!({ "type": "text", "text": value })
+    }
+
+    fn paragraph(value: &str) -> Value {
+        json!({ "type": "paragraph", "content": [text(value)] })
+    }
+
+    fn list_item(value: &str) -> Value {
+        json!({ "type": "listItem", "content": [paragraph(value)] })
+    }
+
+    #[test]
+    fn renders_paragraphs_separated_by_blank_line() {
+        let adf = doc(json!([paragraph("First"), paragraph("Second")]));
+        assert_eq!(adf_to_markdown(&adf), "First\n\nSecond");
+    }
+
+    #[test]
+    fn renders_headings_with_level() {
+        let adf = doc(json!([
+            { "type": "heading", "attrs": { "level": 3 }, "content": [text("Details")] }
+        ]));
+        assert_eq!(adf_to_markdown(&adf), "### Details");
+    }
+
+    #[test]
+    fn renders_text_marks() {
+        let adf = doc(json!([{
+            "type": "paragraph",
+            "content": [
+                { "type": "text", "text": "bold", "marks": [{ "type": "strong" }] },
+                text(" "),
+                { "type": "text", "text": "gone", "marks": [{ "type": "strike" }] },
+                text(" "),
+                { "type": "text", "text": "x()", "marks": [{ "type": "code" }] },
+                text(" "),
+                {
+                    "type": "text",
+                    "text": "docs",
+                    "marks": [{ "type": "link", "attrs": { "href": "https://example.com" } }]
+                }
+            ]
+        }]));
+        assert_eq!(
+            adf_to_markdown(&adf),
+            "**bold** ~~gone~~ `x()` [docs](https://example.com)"
+        );
+    }
+
+    #[test]
+    fn renders_nested_lists() {
+        let adf = doc(json!([{
+            "type": "bulletList",
+            "content": [
+                {
+                    "type": "listItem",
+                    "content": [
+                        paragraph("parent"),
+                        {
+                            "type": "orderedList",
+                            "attrs": { "order": 1 },
+                            "content": [list_item("first"), list_item("second")]
+                        }
+                    ]
+                },
+                list_item("sibling")
+            ]
+        }]));
+        assert_eq!(
+            adf_to_markdown(&adf),
+            "- parent\n  1. first\n  2. second\n- sibling"
+        );
+    }
+
+    #[test]
+    fn renders_ordered_list_with_start() {
+        let adf = doc(json!([{
+            "type": "orderedList",
+            "attrs": { "order": 3 },
+            "content": [list_item("three"), list_item("four")]
+        }]));
+        assert_eq!(adf_to_markdown(&adf), "3. three\n4. four");
+    }
+
+    #[test]
+    fn renders_task_lists_with_nesting() {
+        let adf = doc(json!([{
+            "type": "taskList",
+            "attrs": { "localId": "a" },
+            "content": [
+                { "type": "taskItem", "attrs": { "state": "DONE" }, "content": [text("shipped")] },
+                {
+                    "type": "taskList",
+                    "content": [
+                        { "type": "taskItem", "attrs": { "state": "TODO" }, "content": [text("sub")] }
+                    ]
+                },
+                { "type": "taskItem", "attrs": { "state": "TODO" }, "content": [text("pending")] }
+            ]
+        }]));
+        assert_eq!(
+            adf_to_markdown(&adf),
+            "- [x] shipped\n  - [ ] sub\n- [ ] pending"
+        );
+    }
+
+    #[test]
+    fn renders_code_block_with_language() {
+        let adf = doc(json!([{
+            "type": "codeBlock",
+            "attrs": { "language": "rust" },
+            "content": [text("fn main() {}\n")]
+        }]));
+        assert_eq!(adf_to_markdown(&adf), "```rust\nfn main() {}\n```");
+    }
+
+    #[test]
+    fn renders_blockquote_and_rule() {
+        let adf = doc(json!([
+            { "type": "blockquote", "content": [paragraph("quoted"), paragraph("more")] },
+            { "type": "rule" }
+        ]));
+        assert_eq!(adf_to_markdown(&adf), "> quoted\n>\n> more\n\n---");
+    }
+
+    #[test]
+    fn renders_table_as_gfm() {
+        let adf = doc(json!([{
+            "type": "table",
+            "content": [
+                {
+                    "type": "tableRow",
+                    "content": [
+                        { "type": "tableHeader", "content": [paragraph("Name")] },
+                        { "type": "tableHeader", "content": [paragraph("Value")] }
+                    ]
+                },
+                {
+                    "type": "tableRow",
+                    "content": [
+                        { "type": "tableCell", "content": [paragraph("a|b")] },
+                        { "type": "tableCell", "content": [paragraph("1")] }
+                    ]
+                }
+            ]
+        }]));
+        assert_eq!(
+            adf_to_markdown(&adf),
+            "| Name | Value |\n| --- | --- |\n| a\\|b | 1 |"
+        );
+    }
+
+    #[test]
+    fn renders_inline_nodes() {
+        let adf = doc(json!([{
+            "type": "paragraph",
+            "content": [
+                { "type": "mention", "attrs": { "id": "abc", "text": "@Alice" } },
+                text(" "),
+                { "type": "emoji", "attrs": { "shortName": ":smile:", "text": "😄" } },
+                text(" "),
+                { "type": "date", "attrs": { "timestamp": "1735603200000" } },
+                text(" "),
+                { "type": "status", "attrs": { "text": "IN PROGRESS", "color": "blue" } }
+            ]
+        }]));
+        assert_eq!(adf_to_markdown(&adf), "@Alice 😄 2024-12-31 [IN PROGRESS]");
+    }
+
+    #[test]
+    fn renders_media_and_panel_placeholders() {
+        let adf = doc(json!([
+            {
+                "type": "mediaSingle",
+                "content": [{ "type": "media", "attrs": { "id": "f-1", "alt": "screenshot.png" } }]
+            },
+            { "type": "panel", "attrs": { "panelType": "warning" }, "content": [paragraph("Careful")] }
+        ]));
+        assert_eq!(
+            adf_to_markdown(&adf),
+            "[media: screenshot.png]\n\n> **Warning:** Careful"
+        );
+    }
+
+    #[test]
+    fn returns_empty_string_for_empty_document() {
+        assert_eq!(adf_to_markdown(&doc(json!([]))), "");
+    }
+
+    #[test]
+    fn round_trips_markdown_through_adf() {
+        let cases = [
+            "Hello world",
+            "## Overview\n\nsome text",
+            "**bold** and *italic* text",
+            "use `cargo build` to compile",
+            "- item one\n- item two\n- item three",
+            "1. first\n2. second",
+            "- parent\n  - child\n- sibling",
+            "see [the docs](https://example.com) and ~~old~~ text",
+            "> quoted",
+            "```rust\nfn main() {}\n```",
+            "above\n\n---\n\nbelow",
+            "| Name | Value |\n| --- | --- |\n| a | 1 |",
+            "- [x] done\n- [ ] todo",
+        ];
+
+        for markdown in cases {
+            assert_eq!(adf_to_markdown(&markdown_to_adf(markdown)), markdown);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Confirmation tokens for destructive tools.
+//!
+//! A destructive tool called without a token only previews what it would do
+//! and issues a token for that exact action. Calling it again with the token
+//! carries the action out, so nothing is destroyed in a single call.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::jira::random_token;
+
+/// How long a confirmation token stays valid
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Outstanding confirmation tokens, each bound to one action.
+#[derive(Debug, Default)]
+pub struct Confirmations {
+    pending: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl Confirmations {
+    /// Issue a token confirming `action` (e.g. "delete issue PROJ-1").
+    pub fn issue(&self, action: &str) -> String {
+        let token = random_token();
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|_, (_, issued)| issued.elapsed() < CONFIRMATION_TTL);
+        pending.insert(token.clone(), (action.to_string(), Instant::now()));
+        token
+    }
+
+    /// Use up `token`, which must have been issued for `action` within the
+    /// last [`CONFIRMATION_TTL`]. A token is only accepted once.
+    pub fn redeem(&self, token: &str, action: &str) -> anyhow::Result<()> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        match pending.get(token.trim()) {
+            Some((confirmed, issued))
+                if confirmed == action && issued.elapsed() < CONFIRMATION_TTL =>
+            {
+                pending.remove(token.trim());
+                Ok(())
+            }
+            Some((confirmed, _)) if confirmed != action => anyhow::bail!(
+                "The confirmation token was issued for a different action ({}). \
+                 Call the tool without confirmation_token to preview this one.",
+                confirmed
+            ),
+            _ => anyhow::bail!(
+                "Unknown or expired confirmation token. \
+                 Call the tool without confirmation_token to get a new one."
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_confirm_their_action_once() {
+        let confirmations = Confirmations::default();
+        let token = confirmations.issue("delete issue PROJ-1");
+
+        assert!(confirmations.redeem(&token, "delete issue PROJ-2").is_err());
+        assert!(confirmations.redeem(&token, "delete issue PROJ-1").is_ok());
+        assert!(confirmations.redeem(&token, "delete issue PROJ-1").is_err());
+    }
+
+    #[test]
+    fn unknown_and_expired_tokens_are_rejected() {
+        let confirmations = Confirmations::default();
+        assert!(
+            confirmations
+                .redeem("guess", "delete issue PROJ-1")
+                .is_err()
+        );
+
+        let token = confirmations.issue("delete issue PROJ-1");
+        confirmations
+            .pending
+            .lock()
+            .unwrap()
+            .get_mut(&token)
+            .unwrap()
+            .1 = Instant::now() - CONFIRMATION_TTL;
+
+        assert!(confirmations.redeem(&token, "delete issue PROJ-1").is_err());
+    }
+}
@@ -1,4 +1,12 @@
-use crate::jira::{Comment, CommentResponse, CreatedIssue, Issue, SearchResult};
+use super::adf::adf_to_markdown;
+use super::confirmation::CONFIRMATION_TTL;
+use crate::jira::{
+    Attachment, Board, ChangelogEntry, Comment, CommentResponse, Component, CreateMetaFields,
+    CreateMetaIssueType, CreateMetaIssueTypes, CreatedIssue, Field, Issue, IssueLink,
+    IssueLinkTypesResponse, JiraError, Project, ProjectPage, SearchResult, Sprint, Transition,
+    TransitionsResponse, User, Version, VersionProgress, Worklog, WorklogResponse, format_duration,
+    wiki_to_markdown,
+};
 
 pub fn format_search_result(result: &SearchResult) -> String {
     let total = result.total.unwrap_or(result.issues.len() as u32);
@@ -31,7 +39,7 @@ pub fn format_search_result(result: &SearchResult) -> String {
             .fields
             .assignee
             .as_ref()
-            .map(|a| format!("{} ({})", a.display_name, a.account_id.as_deref().unwrap_or("No ID")))
+            .map(|a| format!("{} ({})", a.display_name, a.id().unwrap_or("No ID")))
             .unwrap_or("Unassigned".to_string());
 
         output.push_str(&format!(
@@ -40,6 +48,13 @@ pub fn format_search_result(result: &SearchResult) -> String {
         ));
     }
 
+    if let Some(token) = &result.next_page_token {
+        output.push_str(&format!(
+            "More results available. Pass page_token: {} to fetch the next page.\n",
+            token
+        ));
+    }
+
     output
 }
 
@@ -59,7 +74,7 @@ pub fn format_issue(issue: &Issue) -> String {
         .fields
         .assignee
         .as_ref()
-        .map(|a| format!("{} ({})", a.display_name, a.account_id.as_deref().unwrap_or("No ID")))
+        .map(|a| format!("{} ({})", a.display_name, a.id().unwrap_or("No ID")))
         .unwrap_or("Unassigned".to_string());
     let priority = issue
         .fields
@@ -80,10 +95,33 @@ pub fn format_issue(issue: &Issue) -> String {
         .fields
         .description
         .as_ref()
-        .map(|d| parse_adf_body(d))
+        .map(render_body)
         .unwrap_or_else(|| "No description".to_string());
 
-    format!(
+    let components: Vec<&str> = issue
+        .fields
+        .components
+        .iter()
+        .flatten()
+        .map(|c| c.name.as_str())
+        .collect();
+    let release_fields: String = [
+        ("Components", components),
+        (
+            "Fix Versions",
+            version_names(issue.fields.fix_versions.as_deref()),
+        ),
+        (
+            "Affects Versions",
+            version_names(issue.fields.versions.as_deref()),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, names)| !names.is_empty())
+    .map(|(label, names)| format!("**{}:** {}\n", label, names.join(", ")))
+    .collect();
+
+    let mut output = format!(
         r#"# {} - {}
 
 **Type:** {}
@@ -93,7 +131,7 @@ pub fn format_issue(issue: &Issue) -> String {
 **Created:** {}
 **Updated:** {}
 **URL:** {}
-
+{}
 ### Description
 {}
 "#,
@@ -106,15 +144,72 @@ pub fn format_issue(issue: &Issue) -> String {
         created,
         updated,
         issue.self_url,
+        release_fields,
         description
-    )
+    );
+
+    if let Some(tracking) = &issue.fields.timetracking {
+        let lines: Vec<String> = [
+            ("Original Estimate", tracking.original_estimate_seconds),
+            ("Remaining Estimate", tracking.remaining_estimate_seconds),
+            ("Time Spent", tracking.time_spent_seconds),
+        ]
+        .into_iter()
+        .filter_map(|(label, seconds)| {
+            seconds.map(|s| format!("**{}:** {}", label, format_duration(s)))
+        })
+        .collect();
+
+        if !lines.is_empty() {
+            output.push_str(&format!("\n### Time Tracking\n{}\n", lines.join("\n")));
+        }
+    }
+
+    if let Some(links) = issue.fields.issuelinks.as_deref().filter(|l| !l.is_empty()) {
+        output.push_str(&format!("\n### Links\n{}", format_issue_links(links)));
+    }
+
+    output
+}
+
+/// Links grouped by relationship ("blocks", "is blocked by", ...) in the order
+/// Jira returns them.
+fn format_issue_links(links: &[IssueLink]) -> String {
+    let mut groups: Vec<(&str, Vec<String>)> = Vec::new();
+    for link in links {
+        let Some((relationship, linked)) = link.relationship() else {
+            continue;
+        };
+        let status = linked
+            .fields
+            .status
+            .as_ref()
+            .map(|s| s.name.as_str())
+            .unwrap_or("Unknown");
+        let summary = linked.fields.summary.as_deref().unwrap_or("No summary");
+        let line = format!(
+            "- **{}** [{}] {} (link ID: {})",
+            linked.key, status, summary, link.id
+        );
+
+        match groups.iter_mut().find(|(r, _)| *r == relationship) {
+            Some((_, lines)) => lines.push(line),
+            None => groups.push((relationship, vec![line])),
+        }
+    }
+
+    let mut output = String::new();
+    for (relationship, lines) in groups {
+        output.push_str(&format!("**{}**\n{}\n", relationship, lines.join("\n")));
+    }
+    output
 }
 
 pub fn format_comment(issue_key: &str, comment: &Comment) -> String {
     let author = comment
         .author
         .as_ref()
-        .map(|a| format!("{} ({})", a.display_name, a.account_id.as_deref().unwrap_or("No ID")))
+        .map(|a| format!("{} ({})", a.display_name, a.id().unwrap_or("No ID")))
         .unwrap_or("Unknown".to_string());
     let created = comment.created.as_deref().unwrap_or("Unknown");
 
@@ -129,17 +224,894 @@ pub fn format_comment(issue_key: &str, comment: &Comment) -> String {
     )
 }
 
-pub fn format_children(parent_key: &str, result: &SearchResult) -> String {
-    if result.issues.is_empty() {
-        return format!("No child issues found for {}", parent_key);
+pub fn format_issue_deletion_preview(issue: &Issue, delete_subtasks: bool, token: &str) -> String {
+    let fields = &issue.fields;
+    let issue_type = fields
+        .issue_type
+        .as_ref()
+        .map_or("Unknown", |t| t.name.as_str());
+    let status = fields
+        .status
+        .as_ref()
+        .map_or("Unknown", |s| s.name.as_str());
+    let assignee = fields
+        .assignee
+        .as_ref()
+        .map_or("Unassigned", |a| a.display_name.as_str());
+
+    let mut output = format!(
+        "About to delete **{}** [{}/{}] {}\nAssignee: {}\n",
+        issue.key,
+        issue_type,
+        status,
+        fields.summary.as_deref().unwrap_or("No summary"),
+        assignee
+    );
+    if delete_subtasks {
+        output.push_str("Its sub-tasks will be deleted too.\n");
+    }
+    output.push_str(&confirmation_prompt("delete_issue", token));
+    output
+}
+
+pub fn format_comment_deletion_preview(issue_key: &str, comment: &Comment, token: &str) -> String {
+    let author = comment
+        .author
+        .as_ref()
+        .map_or("Unknown", |a| a.display_name.as_str());
+    let created = comment.created.as_deref().unwrap_or("Unknown");
+    let body = comment
+        .body
+        .as_ref()
+        .map(render_body)
+        .unwrap_or_else(|| "No content".to_string());
+
+    let mut output = format!(
+        "About to delete comment {} on {} by {} ({}):\n> {}\n",
+        comment.id,
+        issue_key,
+        author,
+        created,
+        one_line(&body, 200)
+    );
+    output.push_str(&confirmation_prompt("delete_comment", token));
+    output
+}
+
+fn confirmation_prompt(tool: &str, token: &str) -> String {
+    format!(
+        "\nThis cannot be undone. To go ahead, call {} again with the same arguments and \
+         confirmation_token: {} (valid for {} minutes).",
+        tool,
+        token,
+        CONFIRMATION_TTL.as_secs() / 60
+    )
+}
+
+pub fn format_children(parent_key: &str, result: &SearchResult) -> String {
+    if result.issues.is_empty() {
+        return format!("No child issues found for {}", parent_key);
+    }
+
+    let total = result.total.unwrap_or(result.issues.len() as u32);
+    let mut output = format!(
+        "Found {} child issue(s) for {}:\n\n",
+        total, parent_key
+    );
+
+    for issue in &result.issues {
+        let status = issue
+            .fields
+            .status
+            .as_ref()
+            .map(|s| s.name.as_str())
+            .unwrap_or("Unknown");
+        let issue_type = issue
+            .fields
+            .issue_type
+            .as_ref()
+            .map(|t| t.name.as_str())
+            .unwrap_or("Unknown");
+        let summary = issue
+            .fields
+            .summary
+            .as_deref()
+            .unwrap_or("No summary");
+        let assignee = issue
+            .fields
+            .assignee
+            .as_ref()
+            .map(|a| a.display_name.as_str())
+            .unwrap_or("Unassigned");
+
+        output.push_str(&format!(
+            "- **{}** [{}/{}] {}\n  Assignee: {}\n\n",
+            issue.key, issue_type, status, summary, assignee
+        ));
+    }
+
+    output
+}
+
+pub fn format_create_result(issue: &CreatedIssue) -> String {
+    format!(
+        "Issue created successfully.\n\n**Key:** {}\n**ID:** {}\n**URL:** {}",
+        issue.key, issue.id, issue.self_url
+    )
+}
+
+pub fn format_update_result(issue_key: &str, updated_fields: &[&str]) -> String {
+    if updated_fields.is_empty() {
+        return format!("No fields were updated for {}", issue_key);
+    }
+
+    format!(
+        "Issue {} updated successfully.\n\n**Updated fields:** {}",
+        issue_key,
+        updated_fields.join(", ")
+    )
+}
+
+pub fn format_comments(issue_key: &str, response: &CommentResponse) -> String {
+    if response.comments.is_empty() {
+        return format!("No comments found for {}", issue_key);
+    }
+
+    let mut output = format!(
+        "Comments for {} (showing {}-{} of {}):\n\n",
+        issue_key,
+        response.start_at + 1,
+        response.start_at + response.comments.len() as u32,
+        response.total
+    );
+
+    for comment in &response.comments {
+        output.push_str(&comment_section(comment));
+        output.push_str("\n\n");
+    }
+
+    output
+}
+
+pub fn format_comment_details(issue_key: &str, comment: &Comment) -> String {
+    format!("Comment on {}\n\n{}\n", issue_key, comment_section(comment))
+}
+
+pub fn format_comment_update_result(issue_key: &str, comment: &Comment) -> String {
+    format!(
+        "Comment {} on {} updated successfully\n\n{}\n",
+        comment.id,
+        issue_key,
+        comment_section(comment)
+    )
+}
+
+/// "### Comment <id> by <author> (<created>)" followed by the body, noting
+/// when the comment was edited after it was posted.
+fn comment_section(comment: &Comment) -> String {
+    let author = comment
+        .author
+        .as_ref()
+        .map(|a| format!("{} ({})", a.display_name, a.id().unwrap_or("No ID")))
+        .unwrap_or_else(|| "Unknown".to_string());
+    let created = comment.created.as_deref().unwrap_or("Unknown");
+    let edited = match &comment.updated {
+        Some(updated) if comment.created.as_ref() != Some(updated) => {
+            format!(", edited {}", updated)
+        }
+        _ => String::new(),
+    };
+
+    let body_text = comment
+        .body
+        .as_ref()
+        .map(render_body)
+        .unwrap_or_else(|| "No content".to_string());
+
+    format!(
+        "### Comment {} by {} ({}{})\n{}",
+        comment.id,
+        author,
+        created,
+        edited,
+        body_text.trim()
+    )
+}
+
+pub fn format_worklogs(issue_key: &str, response: &WorklogResponse) -> String {
+    if response.worklogs.is_empty() {
+        return format!("No worklogs found for {}", issue_key);
+    }
+
+    let mut output = format!(
+        "Worklogs for {} (showing {}-{} of {}):\n\n",
+        issue_key,
+        response.start_at + 1,
+        response.start_at + response.worklogs.len() as u32,
+        response.total
+    );
+
+    for worklog in &response.worklogs {
+        let author = worklog
+            .author
+            .as_ref()
+            .map(|a| a.display_name.as_str())
+            .unwrap_or("Unknown");
+        let started = worklog.started.as_deref().unwrap_or("Unknown");
+
+        output.push_str(&format!(
+            "### {} by {} (started {})\n**Worklog ID:** {}\n",
+            format_duration(worklog.time_spent_seconds),
+            author,
+            started,
+            worklog.id
+        ));
+        if let Some(comment) = &worklog.comment {
+            output.push_str(&format!("{}\n", render_body(comment).trim()));
+        }
+        output.push('\n');
+    }
+
+    let total: u64 = response.worklogs.iter().map(|w| w.time_spent_seconds).sum();
+    output.push_str(&format!(
+        "**Total on this page:** {}\n",
+        format_duration(total)
+    ));
+    output
+}
+
+pub fn format_worklog_result(issue_key: &str, worklog: &Worklog, action: &str) -> String {
+    format!(
+        r#"Worklog {} successfully on {}
+
+**Worklog ID:** {}
+**Time Spent:** {}
+**Started:** {}
+"#,
+        action,
+        issue_key,
+        worklog.id,
+        format_duration(worklog.time_spent_seconds),
+        worklog.started.as_deref().unwrap_or("Unknown")
+    )
+}
+
+pub fn format_attachments(issue_key: &str, attachments: &[Attachment]) -> String {
+    if attachments.is_empty() {
+        return format!("No attachments found for {}", issue_key);
+    }
+
+    let mut output = format!("Attachments on {} ({}):\n\n", issue_key, attachments.len());
+    output.push_str("| ID | Filename | Size | Type | Author | Created |\n");
+    output.push_str("|----|----------|------|------|--------|---------|\n");
+
+    for attachment in attachments {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            attachment.id,
+            attachment.filename,
+            format_size(attachment.size),
+            attachment.mime_type.as_deref().unwrap_or("Unknown"),
+            attachment
+                .author
+                .as_ref()
+                .map(|a| a.display_name.as_str())
+                .unwrap_or("Unknown"),
+            attachment.created.as_deref().unwrap_or("Unknown")
+        ));
+    }
+
+    output
+}
+
+pub fn format_attachment_result(issue_key: &str, attachments: &[Attachment]) -> String {
+    let mut output = format!("Attachment uploaded successfully to {}\n\n", issue_key);
+    for attachment in attachments {
+        output.push_str(&format!(
+            "**Attachment ID:** {}\n**Filename:** {}\n**Size:** {}\n",
+            attachment.id,
+            attachment.filename,
+            format_size(attachment.size)
+        ));
+    }
+    output
+}
+
+/// Human-readable byte count, e.g. `1.5 MB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+pub fn format_transitions(issue_key: &str, response: &TransitionsResponse) -> String {
+    if response.transitions.is_empty() {
+        return format!("No transitions available for {}", issue_key);
+    }
+
+    let mut output = format!("Available transitions for {}:\n\n", issue_key);
+
+    for transition in &response.transitions {
+        let target = transition
+            .to
+            .as_ref()
+            .map(|s| s.name.as_str())
+            .unwrap_or("Unknown");
+
+        output.push_str(&format!(
+            "- **{}** (ID: {}) → {}\n",
+            transition.name, transition.id, target
+        ));
+
+        let mut required: Vec<String> = transition
+            .fields
+            .iter()
+            .filter(|(_, field)| field.required)
+            .map(|(id, field)| format!("{} ({})", field.name, id))
+            .collect();
+        required.sort();
+        if !required.is_empty() {
+            output.push_str(&format!("  Required fields: {}\n", required.join(", ")));
+        }
+    }
+
+    output
+}
+
+pub fn format_transition_result(issue_key: &str, transition: &Transition) -> String {
+    let target = transition
+        .to
+        .as_ref()
+        .map(|s| s.name.as_str())
+        .unwrap_or("Unknown");
+
+    format!(
+        "Issue {} transitioned successfully.\n\n**Transition:** {} (ID: {})\n**New status:** {}",
+        issue_key, transition.name, transition.id, target
+    )
+}
+
+/// Longest changed value shown in a history timeline before it is cut off
+const HISTORY_VALUE_LIMIT: usize = 200;
+
+pub fn format_history(issue_key: &str, entries: &[ChangelogEntry]) -> String {
+    if entries.is_empty() {
+        return format!("No matching history found for {}", issue_key);
+    }
+
+    let changes: usize = entries.iter().map(|e| e.items.len()).sum();
+    let mut output = format!("History of {} ({} changes):\n\n", issue_key, changes);
+
+    for entry in entries {
+        let author = entry
+            .author
+            .as_ref()
+            .map(|a| a.display_name.as_str())
+            .unwrap_or("Unknown");
+        output.push_str(&format!("### {} by {}\n", entry.created, author));
+
+        for item in &entry.items {
+            output.push_str(&format!(
+                "- **{}**: {} → {}\n",
+                item.field,
+                history_value(item.from_text.as_deref().or(item.from.as_deref())),
+                history_value(item.to_text.as_deref().or(item.to.as_deref()))
+            ));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Names of the versions in an issue's version field.
+fn version_names(versions: Option<&[Version]>) -> Vec<&str> {
+    versions
+        .unwrap_or_default()
+        .iter()
+        .map(|v| v.name.as_str())
+        .collect()
+}
+
+/// A changed value on one line, shortened if long (e.g. descriptions).
+fn history_value(value: Option<&str>) -> String {
+    let value = value.map(str::trim).filter(|v| !v.is_empty());
+    let Some(value) = value else {
+        return "(none)".to_string();
+    };
+
+    format!("\"{}\"", one_line(value, HISTORY_VALUE_LIMIT))
+}
+
+/// `text` collapsed onto one line and cut off after `limit` characters.
+fn one_line(text: &str, limit: usize) -> String {
+    let single_line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if single_line.chars().count() > limit {
+        let truncated: String = single_line.chars().take(limit).collect();
+        format!("{}…", truncated)
+    } else {
+        single_line
+    }
+}
+
+pub fn format_projects(page: &ProjectPage) -> String {
+    if page.values.is_empty() {
+        return "No projects found".to_string();
+    }
+
+    let mut output = format!(
+        "Projects (showing {}-{} of {}):\n\n",
+        page.start_at + 1,
+        page.start_at + page.values.len() as u32,
+        page.total
+    );
+    for project in &page.values {
+        match &project.project_type_key {
+            Some(project_type) => output.push_str(&format!(
+                "- **{}** {} ({})\n",
+                project.key, project.name, project_type
+            )),
+            None => output.push_str(&format!("- **{}** {}\n", project.key, project.name)),
+        }
+    }
+    if !page.is_last {
+        output.push_str(&format!(
+            "\nMore projects available: use start_at={}",
+            page.start_at + page.values.len() as u32
+        ));
+    }
+    output
+}
+
+/// Most released versions listed by name in project details
+const RELEASED_VERSIONS_SHOWN: usize = 5;
+
+/// Longest project description shown before it is cut off
+const PROJECT_DESCRIPTION_LIMIT: usize = 300;
+
+pub fn format_project(project: &Project, components: &[Component], versions: &[Version]) -> String {
+    let mut output = format!("## {}: {}\n\n", project.key, project.name);
+    if let Some(project_type) = &project.project_type_key {
+        output.push_str(&format!("- **Type:** {}\n", project_type));
+    }
+    if let Some(lead) = &project.lead {
+        output.push_str(&format!("- **Lead:** {}\n", lead.display_name));
+    }
+    if let Some(description) = project
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        output.push_str(&format!(
+            "- **Description:** {}\n",
+            one_line(description, PROJECT_DESCRIPTION_LIMIT)
+        ));
+    }
+
+    let issue_types: Vec<String> = project
+        .issue_types
+        .iter()
+        .map(|t| {
+            if t.subtask {
+                format!("{} (subtask)", t.name)
+            } else {
+                t.name.clone()
+            }
+        })
+        .collect();
+    output.push_str(&format!(
+        "\n### Issue Types\n{}\n",
+        list_or_none(&issue_types)
+    ));
+
+    let components: Vec<String> = components.iter().map(|c| c.name.clone()).collect();
+    output.push_str(&format!(
+        "\n### Components\n{}\n",
+        list_or_none(&components)
+    ));
+
+    output.push_str("\n### Versions\n");
+    let active: Vec<&Version> = versions.iter().filter(|v| !v.archived).collect();
+    let unreleased: Vec<&&Version> = active.iter().filter(|v| !v.released).collect();
+    let released: Vec<&&Version> = active.iter().filter(|v| v.released).collect();
+    if active.is_empty() {
+        output.push_str("None\n");
+    }
+    for version in &unreleased {
+        let due = version
+            .release_date
+            .as_ref()
+            .map(|date| format!(", due {}", date))
+            .unwrap_or_default();
+        output.push_str(&format!("- {} (unreleased{})\n", version.name, due));
+    }
+    if !released.is_empty() {
+        let latest: Vec<&str> = released
+            .iter()
+            .rev()
+            .take(RELEASED_VERSIONS_SHOWN)
+            .map(|v| v.name.as_str())
+            .collect();
+        output.push_str(&format!(
+            "- {} released (latest: {})\n",
+            released.len(),
+            latest.join(", ")
+        ));
+    }
+    output
+}
+
+/// Comma-separated items, or "None".
+fn list_or_none(items: &[String]) -> String {
+    if items.is_empty() {
+        "None".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+pub fn format_users(users: &[User]) -> String {
+    if users.is_empty() {
+        return "No users found".to_string();
+    }
+
+    let mut output = format!("Found {} user(s):\n\n", users.len());
+    output.push_str("| Name | Email | ID |\n");
+    output.push_str("|------|-------|----|\n");
+    for user in users {
+        output.push_str(&format!(
+            "| {} | {} | {} |\n",
+            user.display_name,
+            user.email_address.as_deref().unwrap_or("-"),
+            user.id().unwrap_or("-")
+        ));
+    }
+    output
+}
+
+pub fn format_user(user: &User) -> String {
+    let mut output = format!("**{}**\n", user.display_name);
+    if let Some(email) = &user.email_address {
+        output.push_str(&format!("- **Email:** {}\n", email));
+    }
+    if let Some(account_id) = &user.account_id {
+        output.push_str(&format!("- **Account ID:** {}\n", account_id));
+    }
+    if let Some(name) = &user.name {
+        output.push_str(&format!("- **Username:** {}\n", name));
+    }
+    output
+}
+
+pub fn format_link_types(response: &IssueLinkTypesResponse) -> String {
+    if response.issue_link_types.is_empty() {
+        return "No issue link types are configured".to_string();
+    }
+
+    let mut output = String::from("Issue link types:\n\n");
+    output.push_str("| Name | Outward | Inward |\n");
+    output.push_str("|------|---------|--------|\n");
+    for link_type in &response.issue_link_types {
+        output.push_str(&format!(
+            "| {} | {} | {} |\n",
+            link_type.name, link_type.outward, link_type.inward
+        ));
+    }
+    output
+}
+
+pub fn format_fields(fields: &[&Field]) -> String {
+    if fields.is_empty() {
+        return "No matching fields found".to_string();
+    }
+
+    let mut output = format!("Found {} field(s):\n\n", fields.len());
+    output.push_str("| Name | ID | Type | Custom |\n");
+    output.push_str("|------|----|------|--------|\n");
+    for field in fields {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            field.name,
+            field.id,
+            field.type_name(),
+            if field.custom { "yes" } else { "no" }
+        ));
+    }
+    output
+}
+
+/// Most allowed values listed per field in create metadata
+const ALLOWED_VALUES_LIMIT: usize = 20;
+
+pub fn format_create_issue_types(project_key: &str, issue_types: &CreateMetaIssueTypes) -> String {
+    if issue_types.issue_types.is_empty() {
+        return format!("No issue types can be created in {}", project_key);
+    }
+
+    let mut output = format!("## Issue types in {}\n\n", project_key);
+    output.push_str("| ID | Name | Subtask |\n");
+    output.push_str("|----|------|---------|\n");
+    for issue_type in &issue_types.issue_types {
+        output.push_str(&format!(
+            "| {} | {} | {} |\n",
+            issue_type.id,
+            issue_type.name,
+            if issue_type.subtask { "yes" } else { "no" }
+        ));
+    }
+    output.push_str("\nPass an issue type to get_create_metadata to see its fields.");
+    output
+}
+
+pub fn format_create_fields(
+    project_key: &str,
+    issue_type: &CreateMetaIssueType,
+    fields: &CreateMetaFields,
+) -> String {
+    let mut output = format!("## {} fields in {}\n\n", issue_type.name, project_key);
+    if fields.fields.is_empty() {
+        output.push_str("No fields on the create screen");
+        return output;
+    }
+
+    output.push_str("| Name | ID | Type | Required | Allowed values |\n");
+    output.push_str("|------|----|------|----------|----------------|\n");
+    for field in &fields.fields {
+        let required = if field.needs_value() {
+            "yes"
+        } else if field.required {
+            "has default"
+        } else {
+            "no"
+        };
+        let names = field.allowed_names();
+        let allowed = if names.len() > ALLOWED_VALUES_LIMIT {
+            format!(
+                "{}, ... ({} more)",
+                names[..ALLOWED_VALUES_LIMIT].join(", "),
+                names.len() - ALLOWED_VALUES_LIMIT
+            )
+        } else {
+            names.join(", ")
+        };
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            field.name,
+            field.field_id,
+            field.type_name(),
+            required,
+            allowed
+        ));
+    }
+    output
+}
+
+pub fn format_boards(boards: &[Board]) -> String {
+    if boards.is_empty() {
+        return "No boards found".to_string();
+    }
+
+    let mut output = format!("Found {} board(s):\n\n", boards.len());
+    output.push_str("| ID | Name | Type | Project |\n");
+    output.push_str("|----|------|------|---------|\n");
+    for board in boards {
+        let project = board
+            .location
+            .as_ref()
+            .and_then(|l| l.project_key.as_deref())
+            .unwrap_or("-");
+        output.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            board.id, board.name, board.board_type, project
+        ));
+    }
+    output
+}
+
+pub fn format_sprints(board_id: u64, sprints: &[Sprint]) -> String {
+    if sprints.is_empty() {
+        return format!("No sprints found on board {}", board_id);
+    }
+
+    let mut output = format!("Sprints on board {}:\n\n", board_id);
+    for sprint in sprints {
+        output.push_str(&format!(
+            "- **{}** (ID: {}) [{}] {}\n",
+            sprint.name,
+            sprint.id,
+            sprint.state,
+            sprint_dates(sprint)
+        ));
+        if let Some(goal) = sprint.goal.as_deref().filter(|g| !g.trim().is_empty()) {
+            output.push_str(&format!("  Goal: {}\n", goal));
+        }
+    }
+    output
+}
+
+/// Sprint details with a status breakdown and the sprint's issues.
+pub fn format_sprint(sprint: &Sprint, result: &SearchResult) -> String {
+    let goal = sprint
+        .goal
+        .as_deref()
+        .filter(|g| !g.trim().is_empty())
+        .unwrap_or("No goal");
+    let mut output = format!(
+        "# {} (ID: {})\n\n**State:** {}\n**Dates:** {}\n**Goal:** {}\n",
+        sprint.name,
+        sprint.id,
+        sprint.state,
+        sprint_dates(sprint),
+        goal
+    );
+    if let Some(completed) = &sprint.complete_date {
+        output.push_str(&format!("**Completed:** {}\n", completed));
+    }
+
+    output.push_str(&format!("\n{}", format_issue_list("Issues", result)));
+    output
+}
+
+pub fn format_backlog(board_id: u64, result: &SearchResult) -> String {
+    format_issue_list(&format!("Backlog of board {}", board_id), result)
+}
+
+pub fn format_sprint_result(sprint: &Sprint, action: &str) -> String {
+    format!(
+        "Sprint {} successfully.\n\n**Sprint:** {} (ID: {})\n**State:** {}\n**Dates:** {}",
+        action,
+        sprint.name,
+        sprint.id,
+        sprint.state,
+        sprint_dates(sprint)
+    )
+}
+
+pub fn format_versions(project_key: &str, versions: &[&Version]) -> String {
+    if versions.is_empty() {
+        return format!("No versions found in {}", project_key);
+    }
+
+    let mut output = format!("## Versions in {}\n\n", project_key);
+    output.push_str("| ID | Name | Status | Start | Release |\n");
+    output.push_str("|----|------|--------|-------|---------|\n");
+    for version in versions {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            version.id,
+            version.name,
+            version_status(version),
+            version.start_date.as_deref().unwrap_or("-"),
+            version.release_date.as_deref().unwrap_or("-")
+        ));
+    }
+    output
+}
+
+pub fn format_version(
+    version: &Version,
+    progress: &VersionProgress,
+    result: &SearchResult,
+) -> String {
+    let mut output = format!(
+        "# {} (ID: {})\n\n**Status:** {}\n**Start:** {}\n**Release:** {}\n",
+        version.name,
+        version.id,
+        version_status(version),
+        version.start_date.as_deref().unwrap_or("-"),
+        version.release_date.as_deref().unwrap_or("-")
+    );
+    if let Some(description) = version
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        output.push_str(&format!("**Description:** {}\n", description));
+    }
+
+    output.push_str(&format!("\n{}\n", format_version_progress(progress)));
+    output.push_str(&format_issue_list("Issues", result));
+    output
+}
+
+pub fn format_version_result(version: &Version, action: &str) -> String {
+    format!(
+        "Version {} successfully.\n\n**Version:** {} (ID: {})\n**Status:** {}\n**Release:** {}",
+        action,
+        version.name,
+        version.id,
+        version_status(version),
+        version.release_date.as_deref().unwrap_or("-")
+    )
+}
+
+fn version_status(version: &Version) -> &'static str {
+    match (version.archived, version.released) {
+        (true, _) => "archived",
+        (false, true) => "released",
+        (false, false) => "unreleased",
+    }
+}
+
+/// Done / in progress / to do counts by status category.
+fn format_version_progress(progress: &VersionProgress) -> String {
+    let total = progress.total();
+    let mut output = String::from("### Progress\n");
+    if total == 0 {
+        output.push_str("No issues\n");
+        return output;
+    }
+
+    let counts = [
+        ("Done", progress.done),
+        ("In Progress", progress.in_progress),
+        ("To Do", progress.to_do),
+    ];
+    for (label, count) in counts {
+        output.push_str(&format!(
+            "- {}: {} ({}%)\n",
+            label,
+            count,
+            count * 100 / total
+        ));
+    }
+    output
+}
+
+fn sprint_dates(sprint: &Sprint) -> String {
+    match (&sprint.start_date, &sprint.end_date) {
+        (Some(start), Some(end)) => format!("{} → {}", start, end),
+        (Some(start), None) => format!("from {}", start),
+        (None, Some(end)) => format!("until {}", end),
+        (None, None) => "not scheduled".to_string(),
     }
+}
 
-    let total = result.total.unwrap_or(result.issues.len() as u32);
-    let mut output = format!(
-        "Found {} child issue(s) for {}:\n\n",
-        total, parent_key
-    );
+/// A titled issue list preceded by a count of issues per status.
+fn format_issue_list(title: &str, result: &SearchResult) -> String {
+    let shown = result.issues.len() as u32;
+    let total = result.total.unwrap_or(shown);
+    if total == 0 {
+        return format!("### {}\nNo issues\n", title);
+    }
+
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for issue in &result.issues {
+        let status = issue
+            .fields
+            .status
+            .as_ref()
+            .map(|s| s.name.as_str())
+            .unwrap_or("Unknown");
+        match counts.iter_mut().find(|(name, _)| *name == status) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((status, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut output = String::from("### Status Breakdown\n");
+    for (status, count) in counts {
+        output.push_str(&format!("- {}: {}\n", status, count));
+    }
+    if shown < total {
+        output.push_str(&format!(
+            "(counted over the first {} of {} issues)\n",
+            shown, total
+        ));
+    }
 
+    output.push_str(&format!("\n### {} ({})\n", title, total));
     for issue in &result.issues {
         let status = issue
             .fields
@@ -153,11 +1125,7 @@ pub fn format_children(parent_key: &str, result: &SearchResult) -> String {
             .as_ref()
             .map(|t| t.name.as_str())
             .unwrap_or("Unknown");
-        let summary = issue
-            .fields
-            .summary
-            .as_deref()
-            .unwrap_or("No summary");
+        let summary = issue.fields.summary.as_deref().unwrap_or("No summary");
         let assignee = issue
             .fields
             .assignee
@@ -166,121 +1134,62 @@ pub fn format_children(parent_key: &str, result: &SearchResult) -> String {
             .unwrap_or("Unassigned");
 
         output.push_str(&format!(
-            "- **{}** [{}/{}] {}\n  Assignee: {}\n\n",
+            "- **{}** [{}/{}] {} ({})\n",
             issue.key, issue_type, status, summary, assignee
         ));
     }
-
-    output
-}
-
-pub fn format_create_result(issue: &CreatedIssue) -> String {
-    format!(
-        "Issue created successfully.\n\n**Key:** {}\n**ID:** {}\n**URL:** {}",
-        issue.key, issue.id, issue.self_url
-    )
-}
-
-pub fn format_update_result(issue_key: &str, updated_fields: &[&str]) -> String {
-    if updated_fields.is_empty() {
-        return format!("No fields were updated for {}", issue_key);
-    }
-
-    format!(
-        "Issue {} updated successfully.\n\n**Updated fields:** {}",
-        issue_key,
-        updated_fields.join(", ")
-    )
-}
-
-pub fn format_comments(issue_key: &str, response: &CommentResponse) -> String {
-    if response.comments.is_empty() {
-        return format!("No comments found for {}", issue_key);
-    }
-
-    let mut output = format!(
-        "Comments for {} (showing {}-{} of {}):\n\n",
-        issue_key,
-        response.start_at + 1,
-        response.start_at + response.comments.len() as u32,
-        response.total
-    );
-
-    for comment in &response.comments {
-        let author = comment
-            .author
-            .as_ref()
-            .map(|a| {
-                format!(
-                    "{} ({})",
-                    a.display_name,
-                    a.account_id.as_deref().unwrap_or("No ID")
-                )
-            })
-            .unwrap_or_else(|| "Unknown".to_string());
-        let created = comment.created.as_deref().unwrap_or("Unknown");
-
-        let body_text = comment
-            .body
-            .as_ref()
-            .map(|b| parse_adf_body(b))
-            .unwrap_or_else(|| "No content".to_string());
-
+    if shown < total {
         output.push_str(&format!(
-            "### Comment by {} ({})\n{}\n\n",
-            author,
-            created,
-            body_text.trim()
+            "\nShowing {} of {} issues. Raise max_results to see more.\n",
+            shown, total
         ));
     }
-
     output
 }
 
-fn parse_adf_body(body: &serde_json::Value) -> String {
-    let mut text_output = String::new();
+/// Format a failed tool call. Validation errors are listed field by field so
+/// the caller can correct its input.
+pub fn format_error(action: &str, error: &JiraError) -> String {
+    let JiraError::Validation {
+        status,
+        messages,
+        fields,
+    } = error
+    else {
+        return format!("{}: {}", action, error);
+    };
 
-    if let Some(content) = body.get("content").and_then(|c| c.as_array()) {
-        for node in content {
-            if let Some(node_type) = node.get("type").and_then(|t| t.as_str()) {
-                match node_type {
-                    "paragraph" => {
-                        if let Some(para_content) = node.get("content").and_then(|c| c.as_array()) {
-                            for text_node in para_content {
-                                if let Some(text) = text_node.get("text").and_then(|t| t.as_str()) {
-                                    text_output.push_str(text);
-                                }
-                            }
-                            text_output.push('\n');
-                        }
-                    }
-                    _ => {
-                        // Very basic fallback for other block types
-                        if let Some(para_content) = node.get("content").and_then(|c| c.as_array()) {
-                             for text_node in para_content {
-                                if let Some(text) = text_node.get("text").and_then(|t| t.as_str()) {
-                                    text_output.push_str(text);
-                                }
-                            }
-                             text_output.push('\n');
-                        }
-                    }
-                }
-            }
+    let mut output = format!("{}: Jira rejected the request ({})\n", action, status);
+    for message in messages {
+        output.push_str(&format!("- {}\n", message));
+    }
+    if !fields.is_empty() {
+        output.push_str("\nField errors:\n");
+        for (field, message) in fields {
+            output.push_str(&format!("- {}: {}\n", field, message));
         }
     }
+    output
+}
 
-    if text_output.is_empty() {
+/// Render a rich-text body as Markdown, falling back to a placeholder when it has no content.
+///
+/// Jira Cloud returns Atlassian Document Format; Data Center returns wiki markup strings.
+fn render_body(body: &serde_json::Value) -> String {
+    let markdown = match body.as_str() {
+        Some(wiki) => wiki_to_markdown(wiki),
+        None => adf_to_markdown(body),
+    };
+    if markdown.trim().is_empty() {
         return "No content".to_string();
     }
-    
-    text_output
+    markdown
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::jira::{IssueFields, IssueType, Priority, Status, User};
+    use crate::jira::{IssueFields, IssueType, Priority, Status, StatusCategory, User};
 
     fn create_test_issue(key: &str, summary: &str, status: &str, assignee: &str) -> Issue {
         Issue {
@@ -291,11 +1200,13 @@ mod tests {
                 summary: Some(summary.to_string()),
                 status: Some(Status {
                     name: status.to_string(),
+                    status_category: None,
                 }),
                 assignee: Some(User {
                     display_name: assignee.to_string(),
                     email_address: Some("test@example.com".to_string()),
                     account_id: Some("account-123".to_string()),
+                    name: None,
                 }),
                 priority: Some(Priority {
                     name: "High".to_string(),
@@ -321,6 +1232,7 @@ mod tests {
                     name: "Story".to_string(),
                     subtask: false,
                 }),
+                ..Default::default()
             },
         }
     }
@@ -331,6 +1243,8 @@ mod tests {
             total: Some(2),
             max_results: Some(50),
             start_at: Some(0),
+            next_page_token: None,
+            is_last: None,
             issues: vec![
                 create_test_issue("PROJ-1", "First issue", "Open", "Alice"),
                 create_test_issue("PROJ-2", "Second issue", "In Progress", "Bob"),
@@ -356,6 +1270,8 @@ mod tests {
             total: Some(0),
             max_results: Some(50),
             start_at: Some(0),
+            next_page_token: None,
+            is_last: None,
             issues: vec![],
         };
 
@@ -380,12 +1296,15 @@ mod tests {
                 updated: None,
                 description: None,
                 issue_type: None,
+                ..Default::default()
             },
         };
         let result = SearchResult {
             total: Some(1),
             max_results: Some(50),
             start_at: Some(0),
+            next_page_token: None,
+            is_last: None,
             issues: vec![issue],
         };
 
@@ -431,6 +1350,7 @@ mod tests {
                 updated: None,
                 description: None,
                 issue_type: None,
+                ..Default::default()
             },
         };
 
@@ -446,6 +1366,60 @@ mod tests {
         assert!(output.contains("No description"));
     }
 
+    #[test]
+    fn format_issue_shows_components_and_versions() {
+        let mut issue = create_test_issue("PROJ-8", "Release", "Open", "Alice");
+        issue.fields.components = Some(
+            serde_json::from_value(serde_json::json!([
+                { "id": "1", "name": "API" },
+                { "id": "2", "name": "Web" }
+            ]))
+            .unwrap(),
+        );
+        issue.fields.fix_versions = Some(
+            serde_json::from_value(serde_json::json!([{ "id": "3", "name": "2.0" }])).unwrap(),
+        );
+
+        let output = format_issue(&issue);
+
+        assert!(output.contains("**Components:** API, Web\n"));
+        assert!(output.contains("**Fix Versions:** 2.0\n\n### Description"));
+        assert!(!output.contains("Affects Versions"));
+    }
+
+    #[test]
+    fn format_issue_renders_description_as_markdown() {
+        let mut issue = create_test_issue("PROJ-7", "Lists", "Open", "Alice");
+        issue.fields.description = Some(serde_json::json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                { "type": "heading", "attrs": { "level": 2 }, "content": [{ "type": "text", "text": "Steps" }] },
+                {
+                    "type": "bulletList",
+                    "content": [
+                        { "type": "listItem", "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "one" }] }] },
+                        { "type": "listItem", "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "two" }] }] }
+                    ]
+                }
+            ]
+        }));
+
+        let output = format_issue(&issue);
+
+        assert!(output.contains("## Steps\n\n- one\n- two"));
+    }
+
+    #[test]
+    fn format_issue_renders_wiki_markup_description() {
+        let mut issue = create_test_issue("PROJ-8", "On prem", "Open", "Alice");
+        issue.fields.description = Some(serde_json::json!("h2. Steps\n* *one*\n* two"));
+
+        let output = format_issue(&issue);
+
+        assert!(output.contains("## Steps\n- **one**\n- two"));
+    }
+
     #[test]
     fn format_create_result_shows_key_and_url() {
         let issue = CreatedIssue {
@@ -472,8 +1446,10 @@ mod tests {
                 display_name: "Developer".to_string(),
                 email_address: Some("dev@example.com".to_string()),
                 account_id: Some("account-456".to_string()),
+                name: None,
             }),
             created: Some("2024-01-17T09:00:00.000+0000".to_string()),
+            updated: None,
             body: None,
         };
 
@@ -493,6 +1469,7 @@ mod tests {
                 .to_string(),
             author: None,
             created: None,
+            updated: None,
             body: None,
         };
 
@@ -504,4 +1481,479 @@ mod tests {
         assert!(output.contains("**Created:** Unknown"));
     }
 
+    #[test]
+    fn format_comment_update_result_shows_id_and_edit_time() {
+        let comment = Comment {
+            id: "10103".to_string(),
+            self_url: String::new(),
+            author: None,
+            created: Some("2024-01-17T09:00:00.000+0000".to_string()),
+            updated: Some("2024-01-18T11:30:00.000+0000".to_string()),
+            body: Some(serde_json::json!("Deployed to staging")),
+        };
+
+        let output = format_comment_update_result("PROJ-1", &comment);
+
+        assert_eq!(
+            output,
+            "Comment 10103 on PROJ-1 updated successfully\n\n\
+             ### Comment 10103 by Unknown (2024-01-17T09:00:00.000+0000, \
+             edited 2024-01-18T11:30:00.000+0000)\nDeployed to staging\n"
+        );
+    }
+
+    #[test]
+    fn format_comment_deletion_preview_quotes_comment_and_token() {
+        let comment = Comment {
+            id: "10102".to_string(),
+            self_url: String::new(),
+            author: None,
+            created: Some("2024-01-17T09:00:00.000+0000".to_string()),
+            updated: None,
+            body: Some(serde_json::json!("Buy *cheap* watches\nnow")),
+        };
+
+        let output = format_comment_deletion_preview("PROJ-1", &comment, "abc123");
+
+        assert!(output.starts_with(
+            "About to delete comment 10102 on PROJ-1 by Unknown (2024-01-17T09:00:00.000+0000):\n\
+             > Buy **cheap** watches now\n"
+        ));
+        assert!(output.contains("call delete_comment again with the same arguments"));
+        assert!(output.contains("confirmation_token: abc123 (valid for 5 minutes)"));
+    }
+
+    fn create_test_transitions() -> TransitionsResponse {
+        serde_json::from_value(serde_json::json!({
+            "transitions": [
+                { "id": "11", "name": "Start Progress", "to": { "name": "In Progress" } },
+                {
+                    "id": "31",
+                    "name": "Resolve",
+                    "to": { "name": "Done" },
+                    "fields": {
+                        "resolution": { "required": true, "name": "Resolution" },
+                        "comment": { "required": false, "name": "Comment" }
+                    }
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn format_transitions_lists_targets_and_required_fields() {
+        let output = format_transitions("PROJ-123", &create_test_transitions());
+
+        assert!(output.contains("Available transitions for PROJ-123"));
+        assert!(output.contains("**Start Progress** (ID: 11) → In Progress"));
+        assert!(output.contains("**Resolve** (ID: 31) → Done"));
+        assert!(output.contains("Required fields: Resolution (resolution)"));
+        assert!(!output.contains("Comment (comment)"));
+    }
+
+    #[test]
+    fn format_create_fields_marks_required_fields_and_allowed_values() {
+        let issue_type: CreateMetaIssueType =
+            serde_json::from_value(serde_json::json!({ "id": "1", "name": "Bug" })).unwrap();
+        let fields: CreateMetaFields = serde_json::from_value(serde_json::json!({
+            "fields": [
+                { "fieldId": "summary", "name": "Summary", "required": true, "schema": { "type": "string" } },
+                {
+                    "fieldId": "priority", "name": "Priority", "required": true, "hasDefaultValue": true,
+                    "schema": { "type": "priority" },
+                    "allowedValues": [{ "id": "1", "name": "High" }, { "id": "2", "name": "Low" }]
+                },
+                {
+                    "fieldId": "customfield_1", "name": "Platforms", "required": false,
+                    "schema": { "type": "array", "items": "option" },
+                    "allowedValues": [{ "id": "10", "value": "iOS" }]
+                }
+            ]
+        }))
+        .unwrap();
+
+        let output = format_create_fields("PROJ", &issue_type, &fields);
+
+        assert!(output.starts_with("## Bug fields in PROJ"));
+        assert!(output.contains("| Summary | summary | string | yes |  |"));
+        assert!(output.contains("| Priority | priority | priority | has default | High, Low |"));
+        assert!(output.contains("| Platforms | customfield_1 | array<option> | no | iOS |"));
+    }
+
+    #[test]
+    fn format_users_shows_ids_for_cloud_and_data_center() {
+        let users = vec![
+            User {
+                display_name: "Alice".to_string(),
+                email_address: Some("alice@example.com".to_string()),
+                account_id: Some("abc-123".to_string()),
+                name: None,
+            },
+            User {
+                display_name: "John Doe".to_string(),
+                email_address: None,
+                account_id: None,
+                name: Some("jdoe".to_string()),
+            },
+        ];
+
+        let output = format_users(&users);
+
+        assert!(output.starts_with("Found 2 user(s)"));
+        assert!(output.contains("| Alice | alice@example.com | abc-123 |"));
+        assert!(output.contains("| John Doe | - | jdoe |"));
+        assert_eq!(format_users(&[]), "No users found");
+    }
+
+    #[test]
+    fn format_project_summarises_issue_types_components_and_versions() {
+        let project: Project = serde_json::from_value(serde_json::json!({
+            "id": "10000",
+            "key": "PAY",
+            "name": "Payments",
+            "projectTypeKey": "software",
+            "lead": { "displayName": "Alice" },
+            "issueTypes": [{ "name": "Bug", "subtask": false }, { "name": "Sub-task", "subtask": true }]
+        }))
+        .unwrap();
+        let versions: Vec<Version> = serde_json::from_value(serde_json::json!([
+            { "id": "1", "name": "1.0", "released": true, "archived": true },
+            { "id": "2", "name": "1.1", "released": true },
+            { "id": "3", "name": "1.2", "released": true },
+            { "id": "4", "name": "2.0", "releaseDate": "2025-06-30" }
+        ]))
+        .unwrap();
+
+        let output = format_project(&project, &[], &versions);
+
+        assert!(output.starts_with("## PAY: Payments"));
+        assert!(output.contains("- **Lead:** Alice"));
+        assert!(output.contains("### Issue Types\nBug, Sub-task (subtask)"));
+        assert!(output.contains("### Components\nNone"));
+        assert!(output.contains("- 2.0 (unreleased, due 2025-06-30)"));
+        assert!(output.contains("- 2 released (latest: 1.2, 1.1)"));
+        assert!(!output.contains("1.0"));
+    }
+
+    #[test]
+    fn format_version_counts_progress_by_status_category() {
+        let version: Version = serde_json::from_value(serde_json::json!({
+            "id": "10001", "name": "2.0", "releaseDate": "2025-06-30"
+        }))
+        .unwrap();
+        let categories = [
+            ("PROJ-1", "done"),
+            ("PROJ-2", "done"),
+            ("PROJ-3", "indeterminate"),
+            ("PROJ-4", "new"),
+        ];
+        let issues = categories
+            .into_iter()
+            .map(|(key, category)| {
+                let mut issue = create_test_issue(key, "Work", "Some status", "Alice");
+                issue.fields.status = Some(Status {
+                    name: "Some status".to_string(),
+                    status_category: Some(StatusCategory {
+                        key: category.to_string(),
+                        name: category.to_string(),
+                    }),
+                });
+                issue
+            })
+            .collect();
+        let result = SearchResult {
+            total: None,
+            max_results: Some(100),
+            start_at: None,
+            next_page_token: None,
+            is_last: Some(true),
+            issues,
+        };
+
+        let progress = VersionProgress::from_issues(&result.issues);
+
+        let output = format_version(&version, &progress, &result);
+
+        assert!(output.starts_with("# 2.0 (ID: 10001)"));
+        assert!(output.contains("**Status:** unreleased"));
+        assert!(output.contains("- Done: 2 (50%)\n- In Progress: 1 (25%)\n- To Do: 1 (25%)"));
+        assert!(output.contains("- **PROJ-4**"));
+    }
+
+    #[test]
+    fn format_transitions_handles_no_transitions() {
+        let response = TransitionsResponse {
+            transitions: vec![],
+        };
+
+        let output = format_transitions("PROJ-1", &response);
+
+        assert_eq!(output, "No transitions available for PROJ-1");
+    }
+
+    #[test]
+    fn format_transition_result_shows_new_status() {
+        let response = create_test_transitions();
+
+        let output = format_transition_result("PROJ-123", &response.transitions[1]);
+
+        assert!(output.contains("Issue PROJ-123 transitioned successfully"));
+        assert!(output.contains("**Transition:** Resolve (ID: 31)"));
+        assert!(output.contains("**New status:** Done"));
+    }
+
+    #[test]
+    fn format_search_result_shows_next_page_token() {
+        let result = SearchResult {
+            total: None,
+            max_results: Some(1),
+            start_at: None,
+            issues: vec![create_test_issue("PROJ-1", "First issue", "Open", "Alice")],
+            next_page_token: Some("token-abc".to_string()),
+            is_last: Some(false),
+        };
+
+        let output = format_search_result(&result);
+
+        assert!(output.contains("PROJ-1"));
+        assert!(output.contains("Pass page_token: token-abc"));
+    }
+
+    #[test]
+    fn format_search_result_omits_token_on_last_page() {
+        let result = SearchResult {
+            total: None,
+            max_results: Some(50),
+            start_at: None,
+            issues: vec![create_test_issue("PROJ-1", "First issue", "Open", "Alice")],
+            next_page_token: None,
+            is_last: Some(true),
+        };
+
+        let output = format_search_result(&result);
+
+        assert!(!output.contains("page_token"));
+    }
+
+    #[test]
+    fn format_error_lists_validation_errors_by_field() {
+        let error = JiraError::Validation {
+            status: reqwest::StatusCode::BAD_REQUEST,
+            messages: vec!["Issue type is invalid".to_string()],
+            fields: [
+                (
+                    "priority".to_string(),
+                    "Priority name 'Urgent' is not valid".to_string(),
+                ),
+                (
+                    "summary".to_string(),
+                    "You must specify a summary of the issue.".to_string(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        let output = format_error("Failed to create issue", &error);
+
+        assert!(
+            output
+                .starts_with("Failed to create issue: Jira rejected the request (400 Bad Request)")
+        );
+        assert!(output.contains("- Issue type is invalid\n"));
+        assert!(output.contains("Field errors:\n- priority: Priority name 'Urgent' is not valid\n- summary: You must specify"));
+    }
+
+    #[test]
+    fn format_error_uses_display_for_other_errors() {
+        let error = JiraError::NotFound("Issue does not exist".to_string());
+
+        let output = format_error("Failed to get issue", &error);
+
+        assert_eq!(
+            output,
+            "Failed to get issue: Not found (404 Not Found): Issue does not exist"
+        );
+    }
+
+    #[test]
+    fn format_issue_shows_time_tracking() {
+        let mut issue = create_test_issue("PROJ-1", "Tracked", "Open", "Alice");
+        issue.fields.timetracking = Some(crate::jira::TimeTracking {
+            original_estimate_seconds: Some(2 * 8 * 3600),
+            remaining_estimate_seconds: Some(4 * 3600),
+            time_spent_seconds: Some(5400),
+        });
+
+        let output = format_issue(&issue);
+
+        assert!(output.contains("### Time Tracking"));
+        assert!(output.contains("**Original Estimate:** 2d"));
+        assert!(output.contains("**Remaining Estimate:** 4h"));
+        assert!(output.contains("**Time Spent:** 1h 30m"));
+    }
+
+    #[test]
+    fn format_history_renders_timeline() {
+        let entries: Vec<ChangelogEntry> = serde_json::from_value(serde_json::json!([{
+            "id": "1",
+            "author": { "displayName": "Alice", "accountId": "alice-id" },
+            "created": "2025-01-10T09:00:00.000+0000",
+            "items": [
+                { "field": "status", "fromString": "To Do", "toString": "In Progress" },
+                { "field": "assignee", "from": null, "toString": "Bob" },
+                { "field": "description", "fromString": "x".repeat(300), "toString": "short\nnew text" }
+            ]
+        }]))
+        .unwrap();
+
+        let output = format_history("PROJ-1", &entries);
+
+        assert!(output.contains("History of PROJ-1 (3 changes)"));
+        assert!(output.contains("### 2025-01-10T09:00:00.000+0000 by Alice"));
+        assert!(output.contains("- **status**: \"To Do\" → \"In Progress\""));
+        assert!(output.contains("- **assignee**: (none) → \"Bob\""));
+        assert!(output.contains(&format!("\"{}…\" → \"short new text\"", "x".repeat(200))));
+        assert_eq!(
+            format_history("PROJ-1", &[]),
+            "No matching history found for PROJ-1"
+        );
+    }
+
+    #[test]
+    fn format_sprint_shows_goal_dates_and_status_breakdown() {
+        let sprint: Sprint = serde_json::from_value(serde_json::json!({
+            "id": 12,
+            "name": "Sprint 3",
+            "state": "active",
+            "startDate": "2025-01-06T09:00:00.000Z",
+            "endDate": "2025-01-20T09:00:00.000Z",
+            "goal": "Ship search"
+        }))
+        .unwrap();
+        let result = SearchResult {
+            total: Some(4),
+            max_results: Some(3),
+            start_at: Some(0),
+            next_page_token: None,
+            is_last: Some(false),
+            issues: vec![
+                create_test_issue("PROJ-1", "First", "Done", "Alice"),
+                create_test_issue("PROJ-2", "Second", "In Progress", "Bob"),
+                create_test_issue("PROJ-3", "Third", "Done", "Alice"),
+            ],
+        };
+
+        let output = format_sprint(&sprint, &result);
+
+        assert!(output.contains("# Sprint 3 (ID: 12)"));
+        assert!(output.contains("**State:** active"));
+        assert!(output.contains("**Dates:** 2025-01-06T09:00:00.000Z → 2025-01-20T09:00:00.000Z"));
+        assert!(output.contains("**Goal:** Ship search"));
+        assert!(output.contains("### Status Breakdown\n- Done: 2\n- In Progress: 1\n"));
+        assert!(output.contains("(counted over the first 3 of 4 issues)"));
+        assert!(output.contains("- **PROJ-2** [Story/In Progress] Second (Bob)"));
+        assert!(output.contains("Showing 3 of 4 issues"));
+    }
+
+    #[test]
+    fn format_boards_lists_project() {
+        let boards: Vec<Board> = serde_json::from_value(serde_json::json!([
+            { "id": 1, "name": "PROJ board", "type": "scrum", "location": { "projectKey": "PROJ" } },
+            { "id": 2, "name": "Ops", "type": "kanban" }
+        ]))
+        .unwrap();
+
+        let output = format_boards(&boards);
+
+        assert!(output.contains("| 1 | PROJ board | scrum | PROJ |"));
+        assert!(output.contains("| 2 | Ops | kanban | - |"));
+    }
+
+    #[test]
+    fn format_issue_groups_links_by_relationship() {
+        let mut issue = create_test_issue("PROJ-1", "Linked", "Open", "Alice");
+        issue.fields.issuelinks = Some(
+            serde_json::from_value(serde_json::json!([
+                {
+                    "id": "1",
+                    "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                    "outwardIssue": {
+                        "id": "2", "key": "PROJ-2", "self": "https://example.atlassian.net/rest/api/3/issue/2",
+                        "fields": { "summary": "Second", "status": { "name": "To Do" } }
+                    }
+                },
+                {
+                    "id": "2",
+                    "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                    "inwardIssue": {
+                        "id": "3", "key": "PROJ-3", "self": "https://example.atlassian.net/rest/api/3/issue/3",
+                        "fields": { "summary": "Third", "status": { "name": "Done" } }
+                    }
+                },
+                {
+                    "id": "3",
+                    "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                    "outwardIssue": {
+                        "id": "4", "key": "PROJ-4", "self": "https://example.atlassian.net/rest/api/3/issue/4",
+                        "fields": { "summary": "Fourth" }
+                    }
+                }
+            ]))
+            .unwrap(),
+        );
+
+        let output = format_issue(&issue);
+
+        assert!(output.contains("### Links"));
+        assert!(output.contains(
+            "**blocks**\n- **PROJ-2** [To Do] Second (link ID: 1)\n- **PROJ-4** [Unknown] Fourth (link ID: 3)\n"
+        ));
+        assert!(output.contains("**is blocked by**\n- **PROJ-3** [Done] Third (link ID: 2)"));
+    }
+
+    #[test]
+    fn format_worklogs_lists_durations_and_total() {
+        let response: WorklogResponse = serde_json::from_value(serde_json::json!({
+            "startAt": 0,
+            "maxResults": 50,
+            "total": 2,
+            "worklogs": [
+                { "id": "1", "author": { "displayName": "Alice" }, "started": "2024-01-05T09:30:00.000+0000", "timeSpentSeconds": 3600, "comment": "Pairing" },
+                { "id": "2", "started": "2024-01-06T09:30:00.000+0000", "timeSpentSeconds": 1800 }
+            ]
+        }))
+        .unwrap();
+
+        let output = format_worklogs("PROJ-1", &response);
+
+        assert!(output.contains("### 1h by Alice (started 2024-01-05T09:30:00.000+0000)"));
+        assert!(output.contains("**Worklog ID:** 2"));
+        assert!(output.contains("Pairing"));
+        assert!(output.contains("**Total on this page:** 1h 30m"));
+    }
+
+    #[test]
+    fn format_attachments_renders_table() {
+        let attachments: Vec<Attachment> = serde_json::from_value(serde_json::json!([{
+            "id": "10001",
+            "filename": "build.log",
+            "author": { "displayName": "Alice" },
+            "created": "2024-01-05T09:30:00.000+0000",
+            "size": 1536,
+            "mimeType": "text/plain"
+        }]))
+        .unwrap();
+
+        let output = format_attachments("PROJ-1", &attachments);
+
+        assert!(output.contains("| 10001 | build.log | 1.5 KB | text/plain | Alice |"));
+    }
+
+    #[test]
+    fn format_size_picks_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2 * 1024 * 1024), "2.0 MB");
+    }
 }
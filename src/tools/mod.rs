@@ -1,5 +1,10 @@
+mod adf;
+mod confirmation;
 mod formatters;
 mod params;
+mod release_notes;
 
+pub use confirmation::Confirmations;
 pub use formatters::*;
 pub use params::*;
+pub use release_notes::*;
@@ -1,11 +1,14 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct SearchIssuesParams {
     /// JQL query string (e.g., 'project = PROJ AND status = Open')
     pub jql: String,
-    /// Maximum number of results to return (default: 50, max: 100)
+    /// Maximum number of results to return (default: 50, max: 500). More than 100 results are fetched across several pages.
     pub max_results: Option<u32>,
+    /// Page token returned by a previous search_issues call to fetch the next page
+    pub page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -18,8 +21,22 @@ pub struct GetIssueParams {
 pub struct AddCommentParams {
     /// The issue key (e.g., 'PROJ-123')
     pub issue_key: String,
-    /// The comment text to add to the issue
+    /// The comment text to add to the issue. Supports Markdown (headings, bold, italic, strikethrough, links, bullet/ordered/task lists, blockquotes, tables, inline code, code blocks). Automatically converted to Atlassian Document Format (ADF).
     pub comment: String,
+    /// Post the comment verbatim without Markdown conversion (default: false)
+    pub plain_text: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpdateCommentParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// The comment ID (from get_comments)
+    pub comment_id: String,
+    /// The new comment text, replacing the current text. Supports Markdown like add_comment.
+    pub comment: String,
+    /// Use the text verbatim without Markdown conversion (default: false)
+    pub plain_text: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -28,18 +45,47 @@ pub struct UpdateIssueParams {
     pub issue_key: String,
     /// New summary/title for the issue
     pub summary: Option<String>,
-    /// New description for the issue. Supports Markdown (headings, bold, italic, bullet lists, inline code, code blocks). Automatically converted to Atlassian Document Format (ADF).
+    /// New description for the issue. Supports Markdown (headings, bold, italic, strikethrough, links, bullet/ordered/task lists, blockquotes, tables, inline code, code blocks). Automatically converted to Atlassian Document Format (ADF).
     pub description: Option<String>,
     /// Due date in YYYY-MM-DD format (e.g., '2025-01-31')
     pub due_date: Option<String>,
     /// Priority name (e.g., 'High', 'Medium', 'Low')
     pub priority: Option<String>,
-    /// Assignee's account ID
+    /// Assignee: account ID (Jira Cloud) or username (Jira Data Center), email address, display name, or 'me'
+    #[serde(alias = "assignee")]
     pub assignee_account_id: Option<String>,
     /// Parent issue key for subtasks or epic (e.g., 'EPIC-123')
     pub parent_key: Option<String>,
-    /// Labels to set on the issue
+    /// Labels to set on the issue, replacing the current ones
     pub labels: Option<Vec<String>>,
+    /// Labels to add, keeping the current ones
+    pub add_labels: Option<Vec<String>>,
+    /// Labels to remove
+    pub remove_labels: Option<Vec<String>>,
+    /// Component names to set, replacing the current ones (an empty list clears them)
+    pub components: Option<Vec<String>>,
+    /// Component names to add, keeping the current ones
+    pub add_components: Option<Vec<String>>,
+    /// Component names to remove
+    pub remove_components: Option<Vec<String>>,
+    /// Fix version names to set, replacing the current ones (an empty list clears them)
+    pub fix_versions: Option<Vec<String>>,
+    /// Fix version names to add, keeping the current ones
+    pub add_fix_versions: Option<Vec<String>>,
+    /// Fix version names to remove
+    pub remove_fix_versions: Option<Vec<String>>,
+    /// Affects version names to set, replacing the current ones (an empty list clears them)
+    pub affects_versions: Option<Vec<String>>,
+    /// Affects version names to add, keeping the current ones
+    pub add_affects_versions: Option<Vec<String>>,
+    /// Affects version names to remove
+    pub remove_affects_versions: Option<Vec<String>>,
+    /// Other fields keyed by display name or ID, e.g. {"Story Points": 5, "Team": "Platform", "customfield_10050": ["A", "B"]}. Values are converted to the field's type (number, select option, multi-select, user, date, ...). See list_fields.
+    pub custom_fields: Option<HashMap<String, serde_json::Value>>,
+    /// Values to add to multi-value fields (multi-selects, multi-user pickers, ...), keyed by display name or ID, e.g. {"Platforms": ["iOS"], "Reviewers": "alice-account-id"}. Current values are kept.
+    pub add_custom_fields: Option<HashMap<String, serde_json::Value>>,
+    /// Values to remove from multi-value fields, keyed by display name or ID
+    pub remove_custom_fields: Option<HashMap<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -58,11 +104,12 @@ pub struct CreateIssueParams {
     pub summary: String,
     /// The issue type (e.g., 'Story', 'Bug', 'Task', 'Epic', 'Subtask')
     pub issue_type: String,
-    /// Description of the issue. Supports Markdown (headings, bold, italic, bullet lists, inline code, code blocks). Automatically converted to Atlassian Document Format (ADF).
+    /// Description of the issue. Supports Markdown (headings, bold, italic, strikethrough, links, bullet/ordered/task lists, blockquotes, tables, inline code, code blocks). Automatically converted to Atlassian Document Format (ADF).
     pub description: Option<String>,
     /// Priority name (e.g., 'High', 'Medium', 'Low')
     pub priority: Option<String>,
-    /// Assignee's account ID
+    /// Assignee: account ID (Jira Cloud) or username (Jira Data Center), email address, display name, or 'me'
+    #[serde(alias = "assignee")]
     pub assignee_account_id: Option<String>,
     /// Parent issue key (e.g., 'EPIC-123' for stories, or parent story for subtasks)
     pub parent_key: Option<String>,
@@ -70,6 +117,24 @@ pub struct CreateIssueParams {
     pub labels: Option<Vec<String>>,
     /// Due date in YYYY-MM-DD format (e.g., '2025-01-31')
     pub due_date: Option<String>,
+    /// Component names (e.g., ['Backend', 'API']). See get_project.
+    pub components: Option<Vec<String>>,
+    /// Fix version names (e.g., ['2.0']). See get_project.
+    pub fix_versions: Option<Vec<String>>,
+    /// Affects version names, for bugs (e.g., ['1.4'])
+    pub affects_versions: Option<Vec<String>>,
+    /// Other fields keyed by display name or ID, e.g. {"Story Points": 5, "Team": "Platform", "customfield_10050": ["A", "B"]}. Values are converted to the field's type (number, select option, multi-select, user, date, ...). See list_fields.
+    pub custom_fields: Option<HashMap<String, serde_json::Value>>,
+    /// Check the fields against the project's create metadata before creating (default: false). Reports missing required fields and values that are not allowed (priority, components, options), with suggestions.
+    pub validate: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetCreateMetadataParams {
+    /// The project key (e.g., 'PROJ')
+    pub project_key: String,
+    /// Issue type name or ID (e.g., 'Bug'). Omit to list the issue types that can be created.
+    pub issue_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -81,3 +146,371 @@ pub struct GetCommentsParams {
     /// Maximum number of comments to return (default: 50, max: 100)
     pub max_results: Option<u32>,
 }
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetCommentParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// The comment ID (from get_comments)
+    pub comment_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListTransitionsParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct TransitionIssueParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// The transition ID (from list_transitions). Either this or 'status' is required.
+    pub transition_id: Option<String>,
+    /// Target status name (e.g., 'In Progress', 'Done'). Used when 'transition_id' is not given.
+    pub status: Option<String>,
+    /// Resolution name to set (e.g., 'Done', "Won't Do")
+    pub resolution: Option<String>,
+    /// Additional fields required by the transition screen, keyed by field ID (e.g., {"customfield_10010": "value"})
+    pub fields: Option<HashMap<String, serde_json::Value>>,
+    /// Comment to add with the transition. Supports Markdown. Automatically converted to Atlassian Document Format (ADF).
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListWorklogsParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// Starting index for pagination (default: 0)
+    pub start_at: Option<u32>,
+    /// Maximum number of worklogs to return (default: 50, max: 1000)
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AddWorklogParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// Time spent in Jira notation (e.g., '1h 30m', '2d', '45m')
+    pub time_spent: String,
+    /// When the work started, ISO 8601 (e.g., '2025-01-15T09:30:00+09:00'). Defaults to now. Without a UTC offset, UTC is assumed.
+    pub started: Option<String>,
+    /// Worklog comment. Supports Markdown.
+    pub comment: Option<String>,
+    /// How to change the remaining estimate: 'auto' (default, reduce by time spent), 'leave', 'new' (set to 'estimate') or 'manual' (reduce by 'estimate')
+    pub adjust_estimate: Option<String>,
+    /// New remaining estimate or amount to reduce by, for 'new' and 'manual' (e.g., '2d')
+    pub estimate: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpdateWorklogParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// The worklog ID (from list_worklogs)
+    pub worklog_id: String,
+    /// New time spent in Jira notation (e.g., '1h 30m')
+    pub time_spent: Option<String>,
+    /// New start time, ISO 8601 (e.g., '2025-01-15T09:30:00+09:00')
+    pub started: Option<String>,
+    /// New worklog comment. Supports Markdown.
+    pub comment: Option<String>,
+    /// How to change the remaining estimate: 'auto' (default), 'leave' or 'new' (set to 'estimate')
+    pub adjust_estimate: Option<String>,
+    /// New remaining estimate, for 'new' (e.g., '2d')
+    pub estimate: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteWorklogParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// The worklog ID (from list_worklogs)
+    pub worklog_id: String,
+    /// How to change the remaining estimate: 'auto' (default, increase by the deleted time), 'leave', 'new' (set to 'estimate') or 'manual' (increase by 'estimate')
+    pub adjust_estimate: Option<String>,
+    /// New remaining estimate or amount to increase by, for 'new' and 'manual' (e.g., '2d')
+    pub estimate: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AddAttachmentParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// Path of a local file to upload. Either this or 'content_base64' is required.
+    pub file_path: Option<String>,
+    /// Base64-encoded file content to upload. Requires 'filename'.
+    pub content_base64: Option<String>,
+    /// Name of the attachment (defaults to the file name of 'file_path')
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListAttachmentsParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DownloadAttachmentParams {
+    /// The attachment ID (from list_attachments)
+    pub attachment_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct LinkIssuesParams {
+    /// The issue the relationship reads from (e.g., 'PROJ-1' in 'PROJ-1 blocks PROJ-2')
+    pub issue_key: String,
+    /// Link type name (e.g., 'Blocks', 'Relates') or relationship (e.g., 'blocks', 'is blocked by'). See list_link_types.
+    pub link_type: String,
+    /// The other issue (e.g., 'PROJ-2')
+    pub other_issue_key: String,
+    /// 'outward' (default: issue_key blocks other_issue_key) or 'inward' (issue_key is blocked by other_issue_key). Implied when link_type is a relationship.
+    pub direction: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteIssueParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// Also delete the issue's sub-tasks (default: false; Jira refuses to delete an issue that has sub-tasks otherwise)
+    pub delete_subtasks: Option<bool>,
+    /// Token returned by a previous call without it, confirming the deletion
+    pub confirmation_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteCommentParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// The comment ID (from get_comments)
+    pub comment_id: String,
+    /// Token returned by a previous call without it, confirming the deletion
+    pub confirmation_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct RemoveIssueLinkParams {
+    /// The link ID (shown under Links by get_issue)
+    pub link_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetIssueHistoryParams {
+    /// The issue key (e.g., 'PROJ-123')
+    pub issue_key: String,
+    /// Only show changes to these fields (e.g., ['status', 'assignee', 'Sprint']). Matches field names or IDs, case-insensitive.
+    pub fields: Option<Vec<String>>,
+    /// Only show changes at or after this time, ISO 8601 (e.g., '2025-01-01' or '2025-01-01T09:00:00Z')
+    pub since: Option<String>,
+    /// Only show changes before this time, ISO 8601. A date includes the whole day.
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListBoardsParams {
+    /// Only boards of this project (e.g., 'PROJ')
+    pub project_key: Option<String>,
+    /// Only boards of this type: 'scrum' or 'kanban'
+    pub board_type: Option<String>,
+    /// Only boards whose name contains this text
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListSprintsParams {
+    /// The board ID (from list_boards)
+    pub board_id: u64,
+    /// Only sprints in these states, comma-separated: 'future', 'active', 'closed' (default: all)
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetSprintParams {
+    /// The sprint ID (from list_sprints). Either this or 'board_id' is required.
+    pub sprint_id: Option<u64>,
+    /// A board ID, to get that board's active sprint
+    pub board_id: Option<u64>,
+    /// Maximum number of issues to return (default: 100, max: 1000)
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetBacklogParams {
+    /// The board ID (from list_boards)
+    pub board_id: u64,
+    /// Maximum number of issues to return (default: 100, max: 1000)
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct MoveIssuesToSprintParams {
+    /// The sprint ID (from list_sprints)
+    pub sprint_id: u64,
+    /// Keys of the issues to move (e.g., ['PROJ-1', 'PROJ-2'])
+    pub issue_keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct MoveIssuesToBacklogParams {
+    /// Keys of the issues to move (e.g., ['PROJ-1', 'PROJ-2'])
+    pub issue_keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreateSprintParams {
+    /// The board the sprint belongs to (from list_boards)
+    pub board_id: u64,
+    /// Sprint name (e.g., 'Sprint 7')
+    pub name: String,
+    /// Sprint goal
+    pub goal: Option<String>,
+    /// Planned start, ISO 8601 (e.g., '2025-01-06' or '2025-01-06T09:00:00+09:00')
+    pub start_date: Option<String>,
+    /// Planned end, ISO 8601 (default: two weeks after 'start_date')
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StartSprintParams {
+    /// The sprint ID (from list_sprints)
+    pub sprint_id: u64,
+    /// Start, ISO 8601 (default: now)
+    pub start_date: Option<String>,
+    /// End, ISO 8601 (default: the sprint's planned end, or two weeks after the start)
+    pub end_date: Option<String>,
+    /// Sprint goal
+    pub goal: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CloseSprintParams {
+    /// The sprint ID (from list_sprints)
+    pub sprint_id: u64,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListFieldsParams {
+    /// Only fields whose name or ID contains this text (case-insensitive)
+    pub query: Option<String>,
+    /// Only custom fields (default: false)
+    pub custom_only: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListProjectsParams {
+    /// Only projects whose key or name contains this text (case-insensitive)
+    pub query: Option<String>,
+    /// Starting index for pagination (default: 0)
+    pub start_at: Option<u32>,
+    /// Maximum number of projects to return (default: 50, max: 100)
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetProjectParams {
+    /// The project key (e.g., 'PROJ')
+    pub project_key: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListVersionsParams {
+    /// The project key (e.g., 'PROJ')
+    pub project_key: String,
+    /// Include archived versions (default: false)
+    pub include_archived: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetVersionParams {
+    /// The version ID (from list_versions)
+    pub version_id: String,
+    /// Maximum number of issues to list (default: 100, max: 1000); progress counts all issues
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreateVersionParams {
+    /// The project key (e.g., 'PROJ')
+    pub project_key: String,
+    /// Version name (e.g., '2.1.0')
+    pub name: String,
+    /// Version description
+    pub description: Option<String>,
+    /// Start date in YYYY-MM-DD format
+    pub start_date: Option<String>,
+    /// Planned release date in YYYY-MM-DD format
+    pub release_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ReleaseVersionParams {
+    /// The version ID (from list_versions)
+    pub version_id: String,
+    /// Release date in YYYY-MM-DD format (default: today)
+    pub release_date: Option<String>,
+    /// ID of the version to move unresolved issues to before releasing (e.g., the next version)
+    pub move_unresolved_to: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ArchiveVersionParams {
+    /// The version ID (from list_versions)
+    pub version_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct MoveVersionIssuesParams {
+    /// ID of the version to move unresolved issues from
+    pub version_id: String,
+    /// ID of the version to move them to
+    pub target_version_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GenerateReleaseNotesParams {
+    /// Fix version name or ID to write release notes for (e.g., '1.2.0')
+    pub fix_version: Option<String>,
+    /// Project key to scope a fix version name to (e.g., 'PROJ')
+    pub project_key: Option<String>,
+    /// JQL selecting the issues instead of a fix version
+    pub jql: Option<String>,
+    /// Title of the release notes (default: 'Release Notes' plus the fix version)
+    pub title: Option<String>,
+    /// How to group issues: 'type' (default), 'component' or 'type_and_component'
+    pub group_by: Option<String>,
+    /// Section headings by issue type, e.g. {"Bug": "Fixes"} (defaults: Story and New Feature
+    /// to Features, Improvement to Improvements, Bug to Bug Fixes, Task to Tasks)
+    pub type_headings: Option<HashMap<String, String>>,
+    /// Include issue keys (default: true)
+    pub include_keys: Option<bool>,
+    /// Link issue keys to Jira (default: false)
+    pub include_links: Option<bool>,
+    /// Line per issue, with {key}, {summary}, {type}, {status}, {assignee}, {components} and
+    /// {link} placeholders (e.g., '- {summary} ({key}, {assignee})')
+    pub line_template: Option<String>,
+    /// Leave out issues with any of these labels (e.g., ['internal'])
+    pub exclude_labels: Option<Vec<String>>,
+    /// Include sub-tasks (default: false)
+    pub include_subtasks: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SearchUsersParams {
+    /// Text matched against display names, email addresses and usernames (e.g., 'alice' or 'alice@example.com')
+    pub query: String,
+    /// Maximum number of users to return (default: 20, max: 100)
+    pub max_results: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct FindAssignableUsersParams {
+    /// Project key (e.g., 'PROJ'). Either this or 'issue_key' is required.
+    pub project_key: Option<String>,
+    /// Issue key (e.g., 'PROJ-123'). Either this or 'project_key' is required.
+    pub issue_key: Option<String>,
+    /// Only users whose name, email address or username matches this text
+    pub query: Option<String>,
+    /// Maximum number of users to return (default: 20, max: 100)
+    pub max_results: Option<u32>,
+}
@@ -0,0 +1,430 @@
+//! Markdown release notes built from the issues in a fix version.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::jira::Issue;
+
+/// Issue types with a heading of their own by default, in the order the
+/// sections appear. Other types are headed by their name and follow these.
+const DEFAULT_TYPE_HEADINGS: &[(&str, &str)] = &[
+    ("Story", "Features"),
+    ("New Feature", "Features"),
+    ("Improvement", "Improvements"),
+    ("Bug", "Bug Fixes"),
+    ("Task", "Tasks"),
+];
+
+/// Heading for issues without a component when grouping by component
+const NO_COMPONENT_HEADING: &str = "Other";
+
+/// How issues are split into sections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupBy {
+    /// A section per issue type heading
+    #[default]
+    Type,
+    /// A section per component; issues with several components appear in each
+    Component,
+    /// A section per issue type heading, split into a subsection per component
+    TypeAndComponent,
+}
+
+impl GroupBy {
+    /// Parse "type", "component" or "type_and_component".
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "type" => Ok(Self::Type),
+            "component" => Ok(Self::Component),
+            "type_and_component" => Ok(Self::TypeAndComponent),
+            other => anyhow::bail!(
+                "Unknown group_by '{}': use type, component or type_and_component",
+                other
+            ),
+        }
+    }
+}
+
+/// What goes into the release notes and how each issue is written.
+#[derive(Debug, Clone)]
+pub struct ReleaseNotesOptions {
+    pub title: String,
+    pub group_by: GroupBy,
+    /// Section headings by issue type name, overriding the defaults
+    pub type_headings: HashMap<String, String>,
+    pub include_keys: bool,
+    pub include_links: bool,
+    /// Line per issue with `{key}`, `{summary}`, `{type}`, `{status}`,
+    /// `{assignee}`, `{components}` and `{link}` placeholders
+    pub line_template: Option<String>,
+    /// Issues with any of these labels (case-insensitive) are left out
+    pub exclude_labels: Vec<String>,
+    pub include_subtasks: bool,
+}
+
+impl Default for ReleaseNotesOptions {
+    fn default() -> Self {
+        Self {
+            title: "Release Notes".to_string(),
+            group_by: GroupBy::default(),
+            type_headings: HashMap::new(),
+            include_keys: true,
+            include_links: false,
+            line_template: None,
+            exclude_labels: Vec::new(),
+            include_subtasks: false,
+        }
+    }
+}
+
+impl ReleaseNotesOptions {
+    fn type_heading(&self, issue_type: &str) -> String {
+        if let Some(heading) = self
+            .type_headings
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(issue_type))
+            .map(|(_, heading)| heading)
+        {
+            return heading.clone();
+        }
+        DEFAULT_TYPE_HEADINGS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(issue_type))
+            .map_or_else(
+                || issue_type.to_string(),
+                |(_, heading)| heading.to_string(),
+            )
+    }
+
+    /// Sections for known types come first, in the default order; the rest
+    /// follow alphabetically.
+    fn type_rank(&self, heading: &str) -> usize {
+        DEFAULT_TYPE_HEADINGS
+            .iter()
+            .position(|(name, _)| self.type_heading(name) == heading)
+            .unwrap_or(usize::MAX)
+    }
+
+    fn is_excluded(&self, issue: &Issue) -> bool {
+        let is_subtask = issue.fields.issue_type.as_ref().is_some_and(|t| t.subtask);
+        let has_excluded_label = issue.fields.labels.iter().flatten().any(|label| {
+            self.exclude_labels
+                .iter()
+                .any(|excluded| excluded.trim().eq_ignore_ascii_case(label))
+        });
+        (is_subtask && !self.include_subtasks) || has_excluded_label
+    }
+
+    fn render_line(&self, issue: &Issue, link: Option<&str>) -> String {
+        let template = match &self.line_template {
+            Some(template) => template.as_str(),
+            None => match (self.include_keys, self.include_links && link.is_some()) {
+                (true, true) => "- {summary} ([{key}]({link}))",
+                (true, false) => "- {summary} ({key})",
+                (false, true) => "- [{summary}]({link})",
+                (false, false) => "- {summary}",
+            },
+        };
+
+        let fields = &issue.fields;
+        fill_template(template, |name| {
+            Some(match name {
+                "key" => issue.key.clone(),
+                "summary" => fields
+                    .summary
+                    .as_deref()
+                    .unwrap_or("No summary")
+                    .to_string(),
+                "type" => issue_type(issue).to_string(),
+                "status" => fields
+                    .status
+                    .as_ref()
+                    .map_or("Unknown", |s| s.name.as_str())
+                    .to_string(),
+                "assignee" => fields
+                    .assignee
+                    .as_ref()
+                    .map_or("Unassigned", |a| a.display_name.as_str())
+                    .to_string(),
+                "components" => {
+                    let components: Vec<&str> = fields
+                        .components
+                        .iter()
+                        .flatten()
+                        .map(|c| c.name.as_str())
+                        .collect();
+                    components.join(", ")
+                }
+                "link" => link.unwrap_or("").to_string(),
+                _ => return None,
+            })
+        })
+    }
+}
+
+/// Render release notes for `issues` in the order given, with `browse_url`
+/// supplying the link to each issue when links are wanted.
+pub fn render_release_notes(
+    issues: &[Issue],
+    options: &ReleaseNotesOptions,
+    browse_url: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut output = format!("# {}\n", options.title);
+    let included: Vec<&Issue> = issues.iter().filter(|i| !options.is_excluded(i)).collect();
+    if included.is_empty() {
+        output.push_str("\nNo issues\n");
+        return output;
+    }
+
+    let line = |issue: &Issue| {
+        let link = browse_url(&issue.key);
+        options.render_line(issue, link.as_deref())
+    };
+
+    match options.group_by {
+        GroupBy::Type => {
+            for (heading, issues) in by_type(&included, options) {
+                output.push_str(&format!("\n## {}\n\n", heading));
+                for issue in issues {
+                    output.push_str(&format!("{}\n", line(issue)));
+                }
+            }
+        }
+        GroupBy::Component => {
+            for (heading, issues) in by_component(&included) {
+                output.push_str(&format!("\n## {}\n\n", heading));
+                for issue in issues {
+                    output.push_str(&format!("{}\n", line(issue)));
+                }
+            }
+        }
+        GroupBy::TypeAndComponent => {
+            for (heading, issues) in by_type(&included, options) {
+                output.push_str(&format!("\n## {}\n", heading));
+                for (component, issues) in by_component(&issues) {
+                    output.push_str(&format!("\n### {}\n\n", component));
+                    for issue in issues {
+                        output.push_str(&format!("{}\n", line(issue)));
+                    }
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Replace each `{name}` placeholder in `template` with `value(name)` in a
+/// single pass, so placeholders appearing in the values are left alone.
+/// Unknown placeholders are kept as written.
+fn fill_template(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after
+            .find('}')
+            .and_then(|end| Some((end, value(&after[..end])?)))
+        {
+            Some((end, filled)) => {
+                output.push_str(&filled);
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn issue_type(issue: &Issue) -> &str {
+    issue
+        .fields
+        .issue_type
+        .as_ref()
+        .map_or("Other", |t| t.name.as_str())
+}
+
+fn by_type<'a>(
+    issues: &[&'a Issue],
+    options: &ReleaseNotesOptions,
+) -> Vec<(String, Vec<&'a Issue>)> {
+    let mut sections: BTreeMap<(usize, String), Vec<&Issue>> = BTreeMap::new();
+    for issue in issues {
+        let heading = options.type_heading(issue_type(issue));
+        sections
+            .entry((options.type_rank(&heading), heading))
+            .or_default()
+            .push(*issue);
+    }
+    sections
+        .into_iter()
+        .map(|((_, heading), issues)| (heading, issues))
+        .collect()
+}
+
+/// Sections by component name, with issues lacking a component last.
+fn by_component<'a>(issues: &[&'a Issue]) -> Vec<(String, Vec<&'a Issue>)> {
+    let mut sections: BTreeMap<String, Vec<&Issue>> = BTreeMap::new();
+    let mut without_component = Vec::new();
+    for issue in issues {
+        let components = issue.fields.components.as_deref().unwrap_or_default();
+        if components.is_empty() {
+            without_component.push(*issue);
+        }
+        for component in components {
+            sections
+                .entry(component.name.clone())
+                .or_default()
+                .push(issue);
+        }
+    }
+
+    let mut sections: Vec<(String, Vec<&Issue>)> = sections.into_iter().collect();
+    if !without_component.is_empty() {
+        sections.push((NO_COMPONENT_HEADING.to_string(), without_component));
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::{Component, IssueFields, IssueType};
+
+    fn issue(key: &str, issue_type: &str, components: &[&str], labels: &[&str]) -> Issue {
+        Issue {
+            id: key.to_string(),
+            key: key.to_string(),
+            self_url: String::new(),
+            fields: IssueFields {
+                summary: Some(format!("Summary of {}", key)),
+                issue_type: Some(IssueType {
+                    name: issue_type.to_string(),
+                    subtask: issue_type == "Sub-task",
+                }),
+                components: Some(
+                    components
+                        .iter()
+                        .map(|name| Component {
+                            id: name.to_string(),
+                            name: name.to_string(),
+                            description: None,
+                        })
+                        .collect(),
+                ),
+                labels: Some(labels.iter().map(|l| l.to_string()).collect()),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn no_links(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn groups_by_type_in_default_order() {
+        let issues = vec![
+            issue("PROJ-1", "Bug", &[], &[]),
+            issue("PROJ-2", "Spike", &[], &[]),
+            issue("PROJ-3", "Story", &[], &[]),
+            issue("PROJ-4", "Sub-task", &[], &[]),
+            issue("PROJ-5", "Bug", &[], &["Internal"]),
+        ];
+        let options = ReleaseNotesOptions {
+            title: "Release 1.2".to_string(),
+            exclude_labels: vec!["internal".to_string()],
+            ..Default::default()
+        };
+
+        let notes = render_release_notes(&issues, &options, no_links);
+
+        assert_eq!(
+            notes,
+            "# Release 1.2\n\
+             \n## Features\n\n- Summary of PROJ-3 (PROJ-3)\n\
+             \n## Bug Fixes\n\n- Summary of PROJ-1 (PROJ-1)\n\
+             \n## Spike\n\n- Summary of PROJ-2 (PROJ-2)\n"
+        );
+    }
+
+    #[test]
+    fn groups_by_type_and_component_with_custom_headings() {
+        let issues = vec![
+            issue("PROJ-1", "Bug", &["API", "Web"], &[]),
+            issue("PROJ-2", "Bug", &[], &[]),
+            issue("PROJ-3", "Story", &["Web"], &[]),
+        ];
+        let options = ReleaseNotesOptions {
+            group_by: GroupBy::TypeAndComponent,
+            type_headings: HashMap::from([("bug".to_string(), "Fixed".to_string())]),
+            include_keys: false,
+            ..Default::default()
+        };
+
+        let notes = render_release_notes(&issues, &options, no_links);
+
+        assert_eq!(
+            notes,
+            "# Release Notes\n\
+             \n## Features\n\n### Web\n\n- Summary of PROJ-3\n\
+             \n## Fixed\n\n### API\n\n- Summary of PROJ-1\n\
+             \n### Web\n\n- Summary of PROJ-1\n\
+             \n### Other\n\n- Summary of PROJ-2\n"
+        );
+    }
+
+    #[test]
+    fn line_template_fills_placeholders_and_links() {
+        let issues = vec![issue("PROJ-7", "Task", &["API"], &[])];
+        let link = |key: &str| Some(format!("https://example.atlassian.net/browse/{}", key));
+
+        let linked = ReleaseNotesOptions {
+            include_links: true,
+            ..Default::default()
+        };
+        assert!(render_release_notes(&issues, &linked, link).contains(
+            "- Summary of PROJ-7 ([PROJ-7](https://example.atlassian.net/browse/PROJ-7))\n"
+        ));
+
+        let templated = ReleaseNotesOptions {
+            group_by: GroupBy::Component,
+            line_template: Some("* {type}: {summary} [{components}] <{link}>".to_string()),
+            ..Default::default()
+        };
+        assert!(render_release_notes(&issues, &templated, link).contains(
+            "## API\n\n* Task: Summary of PROJ-7 [API] \
+             <https://example.atlassian.net/browse/PROJ-7>\n"
+        ));
+    }
+
+    #[test]
+    fn placeholders_in_issue_data_are_not_filled() {
+        let mut tricky = issue("PROJ-8", "Task", &[], &[]);
+        tricky.fields.summary = Some("Support {key} and {link} in {unknown}".to_string());
+        let link = |key: &str| Some(format!("https://example.atlassian.net/browse/{}", key));
+
+        let options = ReleaseNotesOptions {
+            line_template: Some("- {summary} <{link}> {assignee}".to_string()),
+            ..Default::default()
+        };
+        assert!(render_release_notes(&[tricky], &options, link).contains(
+            "- Support {key} and {link} in {unknown} \
+             <https://example.atlassian.net/browse/PROJ-8> Unassigned\n"
+        ));
+    }
+
+    #[test]
+    fn group_by_names_are_parsed() {
+        assert_eq!(GroupBy::from_name("Component").unwrap(), GroupBy::Component);
+        assert_eq!(
+            GroupBy::from_name("type_and_component").unwrap(),
+            GroupBy::TypeAndComponent
+        );
+        assert!(GroupBy::from_name("priority").is_err());
+    }
+}